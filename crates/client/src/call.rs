@@ -0,0 +1,273 @@
+//! Typed core calls, each submitted and waited for with [`Client::submit_and_wait`].
+use anyhow::Result;
+use filament_hub_core::{
+    criteria::Criteria,
+    rewards::ObjectiveMeasurement,
+    session::SessionCall,
+    voting::{CriteriaVote, DistributionVote},
+    CallMessage,
+    Power,
+    Segment,
+};
+use sov_modules_api::Spec;
+use sov_rollup_interface::da::DaSpec;
+
+use crate::{Client, Inclusion};
+
+impl<S: Spec, Da: DaSpec> Client<S, Da> {
+    pub async fn draft(
+        &self,
+        title: String,
+        description: String,
+        criteria: Criteria,
+        evictions: Vec<S::Address>,
+    ) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::Draft {
+            title,
+            description,
+            criteria,
+            evictions,
+        })
+        .await
+    }
+
+    pub async fn init(&self, campaign_id: u64) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::Init { campaign_id })
+            .await
+    }
+
+    pub async fn propose_criteria(
+        &self,
+        campaign_id: u64,
+        criteria: Criteria,
+    ) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::ProposeCriteria {
+            campaign_id,
+            criteria,
+        })
+        .await
+    }
+
+    pub async fn vote_criteria(
+        &self,
+        campaign_id: u64,
+        vote: CriteriaVote,
+    ) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::VoteCriteria { campaign_id, vote })
+            .await
+    }
+
+    pub async fn confirm_criteria(
+        &self,
+        campaign_id: u64,
+        proposal_id: Option<u64>,
+    ) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::ConfirmCriteria {
+            campaign_id,
+            proposal_id,
+        })
+        .await
+    }
+
+    pub async fn reject_criteria(&self, campaign_id: u64) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::RejectCriteria { campaign_id })
+            .await
+    }
+
+    pub async fn index_campaign(&self, campaign_id: u64) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::IndexCampaign { campaign_id })
+            .await
+    }
+
+    pub async fn post_segment(&self, campaign_id: u64, segment: Segment) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::PostSegment {
+            campaign_id,
+            segment,
+        })
+        .await
+    }
+
+    pub async fn vote_distribution(
+        &self,
+        campaign_id: u64,
+        vote: DistributionVote,
+    ) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::VoteDistribution { campaign_id, vote })
+            .await
+    }
+
+    pub async fn confirm_distribution(&self, campaign_id: u64) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::ConfirmDistribution { campaign_id })
+            .await
+    }
+
+    pub async fn register_indexer(
+        &self,
+        address: S::Address,
+        alias: String,
+    ) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::RegisterIndexer { address, alias })
+            .await
+    }
+
+    pub async fn unregister_indexer(&self, address: S::Address) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::UnregisterIndexer { address })
+            .await
+    }
+
+    pub async fn register_relayer(&self, address: S::Address) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::RegisterRelayer { address })
+            .await
+    }
+
+    pub async fn unregister_relayer(&self, address: S::Address) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::UnregisterRelayer { address })
+            .await
+    }
+
+    pub async fn update_voting_power(
+        &self,
+        address: S::Address,
+        power: Power,
+        eth_block: u64,
+    ) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::UpdateVotingPowerAt {
+            address,
+            power,
+            eth_block,
+        })
+        .await
+    }
+
+    pub async fn update_voting_powers(
+        &self,
+        updates: Vec<(S::Address, Power)>,
+        eth_block: u64,
+    ) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::UpdateVotingPowers { updates, eth_block })
+            .await
+    }
+
+    pub async fn fund_reward_pool(&self, campaign_id: u64, amount: u64) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::FundRewardPool {
+            campaign_id,
+            amount,
+        })
+        .await
+    }
+
+    pub async fn post_objective(
+        &self,
+        campaign_id: u64,
+        measurement: ObjectiveMeasurement,
+    ) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::PostObjective {
+            campaign_id,
+            measurement,
+        })
+        .await
+    }
+
+    pub async fn set_attestation_threshold(&self, threshold: u64) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::SetAttestationThreshold { threshold })
+            .await
+    }
+
+    pub async fn attest_voting_power(
+        &self,
+        address: S::Address,
+        power: Power,
+        eth_block: u64,
+    ) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::AttestVotingPower {
+            address,
+            power,
+            eth_block,
+        })
+        .await
+    }
+
+    pub async fn submit_eth_headers(&self, headers: Vec<Vec<u8>>) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::SubmitEthHeaders { headers })
+            .await
+    }
+
+    pub async fn prove_voting_power(
+        &self,
+        eth_address: String,
+        eth_block: u64,
+        account_proof: Vec<Vec<u8>>,
+        storage_proof: Vec<Vec<u8>>,
+    ) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::ProveVotingPower {
+            eth_address,
+            eth_block,
+            account_proof,
+            storage_proof,
+        })
+        .await
+    }
+
+    pub async fn link_eth_address(
+        &self,
+        eth_address: String,
+        signature: Vec<u8>,
+    ) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::LinkEthAddress {
+            eth_address,
+            signature,
+        })
+        .await
+    }
+
+    pub async fn unlink_eth_address(&self) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::UnlinkEthAddress {}).await
+    }
+
+    pub async fn update_multisig(
+        &self,
+        address: String,
+        owners: Vec<String>,
+        threshold: u64,
+        eth_block: u64,
+        account_proof: Vec<Vec<u8>>,
+    ) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::UpdateMultisig {
+            address,
+            owners,
+            threshold,
+            eth_block,
+            account_proof,
+        })
+        .await
+    }
+
+    pub async fn grant_session_key(
+        &self,
+        key: String,
+        allowed_calls: Vec<SessionCall>,
+        expires_at_slot: u64,
+        signature: Vec<u8>,
+    ) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::GrantSessionKey {
+            key,
+            allowed_calls,
+            expires_at_slot,
+            signature,
+        })
+        .await
+    }
+
+    pub async fn revoke_session_key(&self, key: String) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::RevokeSessionKey { key })
+            .await
+    }
+
+    pub async fn execute_session(&self, key: String, call: CallMessage<S>) -> Result<Inclusion<S>> {
+        self.submit_and_wait(CallMessage::ExecuteSession {
+            key,
+            call: Box::new(call),
+        })
+        .await
+    }
+}
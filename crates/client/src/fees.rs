@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+use sov_modules_api::transaction::PriorityFeeBips;
+
+/// Max fee of a call the client hasn't sent yet.
+pub const DEFAULT_MAX_FEE: u64 = 1_000_000;
+/// Margin added to the fees observed for a call, in basis points.
+pub const FEE_MARGIN_BPS: u64 = 10_000;
+
+/// Estimates the max fee of calls from the fees charged for the same calls before.
+///
+/// The max fee is only a cap, the unused part is refunded, so the estimate errs on the high
+/// side: twice the highest fee observed for the call by default.
+#[derive(Clone, Debug)]
+pub struct FeeEstimator {
+    pub max_priority_fee_bips: PriorityFeeBips,
+    pub default_max_fee: u64,
+    pub margin_bps: u64,
+    /// Highest fee charged per call name.
+    observed: HashMap<&'static str, u64>,
+}
+
+impl Default for FeeEstimator {
+    fn default() -> Self {
+        Self {
+            max_priority_fee_bips: PriorityFeeBips::ZERO,
+            default_max_fee: DEFAULT_MAX_FEE,
+            margin_bps: FEE_MARGIN_BPS,
+            observed: HashMap::new(),
+        }
+    }
+}
+
+impl FeeEstimator {
+    /// Max fee to send the call `name` with.
+    pub fn estimate(&self, name: &str) -> u64 {
+        match self.observed.get(name) {
+            Some(fee) => fee.saturating_add(fee.saturating_mul(self.margin_bps) / 10_000),
+            None => self.default_max_fee,
+        }
+        .max(1)
+    }
+
+    /// Records the fee charged for the call `name`.
+    pub fn observe(&mut self, name: &'static str, fee: u64) {
+        let highest = self.observed.entry(name).or_default();
+        *highest = (*highest).max(fee);
+    }
+}
@@ -0,0 +1,308 @@
+//! Typed client of the hub.
+//!
+//! [`Client`] queries the core module over JSON-RPC and sends its call messages: it keeps track
+//! of the signer's nonce, estimates fees, signs with a standard or an eth key and waits for the
+//! transactions to be included, returning the core events they emitted.
+use std::{marker::PhantomData, ops::Range, time::Duration};
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use filament_hub_core::{CallMessage, Event};
+use filament_hub_stf::RuntimeCall;
+use futures::StreamExt as _;
+use jsonrpsee::{
+    core::client::ClientT as _,
+    rpc_params,
+    ws_client::{WsClient, WsClientBuilder},
+};
+use sha2::Digest as _;
+use sov_ledger_apis::rpc::client::RpcClient;
+use sov_modules_api::{transaction::TxDetails, CredentialId, CryptoSpec, Spec};
+use sov_rollup_interface::{
+    da::DaSpec,
+    rpc::{BatchResponse, QueryMode, SlotResponse, TxResponse},
+    TxHash,
+};
+use tokio::sync::Mutex;
+use tracing::debug;
+
+mod call;
+mod fees;
+pub use fees::{FeeEstimator, DEFAULT_MAX_FEE, FEE_MARGIN_BPS};
+mod query;
+mod signer;
+pub use signer::Signer;
+
+/// Number of slots to wait for a transaction to be included before giving up.
+pub const INCLUSION_TIMEOUT_SLOTS: usize = 10;
+
+/// Transaction included by the rollup.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Inclusion<S: Spec> {
+    pub slot_number: u64,
+    pub nonce: u64,
+    pub hash: TxHash,
+    /// Base fee charged for the gas the transaction used, in gas tokens.
+    pub fee: u64,
+    /// Core events emitted by the transaction. A reverted transaction emits none.
+    pub events: Vec<Event<S>>,
+}
+
+/// Core event as returned by `core_getSlotEvents`.
+#[derive(serde::Deserialize)]
+#[serde(bound = "S::Address: serde::de::DeserializeOwned")]
+struct SlotEvent<S: Spec> {
+    event_number: u64,
+    event: Event<S>,
+}
+
+/// Transaction as returned by `ledger_getTxByHash`, with the fields the client reads.
+#[derive(serde::Deserialize)]
+struct TxReceipt {
+    event_range: Range<u64>,
+    receipt: TxEffect,
+}
+
+/// Outcome of an executed transaction, with the gas it used per dimension.
+#[derive(serde::Deserialize)]
+#[serde(tag = "result", content = "data", rename_all = "snake_case")]
+enum TxEffect {
+    Successful {
+        gas_used: Vec<u64>,
+    },
+    Reverted {
+        gas_used: Vec<u64>,
+    },
+    #[serde(other)]
+    Skipped,
+}
+
+/// Slot as returned by `ledger_getSlotByNumber`, with the fields the client reads.
+#[derive(serde::Deserialize)]
+struct SlotGasPrice {
+    /// Base fee per unit of gas of every dimension in the slot.
+    gas_price: Vec<u64>,
+}
+
+pub struct Client<S: Spec, Da: DaSpec> {
+    rpc: WsClient,
+    signer: Signer<S>,
+    address: S::Address,
+    credential_id: CredentialId,
+    chain_id: u64,
+    /// Next nonce of the signer, fetched from the rollup when unknown. Held while submitting, so
+    /// the transactions of a client are sent one at a time.
+    nonce: Mutex<Option<u64>>,
+    fees: Mutex<FeeEstimator>,
+    phantom: PhantomData<Da>,
+}
+
+impl<S: Spec, Da: DaSpec> Client<S, Da> {
+    /// Connects to the JSON-RPC server of a node at `url`, e.g. `ws://127.0.0.1:12345`.
+    pub async fn new(url: &str, signer: Signer<S>) -> Result<Self> {
+        let rpc = WsClientBuilder::new()
+            .request_timeout(Duration::from_secs(60))
+            .build(url)
+            .await
+            .with_context(|| format!("unable to connect to {url}"))?;
+
+        Ok(Self {
+            rpc,
+            address: signer.address()?,
+            credential_id: signer.credential_id()?,
+            signer,
+            chain_id: filament_hub_eth::CHAIN_ID,
+            nonce: Mutex::new(None),
+            fees: Mutex::new(FeeEstimator::default()),
+            phantom: PhantomData,
+        })
+    }
+
+    /// Sends transactions for `chain_id` instead of the chain id the crate is built with.
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    pub fn with_fees(mut self, fees: FeeEstimator) -> Self {
+        self.fees = Mutex::new(fees);
+        self
+    }
+
+    /// JSON-RPC client of the node, for the queries this client doesn't wrap, e.g. of past
+    /// heights.
+    pub fn rpc(&self) -> &WsClient {
+        &self.rpc
+    }
+
+    /// Hub address the transactions are sent from.
+    pub fn address(&self) -> &S::Address {
+        &self.address
+    }
+
+    /// Nonce of the signer's next transaction on the rollup.
+    pub async fn fetch_nonce(&self) -> Result<u64> {
+        Ok(self
+            .get_account_by_credential_id(self.credential_id)
+            .await?
+            .map(|account| account.nonce)
+            .unwrap_or_default())
+    }
+
+    /// Max fee the call would be sent with.
+    pub async fn estimate_fee(&self, call: &CallMessage<S>) -> u64 {
+        self.fees.lock().await.estimate(call.name())
+    }
+
+    /// Signs and submits the call without waiting for it to be included, returning its nonce.
+    pub async fn submit(&self, call: CallMessage<S>) -> Result<u64> {
+        let mut nonce = self.nonce.lock().await;
+        Ok(self.submit_with_nonce(&call, &mut nonce).await?.0)
+    }
+
+    /// Signs and submits the call, then waits for it to be included.
+    pub async fn submit_and_wait(&self, call: CallMessage<S>) -> Result<Inclusion<S>> {
+        let mut nonce = self.nonce.lock().await;
+
+        // Subscribe before submitting, so the slot including the transaction isn't missed.
+        let mut slots = self
+            .ledger()
+            .subscribe_slots()
+            .await
+            .context("unable to subscribe to slots")?;
+        let (tx_nonce, hash) = self.submit_with_nonce(&call, &mut nonce).await?;
+
+        let mut waited = 0;
+        let (slot_number, tx) = loop {
+            if waited == INCLUSION_TIMEOUT_SLOTS {
+                // The transaction may still be included later, so the nonce is unknown.
+                *nonce = None;
+                bail!("transaction {hash} not included after {INCLUSION_TIMEOUT_SLOTS} slots");
+            }
+            let slot_number = slots
+                .next()
+                .await
+                .ok_or(anyhow!("slot subscription closed"))??;
+            waited += 1;
+
+            if let Some(tx) = self.fetch_tx(&hash).await? {
+                break (slot_number, tx);
+            }
+        };
+
+        let gas_used = match tx.receipt {
+            TxEffect::Successful { gas_used } | TxEffect::Reverted { gas_used } => gas_used,
+            TxEffect::Skipped => {
+                // Skipped transactions don't consume their nonce.
+                *nonce = None;
+                bail!("transaction {hash} was skipped");
+            },
+        };
+        let fee = self.gas_fee(slot_number, &gas_used).await?;
+        self.fees.lock().await.observe(call.name(), fee);
+
+        let events: Vec<SlotEvent<S>> = self
+            .rpc
+            .request(
+                "core_getSlotEvents",
+                rpc_params![
+                    slot_number,
+                    serde_json::json!({ "from_event": tx.event_range.start })
+                ],
+            )
+            .await
+            .context("unable to fetch the events of the transaction")?;
+        debug!(%slot_number, %hash, %fee, "transaction included");
+
+        Ok(Inclusion {
+            slot_number,
+            nonce: tx_nonce,
+            hash,
+            fee,
+            events: events
+                .into_iter()
+                .filter(|event| tx.event_range.contains(&event.event_number))
+                .map(|event| event.event)
+                .collect(),
+        })
+    }
+
+    /// Signs and submits the call with the next nonce, returning the nonce and hash of the
+    /// transaction.
+    async fn submit_with_nonce(
+        &self,
+        call: &CallMessage<S>,
+        nonce: &mut Option<u64>,
+    ) -> Result<(u64, TxHash)> {
+        let tx_nonce = match *nonce {
+            Some(nonce) => nonce,
+            None => self.fetch_nonce().await?,
+        };
+        let details = {
+            let fees = self.fees.lock().await;
+            TxDetails::<S> {
+                max_priority_fee_bips: fees.max_priority_fee_bips,
+                max_fee: fees.estimate(call.name()),
+                gas_limit: None,
+                chain_id: self.chain_id,
+            }
+        };
+
+        let runtime_msg = borsh::to_vec(&RuntimeCall::<S, Da>::Core(call.clone()))?;
+        let auth = self.signer.sign(runtime_msg, tx_nonce, details)?;
+        // Authenticators hash the transaction without its auth scheme tag.
+        let hash =
+            TxHash::new(<S::CryptoSpec as CryptoSpec>::Hasher::digest(auth.payload()).into());
+        let tx = borsh::to_vec(&auth)?;
+
+        let res: Result<serde_json::Value, _> = self
+            .rpc
+            .request("sequencer_publishBatch", rpc_params![vec![tx]])
+            .await;
+        if let Err(e) = res {
+            // The transaction may or may not have been accepted.
+            *nonce = None;
+            bail!("unable to submit transaction {tx_nonce}: {e}");
+        }
+        debug!(call = call.name(), nonce = %tx_nonce, %hash, "transaction submitted");
+
+        *nonce = Some(tx_nonce + 1);
+        Ok((tx_nonce, hash))
+    }
+
+    /// Transaction `hash`, `None` until it is included.
+    async fn fetch_tx(&self, hash: &TxHash) -> Result<Option<TxReceipt>> {
+        self.rpc
+            .request(
+                "ledger_getTxByHash",
+                rpc_params![hash.to_string(), QueryMode::Compact],
+            )
+            .await
+            .with_context(|| format!("unable to fetch transaction {hash}"))
+    }
+
+    /// Base fee of `gas_used` at the gas price of the slot `slot_number`.
+    async fn gas_fee(&self, slot_number: u64, gas_used: &[u64]) -> Result<u64> {
+        let slot: Option<SlotGasPrice> = self
+            .rpc
+            .request(
+                "ledger_getSlotByNumber",
+                rpc_params![slot_number, QueryMode::Compact],
+            )
+            .await
+            .with_context(|| format!("unable to fetch slot {slot_number}"))?;
+        let slot = slot.ok_or(anyhow!("slot {slot_number} not found"))?;
+
+        Ok(gas_used
+            .iter()
+            .zip(&slot.gas_price)
+            .fold(0u64, |fee, (gas, price)| {
+                fee.saturating_add(gas.saturating_mul(*price))
+            }))
+    }
+
+    fn ledger(
+        &self,
+    ) -> &impl RpcClient<SlotResponse<u32, u32>, BatchResponse<u32, u32>, TxResponse<u32>> {
+        &self.rpc
+    }
+}
@@ -0,0 +1,283 @@
+//! Typed core queries, reading the latest state. Queries of past heights go through
+//! [`Client::rpc`] and [`CoreRpcClient`].
+use std::collections::HashMap;
+
+use anyhow::Result;
+use filament_hub_core::{
+    attestation::{Attestation, AttestationConflict},
+    campaign::Campaign,
+    campaign_index::{CampaignFilter, CampaignPage, CampaignSort, SortOrder},
+    criteria::CriteriaProposal,
+    light_client::EthHeader,
+    multisig::Multisig,
+    rewards::{DelegateReward, ObjectiveMeasurement, RewardPool},
+    session::SessionKey,
+    voting::{CriteriaVote, DistributionVote, PowerCheckpoint, Tally},
+    Account,
+    CoreRpcClient,
+    Indexer,
+    PendingAction,
+    Power,
+    Relayer,
+    Segment,
+    StatsResponse,
+    TallyResponse,
+};
+use sov_modules_api::{CredentialId, Spec};
+use sov_rollup_interface::da::DaSpec;
+
+use crate::Client;
+
+impl<S: Spec, Da: DaSpec> Client<S, Da> {
+    pub async fn get_campaign(&self, id: u64) -> Result<Option<Campaign<S>>> {
+        Ok(CoreRpcClient::<S>::rpc_get_campaign(&self.rpc, id, None).await?)
+    }
+
+    pub async fn get_criteria_proposal(
+        &self,
+        campaign_id: u64,
+        proposal_id: u64,
+    ) -> Result<Option<CriteriaProposal<S>>> {
+        Ok(
+            CoreRpcClient::<S>::rpc_get_criteria_proposal(
+                &self.rpc,
+                campaign_id,
+                proposal_id,
+                None,
+            )
+            .await?,
+        )
+    }
+
+    pub async fn get_indexer(&self, addr: S::Address) -> Result<Option<Indexer<S>>> {
+        Ok(CoreRpcClient::<S>::rpc_get_indexer(&self.rpc, addr, None).await?)
+    }
+
+    pub async fn get_indexers(&self) -> Result<Vec<Indexer<S>>> {
+        Ok(CoreRpcClient::<S>::rpc_get_indexers(&self.rpc, None).await?)
+    }
+
+    pub async fn get_campaign_powers(
+        &self,
+        campaign_id: u64,
+    ) -> Result<Option<HashMap<String, Power>>> {
+        Ok(CoreRpcClient::<S>::rpc_get_campaign_powers(&self.rpc, campaign_id, None).await?)
+    }
+
+    pub async fn get_segment(&self, id: u64) -> Result<Option<Segment>> {
+        Ok(CoreRpcClient::<S>::rpc_get_segment(&self.rpc, id, None).await?)
+    }
+
+    pub async fn get_delegate_rewards(&self, campaign_id: u64) -> Result<Vec<DelegateReward>> {
+        Ok(CoreRpcClient::<S>::rpc_get_delegate_rewards(&self.rpc, campaign_id, None).await?)
+    }
+
+    pub async fn get_attestation_conflicts(
+        &self,
+        addr: S::Address,
+    ) -> Result<Vec<AttestationConflict<S>>> {
+        Ok(CoreRpcClient::<S>::rpc_get_attestation_conflicts(&self.rpc, addr, None).await?)
+    }
+
+    pub async fn get_eth_head(&self) -> Result<Option<EthHeader>> {
+        Ok(CoreRpcClient::<S>::rpc_get_eth_head(&self.rpc, None).await?)
+    }
+
+    pub async fn get_total_voting_power(&self) -> Result<Power> {
+        Ok(CoreRpcClient::<S>::rpc_get_total_voting_power(&self.rpc, None).await?)
+    }
+
+    pub async fn get_voting_power_at(&self, addr: S::Address, slot: u64) -> Result<Power> {
+        Ok(CoreRpcClient::<S>::rpc_get_voting_power_at(&self.rpc, addr, slot, None).await?)
+    }
+
+    pub async fn get_multisig(&self, eth_addr: String) -> Result<Option<Multisig>> {
+        Ok(CoreRpcClient::<S>::rpc_get_multisig(&self.rpc, eth_addr, None).await?)
+    }
+
+    pub async fn get_session_key(&self, key: String) -> Result<Option<SessionKey<S>>> {
+        Ok(CoreRpcClient::<S>::rpc_get_session_key(&self.rpc, key, None).await?)
+    }
+
+    pub async fn get_admin(&self) -> Result<Option<S::Address>> {
+        Ok(CoreRpcClient::<S>::rpc_get_admin(&self.rpc, None).await?)
+    }
+
+    pub async fn get_account_by_eth_addr(&self, eth_addr: String) -> Result<Option<Account>> {
+        Ok(CoreRpcClient::<S>::rpc_get_account_by_eth_addr(&self.rpc, eth_addr, None).await?)
+    }
+
+    pub async fn get_account_by_credential_id(
+        &self,
+        credential_id: CredentialId,
+    ) -> Result<Option<Account>> {
+        Ok(
+            CoreRpcClient::<S>::rpc_get_account_by_credential_id(&self.rpc, credential_id, None)
+                .await?,
+        )
+    }
+
+    pub async fn get_linked_eth_address(&self, addr: S::Address) -> Result<Option<String>> {
+        Ok(CoreRpcClient::<S>::rpc_get_linked_eth_address(&self.rpc, addr, None).await?)
+    }
+
+    pub async fn get_linked_hub_address(&self, eth_addr: String) -> Result<Option<S::Address>> {
+        Ok(CoreRpcClient::<S>::rpc_get_linked_hub_address(&self.rpc, eth_addr, None).await?)
+    }
+
+    pub async fn get_campaigns(
+        &self,
+        filter: CampaignFilter<S>,
+        sort: Option<CampaignSort>,
+        order: Option<SortOrder>,
+        after: Option<u64>,
+        limit: Option<usize>,
+    ) -> Result<CampaignPage<S>> {
+        Ok(CoreRpcClient::<S>::rpc_get_campaigns(
+            &self.rpc, filter, sort, order, after, limit, None,
+        )
+        .await?)
+    }
+
+    pub async fn get_campaigns_by_addr(&self, addr: S::Address) -> Result<Vec<Campaign<S>>> {
+        Ok(CoreRpcClient::<S>::rpc_get_campaigns_by_addr(&self.rpc, addr, None).await?)
+    }
+
+    pub async fn get_criteria_proposals(
+        &self,
+        campaign_id: u64,
+    ) -> Result<Vec<CriteriaProposal<S>>> {
+        Ok(CoreRpcClient::<S>::rpc_get_criteria_proposals(&self.rpc, campaign_id, None).await?)
+    }
+
+    pub async fn get_criteria_votes(
+        &self,
+        campaign_id: u64,
+    ) -> Result<HashMap<String, CriteriaVote>> {
+        Ok(CoreRpcClient::<S>::rpc_get_criteria_votes(&self.rpc, campaign_id, None).await?)
+    }
+
+    pub async fn get_distribution_votes(
+        &self,
+        campaign_id: u64,
+    ) -> Result<HashMap<String, DistributionVote>> {
+        Ok(CoreRpcClient::<S>::rpc_get_distribution_votes(&self.rpc, campaign_id, None).await?)
+    }
+
+    pub async fn get_criteria_tally(&self, campaign_id: u64) -> Result<Option<Tally>> {
+        Ok(CoreRpcClient::<S>::rpc_get_criteria_tally(&self.rpc, campaign_id, None).await?)
+    }
+
+    pub async fn get_distribution_tally(&self, campaign_id: u64) -> Result<Option<Tally>> {
+        Ok(CoreRpcClient::<S>::rpc_get_distribution_tally(&self.rpc, campaign_id, None).await?)
+    }
+
+    pub async fn get_criteria_tally_result(
+        &self,
+        campaign_id: u64,
+    ) -> Result<Option<TallyResponse>> {
+        Ok(CoreRpcClient::<S>::rpc_get_criteria_tally_result(&self.rpc, campaign_id, None).await?)
+    }
+
+    pub async fn get_distribution_tally_result(
+        &self,
+        campaign_id: u64,
+    ) -> Result<Option<TallyResponse>> {
+        Ok(
+            CoreRpcClient::<S>::rpc_get_distribution_tally_result(&self.rpc, campaign_id, None)
+                .await?,
+        )
+    }
+
+    pub async fn get_delegates(&self) -> Result<Vec<S::Address>> {
+        Ok(CoreRpcClient::<S>::rpc_get_delegates(&self.rpc, None).await?)
+    }
+
+    pub async fn get_campaigns_by_delegate(&self, addr: S::Address) -> Result<Vec<Campaign<S>>> {
+        Ok(CoreRpcClient::<S>::rpc_get_campaigns_by_delegate(&self.rpc, addr, None).await?)
+    }
+
+    pub async fn get_campaigns_by_delegate_eth_addr(
+        &self,
+        eth_addr: String,
+    ) -> Result<Vec<Campaign<S>>> {
+        Ok(
+            CoreRpcClient::<S>::rpc_get_campaigns_by_delegate_eth_addr(&self.rpc, eth_addr, None)
+                .await?,
+        )
+    }
+
+    pub async fn get_pending_actions(&self, addr: S::Address) -> Result<Vec<PendingAction>> {
+        Ok(CoreRpcClient::<S>::rpc_get_pending_actions(&self.rpc, addr, None).await?)
+    }
+
+    pub async fn get_pending_actions_by_eth_addr(
+        &self,
+        eth_addr: String,
+    ) -> Result<Vec<PendingAction>> {
+        Ok(
+            CoreRpcClient::<S>::rpc_get_pending_actions_by_eth_addr(&self.rpc, eth_addr, None)
+                .await?,
+        )
+    }
+
+    pub async fn get_relayers(&self) -> Result<Vec<Relayer<S>>> {
+        Ok(CoreRpcClient::<S>::rpc_get_relayers(&self.rpc, None).await?)
+    }
+
+    pub async fn get_voting_power(&self, addr: S::Address) -> Result<Power> {
+        Ok(CoreRpcClient::<S>::rpc_get_voting_power(&self.rpc, addr, None).await?)
+    }
+
+    pub async fn get_voting_powers(
+        &self,
+        after: Option<S::Address>,
+        limit: Option<usize>,
+    ) -> Result<Vec<(S::Address, Power)>> {
+        Ok(CoreRpcClient::<S>::rpc_get_voting_powers(&self.rpc, after, limit, None).await?)
+    }
+
+    pub async fn get_voting_powers_count(&self) -> Result<u64> {
+        Ok(CoreRpcClient::<S>::rpc_get_voting_powers_count(&self.rpc, None).await?)
+    }
+
+    pub async fn get_power_checkpoints(&self, addr: S::Address) -> Result<Vec<PowerCheckpoint>> {
+        Ok(CoreRpcClient::<S>::rpc_get_power_checkpoints(&self.rpc, addr, None).await?)
+    }
+
+    pub async fn get_total_voting_power_at(&self, slot: u64) -> Result<Power> {
+        Ok(CoreRpcClient::<S>::rpc_get_total_voting_power_at(&self.rpc, slot, None).await?)
+    }
+
+    pub async fn get_last_eth_block(&self) -> Result<Option<u64>> {
+        Ok(CoreRpcClient::<S>::rpc_get_last_eth_block(&self.rpc, None).await?)
+    }
+
+    pub async fn get_attestation_threshold(&self) -> Result<Option<u64>> {
+        Ok(CoreRpcClient::<S>::rpc_get_attestation_threshold(&self.rpc, None).await?)
+    }
+
+    pub async fn get_attestations(
+        &self,
+        addr: S::Address,
+        eth_block: u64,
+    ) -> Result<Vec<Attestation<S>>> {
+        Ok(CoreRpcClient::<S>::rpc_get_attestations(&self.rpc, addr, eth_block, None).await?)
+    }
+
+    pub async fn get_eth_header(&self, number: u64) -> Result<Option<EthHeader>> {
+        Ok(CoreRpcClient::<S>::rpc_get_eth_header(&self.rpc, number, None).await?)
+    }
+
+    pub async fn get_reward_pool(&self, campaign_id: u64) -> Result<Option<RewardPool<S>>> {
+        Ok(CoreRpcClient::<S>::rpc_get_reward_pool(&self.rpc, campaign_id, None).await?)
+    }
+
+    pub async fn get_objective(&self, campaign_id: u64) -> Result<Option<ObjectiveMeasurement>> {
+        Ok(CoreRpcClient::<S>::rpc_get_objective(&self.rpc, campaign_id, None).await?)
+    }
+
+    pub async fn get_stats(&self) -> Result<StatsResponse> {
+        Ok(CoreRpcClient::<S>::rpc_get_stats(&self.rpc, None).await?)
+    }
+}
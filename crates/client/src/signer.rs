@@ -0,0 +1,85 @@
+use anyhow::Result;
+use filament_hub_eth::Tx;
+use filament_hub_stf::authentication::Auth;
+use k256::ecdsa::SigningKey;
+use sov_modules_api::{
+    transaction::{Transaction, TxDetails, UnsignedTransaction},
+    CredentialId,
+    CryptoSpec,
+    PrivateKey as _,
+    PublicKey as _,
+    Spec,
+};
+
+/// Key transactions are signed with.
+pub enum Signer<S: Spec> {
+    /// Key of the rollup's native signature scheme, with the address it controls.
+    Standard {
+        key: <S::CryptoSpec as CryptoSpec>::PrivateKey,
+        address: S::Address,
+    },
+    /// Ethereum key, acting as the hub address derived from its eth address.
+    Eth(SigningKey),
+}
+
+impl<S: Spec> Signer<S> {
+    pub fn standard(key: <S::CryptoSpec as CryptoSpec>::PrivateKey, address: S::Address) -> Self {
+        Self::Standard { key, address }
+    }
+
+    pub fn eth(key: SigningKey) -> Self {
+        Self::Eth(key)
+    }
+
+    /// Hub address the transactions are sent from.
+    pub fn address(&self) -> Result<S::Address> {
+        match self {
+            Self::Standard { address, .. } => Ok(address.clone()),
+            Self::Eth(key) => filament_hub_eth::vk_to_address::<S>(key.verifying_key()),
+        }
+    }
+
+    /// Credential the rollup tracks the nonce of, the way its authenticator derives it.
+    pub fn credential_id(&self) -> Result<CredentialId> {
+        match self {
+            Self::Standard { key, .. } => Ok(key
+                .pub_key()
+                .credential_id::<<S::CryptoSpec as CryptoSpec>::Hasher>()),
+            Self::Eth(key) => {
+                let address = filament_hub_eth::vk_to_address::<S>(key.verifying_key())?;
+                Ok(filament_hub_eth::hub_addr_to_credential_id::<
+                    <S::CryptoSpec as CryptoSpec>::Hasher,
+                    S,
+                >(&address))
+            },
+        }
+    }
+
+    /// Signs the runtime message, returning the transaction as the rollup accepts it.
+    pub fn sign(&self, runtime_msg: Vec<u8>, nonce: u64, details: TxDetails<S>) -> Result<Auth> {
+        match self {
+            Self::Standard { key, .. } => {
+                let tx = Transaction::<S>::new_signed_tx(
+                    key,
+                    UnsignedTransaction::new_with_details(runtime_msg, nonce, details),
+                );
+                Ok(Auth::Standard(borsh::to_vec(&tx)?))
+            },
+            Self::Eth(key) => {
+                let unsigned = UnsignedTransaction::<S>::new_with_details(
+                    runtime_msg.clone(),
+                    nonce,
+                    details.clone(),
+                );
+                let signature = filament_hub_eth::sign(key, borsh::to_vec(&unsigned)?)?;
+                let tx = Tx::<S> {
+                    signature: signature.to_vec(),
+                    runtime_msg,
+                    nonce,
+                    details,
+                };
+                Ok(Auth::Eth(borsh::to_vec(&tx)?))
+            },
+        }
+    }
+}
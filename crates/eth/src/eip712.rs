@@ -0,0 +1,514 @@
+//! Transactions signed as [EIP-712](https://eips.ethereum.org/EIPS/eip-712) typed data, so
+//! Ethereum wallets can show the runtime call as structured fields instead of an opaque blob.
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{anyhow, bail};
+use borsh::BorshDeserialize;
+use k256::ecdsa::{SigningKey, VerifyingKey};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use sov_modules_api::{
+    capabilities::{AuthenticationError, AuthenticationResult, AuthorizationData, FatalError},
+    transaction::{
+        AuthenticatedTransactionAndRawHash,
+        AuthenticatedTransactionData,
+        Credentials,
+        TxDetails,
+    },
+    CryptoSpec,
+    DispatchCall,
+    GasMeter,
+    MeteredHasher,
+    PreExecWorkingSet,
+    Spec,
+};
+use sov_rollup_interface::TxHash;
+
+use crate::{
+    hub_addr_to_credential_id,
+    parse_eth_address,
+    parse_signature,
+    proof::keccak,
+    vk_to_address,
+    CHAIN_ID,
+};
+
+/// Name of the signing domain.
+pub const DOMAIN_NAME: &str = "Filament Hub";
+
+/// Version of the signing domain.
+pub const DOMAIN_VERSION: &str = "1";
+
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+pub struct Eip712Tx<S: Spec> {
+    pub signature: Vec<u8>,
+    /// The runtime call of the transaction as JSON, e.g. `{"core":{"init":{"campaign_id":1}}}`.
+    pub call: String,
+    /// The nonce of the transaction.
+    pub nonce: u64,
+    /// The transaction metadata. Contains gas parameters and the chain ID.
+    pub details: TxDetails<S>,
+}
+
+impl<S: Spec> Eip712Tx<S> {
+    /// Hash the wallet signs, `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(message))`.
+    pub fn signing_hash(&self) -> anyhow::Result<[u8; 32]> {
+        if self.details.gas_limit.is_some() {
+            bail!("gas limit is not supported for EIP-712 transactions");
+        }
+
+        let call = serde_json::from_str(&self.call)?;
+
+        TypedData::transaction(
+            &call,
+            self.nonce,
+            self.details.chain_id,
+            self.details.max_fee,
+            self.details.max_priority_fee_bips.0,
+        )?
+        .signing_hash()
+    }
+
+    /// Recovers the key that signed the typed data of the transaction.
+    pub fn verify(&self) -> anyhow::Result<VerifyingKey> {
+        let (signature, recid) = parse_signature(&self.signature, self.details.chain_id)?;
+
+        let vk = VerifyingKey::recover_from_prehash(&self.signing_hash()?, &signature, recid)?;
+
+        Ok(vk)
+    }
+}
+
+/// Member of a struct type.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Member {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+/// Typed data in the format of `eth_signTypedData_v4`.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    /// Struct types by name, including `EIP712Domain`.
+    pub types: BTreeMap<String, Vec<Member>>,
+    pub primary_type: String,
+    pub domain: Value,
+    pub message: Value,
+}
+
+impl TypedData {
+    /// Typed data of a transaction with the runtime call `call` in its JSON form
+    /// `{"<module>":{"<method>":{...}}}`.
+    ///
+    /// The struct types of the call are derived from its JSON, so wallets show every field of
+    /// the call. Objects become struct types named after their path, e.g. `CallCoreInit`,
+    /// integers `uint256` or `int256`, and unset options are left out.
+    pub fn transaction(
+        call: &Value,
+        nonce: u64,
+        chain_id: u64,
+        max_fee: u64,
+        max_priority_fee_bips: u64,
+    ) -> anyhow::Result<Self> {
+        if !call.is_object() {
+            bail!("call is not a module call");
+        }
+
+        let mut types = BTreeMap::from([
+            (
+                "EIP712Domain".to_string(),
+                vec![
+                    member("name", "string"),
+                    member("version", "string"),
+                    member("chainId", "uint256"),
+                ],
+            ),
+            (
+                "Transaction".to_string(),
+                vec![
+                    member("call", "Call"),
+                    member("nonce", "uint64"),
+                    member("maxFee", "uint256"),
+                    member("maxPriorityFeeBips", "uint64"),
+                ],
+            ),
+        ]);
+        let (_, call) = call_type("Call", call, &mut types)?;
+
+        // Integers are passed as strings, as wallets parse JSON numbers as doubles.
+        Ok(Self {
+            types,
+            primary_type: "Transaction".to_string(),
+            domain: serde_json::json!({
+                "name": DOMAIN_NAME,
+                "version": DOMAIN_VERSION,
+                "chainId": chain_id,
+            }),
+            message: serde_json::json!({
+                "call": call,
+                "nonce": nonce.to_string(),
+                "maxFee": max_fee.to_string(),
+                "maxPriorityFeeBips": max_priority_fee_bips.to_string(),
+            }),
+        })
+    }
+
+    /// Hash the wallet signs, `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(message))`.
+    pub fn signing_hash(&self) -> anyhow::Result<[u8; 32]> {
+        let domain_separator = self.hash_struct("EIP712Domain", &self.domain)?;
+        let message = self.hash_struct(&self.primary_type, &self.message)?;
+
+        Ok(keccak(
+            &[&b"\x19\x01"[..], &domain_separator[..], &message[..]].concat(),
+        ))
+    }
+
+    /// Encoding of the struct type `name` followed by the types it references, in alphabetical
+    /// order, e.g. `Mail(Person from,Person to,string contents)Person(string name,address wallet)`.
+    pub fn encode_type(&self, name: &str) -> anyhow::Result<String> {
+        let mut referenced = BTreeSet::new();
+        self.collect_types(name, &mut referenced)?;
+        referenced.remove(name);
+
+        std::iter::once(name)
+            .chain(referenced.iter().map(String::as_str))
+            .map(|name| {
+                let members = self.members(name)?;
+                let members = members
+                    .iter()
+                    .map(|member| format!("{} {}", member.ty, member.name))
+                    .collect::<Vec<_>>();
+
+                Ok(format!("{name}({})", members.join(",")))
+            })
+            .collect()
+    }
+
+    /// `keccak256(typeHash ‖ encodeData(value))` of a value of the struct type `name`.
+    pub fn hash_struct(&self, name: &str, value: &Value) -> anyhow::Result<[u8; 32]> {
+        let Value::Object(fields) = value else {
+            bail!("value of type {name} is not an object");
+        };
+
+        let mut encoded = keccak(self.encode_type(name)?.as_bytes()).to_vec();
+        for member in self.members(name)? {
+            let value = fields
+                .get(&member.name)
+                .ok_or(anyhow!("value of type {name} is missing {}", member.name))?;
+            encoded.extend(self.encode_value(&member.ty, value)?);
+        }
+
+        Ok(keccak(&encoded))
+    }
+
+    fn members(&self, name: &str) -> anyhow::Result<&[Member]> {
+        self.types
+            .get(name)
+            .map(Vec::as_slice)
+            .ok_or(anyhow!("type {name} is not defined"))
+    }
+
+    fn collect_types(&self, ty: &str, referenced: &mut BTreeSet<String>) -> anyhow::Result<()> {
+        let name = ty.split('[').next().unwrap_or(ty);
+        if !self.types.contains_key(name) || !referenced.insert(name.to_string()) {
+            return Ok(());
+        }
+        for member in self.members(name)? {
+            self.collect_types(&member.ty, referenced)?;
+        }
+
+        Ok(())
+    }
+
+    fn encode_value(&self, ty: &str, value: &Value) -> anyhow::Result<[u8; 32]> {
+        if let Some(item_ty) = ty.strip_suffix(']') {
+            let (item_ty, len) = item_ty
+                .rsplit_once('[')
+                .ok_or(anyhow!("type {ty} is invalid"))?;
+            let Value::Array(items) = value else {
+                bail!("value of type {ty} is not an array");
+            };
+            if !len.is_empty() && len.parse::<usize>()? != items.len() {
+                bail!("value of type {ty} has {} items", items.len());
+            }
+
+            let encoded = items
+                .iter()
+                .map(|item| self.encode_value(item_ty, item))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            return Ok(keccak(&encoded.concat()));
+        }
+        if self.types.contains_key(ty) {
+            return self.hash_struct(ty, value);
+        }
+
+        match ty {
+            "string" => {
+                let value = value
+                    .as_str()
+                    .ok_or(anyhow!("value of type string is not a string"))?;
+                Ok(keccak(value.as_bytes()))
+            },
+            "bytes" => Ok(keccak(&hex_bytes(value)?)),
+            "bool" => {
+                let value = value
+                    .as_bool()
+                    .ok_or(anyhow!("value of type bool is not a boolean"))?;
+                Ok(word(u128::from(value)))
+            },
+            "address" => {
+                let value = value
+                    .as_str()
+                    .ok_or(anyhow!("value of type address is not a string"))?;
+                let mut word = [0u8; 32];
+                word[12..].copy_from_slice(&parse_eth_address(value)?);
+                Ok(word)
+            },
+            _ if ty.starts_with("uint") => Ok(word(integer(value)?)),
+            _ if ty.starts_with("int") => {
+                let value = integer_signed(value)?;
+                let mut word = [if value < 0 { 0xff } else { 0 }; 32];
+                word[16..].copy_from_slice(&value.to_be_bytes());
+                Ok(word)
+            },
+            _ if ty.starts_with("bytes") => {
+                let bytes = hex_bytes(value)?;
+                if bytes.len() > 32 {
+                    bail!("value of type {ty} is longer than 32 bytes");
+                }
+                let mut word = [0u8; 32];
+                word[..bytes.len()].copy_from_slice(&bytes);
+                Ok(word)
+            },
+            _ => bail!("type {ty} is not supported"),
+        }
+    }
+}
+
+fn member(name: &str, ty: &str) -> Member {
+    Member {
+        name: name.to_string(),
+        ty: ty.to_string(),
+    }
+}
+
+/// Derives the type of a value of the runtime call, defining the struct types of its objects
+/// in `types` under names derived from `name`. Returns the type and the value as it is put in
+/// the message.
+fn call_type(
+    name: &str,
+    value: &Value,
+    types: &mut BTreeMap<String, Vec<Member>>,
+) -> anyhow::Result<(String, Value)> {
+    match value {
+        Value::Bool(_) => Ok(("bool".to_string(), value.clone())),
+        Value::String(_) => Ok(("string".to_string(), value.clone())),
+        Value::Number(number) if number.is_u64() => {
+            Ok(("uint256".to_string(), Value::String(number.to_string())))
+        },
+        Value::Number(number) if number.is_i64() => {
+            Ok(("int256".to_string(), Value::String(number.to_string())))
+        },
+        Value::Number(number) => bail!("call contains the non integer number {number}"),
+        Value::Null => bail!("call contains a null value outside of an object"),
+        Value::Array(items) => {
+            let mut item_ty: Option<String> = None;
+            let mut encoded = Vec::with_capacity(items.len());
+            for item in items {
+                let (ty, item) = call_type(name, item, types)?;
+                match &item_ty {
+                    Some(item_ty) if *item_ty != ty => {
+                        bail!("array {name} mixes items of type {item_ty} and {ty}")
+                    },
+                    _ => item_ty = Some(ty),
+                }
+                encoded.push(item);
+            }
+
+            // Empty arrays encode the same whatever the type of their items.
+            let item_ty = item_ty.unwrap_or_else(|| "string".to_string());
+            Ok((format!("{item_ty}[]"), Value::Array(encoded)))
+        },
+        Value::Object(fields) => {
+            let mut members = vec![];
+            let mut encoded = serde_json::Map::new();
+            for (key, field) in fields {
+                if !is_identifier(key) {
+                    bail!("call member '{key}' is not a valid identifier");
+                }
+                // Unset options are the same as missing fields to the runtime call.
+                if field.is_null() {
+                    continue;
+                }
+
+                let (ty, field) = call_type(&format!("{name}{}", pascal_case(key)), field, types)?;
+                members.push(member(key, &ty));
+                encoded.insert(key.clone(), field);
+            }
+
+            match types.get(name) {
+                Some(existing) if *existing != members => {
+                    bail!("call has conflicting definitions of type {name}")
+                },
+                Some(_) => {},
+                None => {
+                    types.insert(name.to_string(), members);
+                },
+            }
+
+            Ok((name.to_string(), Value::Object(encoded)))
+        },
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// `snake_case` key as a type name segment, e.g. `campaign_id` to `CampaignId`.
+fn pascal_case(key: &str) -> String {
+    key.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Unsigned integer given as a JSON number, a decimal string or a `0x` prefixed hex string.
+fn integer(value: &Value) -> anyhow::Result<u128> {
+    match value {
+        Value::Number(number) => number
+            .as_u64()
+            .map(u128::from)
+            .ok_or(anyhow!("{number} is not an unsigned integer")),
+        Value::String(value) => match value.strip_prefix("0x") {
+            Some(hex) => Ok(u128::from_str_radix(hex, 16)?),
+            None => Ok(value.parse()?),
+        },
+        _ => bail!("value of integer type is not a number"),
+    }
+}
+
+/// Signed integer given as a JSON number or a decimal string.
+fn integer_signed(value: &Value) -> anyhow::Result<i128> {
+    match value {
+        Value::Number(number) => number
+            .as_i64()
+            .map(i128::from)
+            .ok_or(anyhow!("{number} is not an integer")),
+        Value::String(value) => Ok(value.parse()?),
+        _ => bail!("value of integer type is not a number"),
+    }
+}
+
+fn hex_bytes(value: &Value) -> anyhow::Result<Vec<u8>> {
+    let value = value
+        .as_str()
+        .ok_or(anyhow!("value of bytes type is not a string"))?;
+
+    Ok(hex::decode(value.strip_prefix("0x").unwrap_or(value))?)
+}
+
+/// Signs a typed data hash, returning the signature in the `r ‖ s ‖ v` format of wallets.
+pub fn sign(signing_key: &SigningKey, hash: &[u8; 32]) -> anyhow::Result<[u8; 65]> {
+    let (signature, recid) = signing_key.sign_prehash_recoverable(hash)?;
+
+    let mut ethereum_sig = [0u8; 65];
+    ethereum_sig[..64].copy_from_slice(&signature.to_bytes());
+    ethereum_sig[64] = recid.to_byte() + 27;
+
+    Ok(ethereum_sig)
+}
+
+pub fn authenticate<S, D, Meter>(
+    raw_tx: &[u8],
+    state: &mut PreExecWorkingSet<S, Meter>,
+) -> AuthenticationResult<S, D::Decodable, AuthorizationData<S>>
+where
+    S: Spec,
+    D: DispatchCall<Spec = S>,
+    D::Decodable: DeserializeOwned,
+    Meter: GasMeter<S::Gas>,
+{
+    let raw_tx_hash = MeteredHasher::<
+        S::Gas,
+        PreExecWorkingSet<S, Meter>,
+        <S::CryptoSpec as CryptoSpec>::Hasher,
+    >::digest(raw_tx, state)
+    .map(TxHash::new)
+    .map_err(|e| AuthenticationError::Invalid(e.to_string()))?;
+
+    let tx = <Eip712Tx<S> as BorshDeserialize>::try_from_slice(raw_tx).map_err(|e| {
+        AuthenticationError::FatalError(FatalError::DeserializationFailed(e.to_string()))
+    })?;
+
+    if tx.details.chain_id != CHAIN_ID {
+        return Err(AuthenticationError::FatalError(
+            FatalError::InvalidChainId {
+                expected: CHAIN_ID,
+                got: tx.details.chain_id,
+            },
+        ));
+    }
+
+    let vk = tx.verify().map_err(|e| {
+        AuthenticationError::FatalError(FatalError::SigVerificationFailed(e.to_string()))
+    })?;
+
+    let runtime_call = serde_json::from_str::<D::Decodable>(&tx.call).map_err(|e| {
+        AuthenticationError::FatalError(FatalError::MessageDecodingFailed(
+            e.to_string(),
+            raw_tx_hash,
+        ))
+    })?;
+
+    let address = vk_to_address::<S>(&vk)
+        .map_err(|e| AuthenticationError::FatalError(FatalError::Other(e.to_string())))?;
+    let credential_id =
+        hub_addr_to_credential_id::<<S::CryptoSpec as CryptoSpec>::Hasher, S>(&address);
+    let credentials = Credentials::new(credential_id);
+
+    Ok((
+        AuthenticatedTransactionAndRawHash {
+            raw_tx_hash,
+            authenticated_tx: AuthenticatedTransactionData {
+                chain_id: tx.details.chain_id,
+                gas_limit: tx.details.gas_limit,
+                max_fee: tx.details.max_fee,
+                max_priority_fee_bips: tx.details.max_priority_fee_bips,
+            },
+        },
+        AuthorizationData {
+            nonce: tx.nonce,
+            credential_id,
+            credentials,
+            default_address: Some(address),
+        },
+        runtime_call,
+    ))
+}
+
+fn word(value: u128) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
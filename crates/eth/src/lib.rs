@@ -31,6 +31,11 @@ use sov_modules_api::{
 };
 use sov_rollup_interface::{crypto::SigVerificationError, TxHash};
 
+pub mod eip712;
+pub mod multisig;
+pub mod proof;
+pub mod session;
+
 /// The chain id of the rollup.
 pub const CHAIN_ID: u64 = config_value!("CHAIN_ID");
 
@@ -64,39 +69,14 @@ impl<S: Spec> Tx<S> {
         })?;
         let digest = Keccak256::new_with_prefix(prefix_msg(serialized_tx));
 
-        if self.signature.len() != 65 {
-            return Err(
-                TransactionVerificationError::TransactionDeserializationError(format!(
-                    "invalid signature length: {}",
-                    self.signature.len()
-                )),
-            );
-        }
-
-        let mut r = [0u8; 32];
-        let mut s = [0u8; 32];
-        r.copy_from_slice(&self.signature[0..32]);
-        s.copy_from_slice(&self.signature[32..64]);
-
-        let v = &self.signature[64];
-        let recid = match v {
-            27 | 28 => v - 27,
-            _ => panic!("invalid recovery id"),
-        };
-
-        let signature = Signature::from_scalars(r, s).map_err(|e| {
-            TransactionVerificationError::BadSignature(SigVerificationError::BadSignature(
-                e.to_string(),
-            ))
-        })?;
-        let vk = VerifyingKey::recover_from_digest(
-            digest.clone(),
-            &signature,
-            RecoveryId::from_byte(recid).expect("construction of recovery id should not fail"),
-        )
-        .map_err(|e| {
-            TransactionVerificationError::TransactionDeserializationError(e.to_string())
-        })?;
+        let (signature, recid) = parse_signature(&self.signature, self.details.chain_id)
+            .map_err(TransactionVerificationError::BadSignature)?;
+        let vk =
+            VerifyingKey::recover_from_digest(digest.clone(), &signature, recid).map_err(|e| {
+                TransactionVerificationError::BadSignature(SigVerificationError::BadSignature(
+                    e.to_string(),
+                ))
+            })?;
         vk.verify_digest(digest, &signature).map_err(|e| {
             TransactionVerificationError::BadSignature(SigVerificationError::BadSignature(
                 e.to_string(),
@@ -202,6 +182,11 @@ pub fn sign(signing_key: &SigningKey, msg: Vec<u8>) -> anyhow::Result<[u8; 65]>
 }
 
 pub fn addr_to_hub_address<S: Spec>(addr: &str) -> anyhow::Result<S::Address> {
+    bytes_to_address::<S>(parse_eth_address(addr)?)
+}
+
+/// Parses a hex encoded Ethereum address, with or without `0x` prefix.
+pub fn parse_eth_address(addr: &str) -> anyhow::Result<[u8; 20]> {
     let stripped = addr.strip_prefix("0x").unwrap_or(addr);
     let bytes = Vec::from_hex(stripped)?;
 
@@ -212,7 +197,7 @@ pub fn addr_to_hub_address<S: Spec>(addr: &str) -> anyhow::Result<S::Address> {
     let mut arr = [0u8; 20];
     arr.copy_from_slice(&bytes);
 
-    bytes_to_address::<S>(arr)
+    Ok(arr)
 }
 
 pub fn hub_addr_to_credential_id<Hasher, S>(addr: &S::Address) -> CredentialId
@@ -228,6 +213,11 @@ where
 }
 
 pub fn vk_to_address<S: Spec>(vk: &VerifyingKey) -> anyhow::Result<S::Address> {
+    bytes_to_address::<S>(vk_to_eth_address(vk))
+}
+
+/// Ethereum address of the verifying key, the last 20 bytes of the keccak hash of the public key.
+pub fn vk_to_eth_address(vk: &VerifyingKey) -> [u8; 20] {
     let ep = vk.to_encoded_point(false);
     let pk_bytes = &ep.as_bytes()[1..];
 
@@ -238,7 +228,65 @@ pub fn vk_to_address<S: Spec>(vk: &VerifyingKey) -> anyhow::Result<S::Address> {
     let mut arr = [0u8; 20];
     arr.copy_from_slice(&hash[12..]);
 
-    bytes_to_address::<S>(arr)
+    arr
+}
+
+/// Message an Ethereum account signs to link itself to the hub address `addr` on `chain_id`.
+pub fn link_eth_address_msg(addr: &str, chain_id: u64) -> Vec<u8> {
+    format!("Link to Filament Hub address {addr} on chain {chain_id}").into_bytes()
+}
+
+/// Recovers the Ethereum address that signed `msg` with `personal_sign`.
+pub fn recover_signer(msg: Vec<u8>, signature: &[u8]) -> anyhow::Result<[u8; 20]> {
+    let (signature, recid) = parse_signature(signature, CHAIN_ID)?;
+
+    let digest = Keccak256::new_with_prefix(prefix_msg(msg));
+    let vk = VerifyingKey::recover_from_digest(digest, &signature, recid)?;
+
+    Ok(vk_to_eth_address(&vk))
+}
+
+/// Splits a `r ‖ s ‖ v` signature into the signature and its recovery id. `v` is big-endian
+/// and may span several bytes, as EIP-155 values of large chain ids don't fit into one.
+pub fn parse_signature(
+    signature: &[u8],
+    chain_id: u64,
+) -> Result<(Signature, RecoveryId), SigVerificationError> {
+    if !(65..=72).contains(&signature.len()) {
+        return Err(SigVerificationError::BadSignature(format!(
+            "invalid signature length: {}",
+            signature.len()
+        )));
+    }
+
+    let v = signature[64..]
+        .iter()
+        .fold(0u64, |v, byte| v << 8 | u64::from(*byte));
+    let recid = recovery_id(v, chain_id).ok_or(SigVerificationError::BadSignature(format!(
+        "invalid recovery id: {v}"
+    )))?;
+    let signature = Signature::from_slice(&signature[..64])
+        .map_err(|e| SigVerificationError::BadSignature(e.to_string()))?;
+
+    Ok((signature, recid))
+}
+
+/// Recovery id encoded by `v`, either raw (`0`/`1`), legacy (`27`/`28`) or EIP-155
+/// (`chain_id * 2 + 35`/`36`).
+pub fn recovery_id(v: u64, chain_id: u64) -> Option<RecoveryId> {
+    let parity = match v {
+        0 | 1 => v,
+        27 | 28 => v - 27,
+        _ => {
+            let eip155 = v.checked_sub(35)?;
+            if eip155 / 2 != chain_id {
+                return None;
+            }
+            eip155 % 2
+        },
+    };
+
+    RecoveryId::from_byte(parity as u8)
 }
 
 pub fn vk_to_credential_id<Hasher: sha3::Digest<OutputSize = sha3::digest::consts::U32>>(
@@ -253,7 +301,8 @@ pub fn vk_to_credential_id<Hasher: sha3::Digest<OutputSize = sha3::digest::const
     CredentialId(hasher.finalize().into())
 }
 
-fn bytes_to_address<S: Spec>(arr: [u8; 20]) -> anyhow::Result<S::Address> {
+/// Hub address of the Ethereum address `arr`.
+pub fn bytes_to_address<S: Spec>(arr: [u8; 20]) -> anyhow::Result<S::Address> {
     let hrp = Hrp::parse("sov")?;
     let bech32_address = bech32::encode::<Bech32m>(hrp, &arr)?;
     let address = bech32_address
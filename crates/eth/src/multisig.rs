@@ -0,0 +1,216 @@
+//! Transactions of smart contract wallets, such as Safe multisigs, which can't produce an ECDSA
+//! signature themselves. The contract is named as the signer and the transaction carries the
+//! signatures of its owners, which are checked against the owner set mirrored on the hub.
+use std::collections::HashSet;
+
+use anyhow::bail;
+use borsh::BorshDeserialize;
+use k256::ecdsa::VerifyingKey;
+use sha3::{Digest, Keccak256};
+use sov_modules_api::{
+    capabilities::{AuthenticationError, AuthenticationResult, AuthorizationData, FatalError},
+    transaction::{
+        AuthenticatedTransactionAndRawHash,
+        AuthenticatedTransactionData,
+        Credentials,
+        TxDetails,
+        UnsignedTransaction,
+    },
+    CryptoSpec,
+    DispatchCall,
+    GasMeter,
+    MeteredHasher,
+    PreExecWorkingSet,
+    Spec,
+};
+use sov_rollup_interface::TxHash;
+
+use crate::{
+    bytes_to_address,
+    hub_addr_to_credential_id,
+    parse_signature,
+    prefix_msg,
+    proof::keccak,
+    vk_to_eth_address,
+    CHAIN_ID,
+};
+
+/// Prefix of the preimage wallet hub addresses are derived from.
+const WALLET_ADDRESS_DOMAIN: &[u8] = b"filament-hub/multisig";
+
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+pub struct MultisigTx<S: Spec> {
+    /// Address of the wallet contract the transaction is sent from.
+    pub wallet: [u8; 20],
+    /// Signatures of the wallet owners over [`MultisigTx::signing_msg`].
+    pub signatures: Vec<Vec<u8>>,
+    /// The runtime message of the transaction.
+    pub runtime_msg: Vec<u8>,
+    /// The nonce of the transaction.
+    pub nonce: u64,
+    /// The transaction metadata. Contains gas parameters and the chain ID.
+    pub details: TxDetails<S>,
+}
+
+/// Owners of a wallet contract and the number of them required to sign.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Owners {
+    pub owners: Vec<[u8; 20]>,
+    pub threshold: u64,
+}
+
+impl<S: Spec> MultisigTx<S> {
+    /// Message every owner signs with `personal_sign`, the wallet address followed by the
+    /// unsigned transaction, so owner signatures can't be replayed for their own accounts.
+    pub fn signing_msg(&self) -> Result<Vec<u8>, borsh::io::Error> {
+        let unsigned_tx = UnsignedTransaction::<S>::new_with_details(
+            self.runtime_msg.clone(),
+            self.nonce,
+            self.details.clone(),
+        );
+
+        Ok([&self.wallet[..], &borsh::to_vec(&unsigned_tx)?].concat())
+    }
+
+    /// Recovers the distinct Ethereum addresses that signed the transaction.
+    pub fn signers(&self) -> anyhow::Result<HashSet<[u8; 20]>> {
+        let digest = Keccak256::new_with_prefix(prefix_msg(self.signing_msg()?));
+
+        let mut signers = HashSet::new();
+        for signature in &self.signatures {
+            let (signature, recid) = parse_signature(signature, self.details.chain_id)?;
+            let vk = VerifyingKey::recover_from_digest(digest.clone(), &signature, recid)?;
+            signers.insert(vk_to_eth_address(&vk));
+        }
+
+        Ok(signers)
+    }
+
+    /// Checks that at least `threshold` distinct owners signed the transaction.
+    pub fn verify(&self, owners: &Owners) -> anyhow::Result<()> {
+        if owners.threshold == 0 {
+            bail!("wallet threshold is zero");
+        }
+        // Bound the signatures recovered to what the owner set can use.
+        if self.signatures.len() > owners.owners.len() {
+            bail!(
+                "{} signatures exceed the {} owners of the wallet",
+                self.signatures.len(),
+                owners.owners.len()
+            );
+        }
+
+        let approvals = self
+            .signers()?
+            .iter()
+            .filter(|signer| owners.owners.contains(signer))
+            .count() as u64;
+        if approvals < owners.threshold {
+            bail!(
+                "{} of {} required owners signed the transaction",
+                approvals,
+                owners.threshold
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Hub address of the wallet contract at `wallet`. It is derived apart from the addresses of
+/// Ethereum keys, so a mirrored owner set can't act for an externally owned account.
+pub fn wallet_to_hub_address<S: Spec>(wallet: &[u8; 20]) -> anyhow::Result<S::Address> {
+    let hash = keccak(&[WALLET_ADDRESS_DOMAIN, &wallet[..]].concat());
+
+    let mut addr = [0u8; 20];
+    addr.copy_from_slice(&hash[12..]);
+
+    bytes_to_address::<S>(addr)
+}
+
+/// Authenticates a [`MultisigTx`]. `owners` looks up the owner set of the wallet contract.
+pub fn authenticate<S, D, Meter, F>(
+    raw_tx: &[u8],
+    owners: F,
+    state: &mut PreExecWorkingSet<S, Meter>,
+) -> AuthenticationResult<S, D::Decodable, AuthorizationData<S>>
+where
+    S: Spec,
+    D: DispatchCall<Spec = S>,
+    Meter: GasMeter<S::Gas>,
+    F: FnOnce(&[u8; 20], &mut PreExecWorkingSet<S, Meter>) -> anyhow::Result<Option<Owners>>,
+{
+    let raw_tx_hash = MeteredHasher::<
+        S::Gas,
+        PreExecWorkingSet<S, Meter>,
+        <S::CryptoSpec as CryptoSpec>::Hasher,
+    >::digest(raw_tx, state)
+    .map(TxHash::new)
+    .map_err(|e| AuthenticationError::Invalid(e.to_string()))?;
+
+    let tx = <MultisigTx<S> as BorshDeserialize>::try_from_slice(raw_tx).map_err(|e| {
+        AuthenticationError::FatalError(FatalError::DeserializationFailed(e.to_string()))
+    })?;
+
+    if tx.details.chain_id != CHAIN_ID {
+        return Err(AuthenticationError::FatalError(
+            FatalError::InvalidChainId {
+                expected: CHAIN_ID,
+                got: tx.details.chain_id,
+            },
+        ));
+    }
+
+    let wallet_owners = owners(&tx.wallet, state)
+        .map_err(|e| AuthenticationError::Invalid(e.to_string()))?
+        .ok_or(AuthenticationError::FatalError(
+            FatalError::SigVerificationFailed(format!(
+                "wallet '0x{}' is not registered",
+                hex::encode(tx.wallet)
+            )),
+        ))?;
+    tx.verify(&wallet_owners).map_err(|e| {
+        AuthenticationError::FatalError(FatalError::SigVerificationFailed(e.to_string()))
+    })?;
+
+    let runtime_call = D::decode_call(&tx.runtime_msg, state).map_err(|e| {
+        AuthenticationError::FatalError(FatalError::MessageDecodingFailed(
+            e.to_string(),
+            raw_tx_hash,
+        ))
+    })?;
+
+    let address = wallet_to_hub_address::<S>(&tx.wallet)
+        .map_err(|e| AuthenticationError::FatalError(FatalError::Other(e.to_string())))?;
+    let credential_id =
+        hub_addr_to_credential_id::<<S::CryptoSpec as CryptoSpec>::Hasher, S>(&address);
+    let credentials = Credentials::new(credential_id);
+
+    Ok((
+        AuthenticatedTransactionAndRawHash {
+            raw_tx_hash,
+            authenticated_tx: AuthenticatedTransactionData {
+                chain_id: tx.details.chain_id,
+                gas_limit: tx.details.gas_limit,
+                max_fee: tx.details.max_fee,
+                max_priority_fee_bips: tx.details.max_priority_fee_bips,
+            },
+        },
+        AuthorizationData {
+            nonce: tx.nonce,
+            credential_id,
+            credentials,
+            default_address: Some(address),
+        },
+        runtime_call,
+    ))
+}
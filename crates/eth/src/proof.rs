@@ -0,0 +1,389 @@
+//! Verification of Ethereum block headers and Merkle-Patricia trie proofs as returned by
+//! `eth_getProof`, used to read storage of Ethereum contracts against a known state root.
+use anyhow::{anyhow, bail, Result};
+use hex::FromHex as _;
+use sha3::{Digest, Keccak256};
+
+/// Code hash of accounts without code, the keccak hash of the empty string.
+pub const EMPTY_CODE_HASH: [u8; 32] = [
+    0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03, 0xc0,
+    0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85, 0xa4, 0x70,
+];
+
+/// Fields of an Ethereum block header needed to verify state proofs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Header {
+    /// Keccak hash of the RLP encoded header.
+    pub hash: [u8; 32],
+    pub parent_hash: [u8; 32],
+    pub number: u64,
+    pub state_root: [u8; 32],
+}
+
+impl Header {
+    /// Decodes a hex encoded RLP block header, with or without `0x` prefix.
+    pub fn decode_hex(raw: &str) -> Result<Self> {
+        Self::decode(&Vec::from_hex(raw.strip_prefix("0x").unwrap_or(raw))?)
+    }
+
+    /// Decodes an RLP encoded block header.
+    pub fn decode(raw: &[u8]) -> Result<Self> {
+        let Item::List(_, fields) = rlp::decode(raw)? else {
+            bail!("header is not an RLP list");
+        };
+        // Pre-London headers have 15 fields, every fork since only appended fields.
+        if fields.len() < 15 {
+            bail!("header has {} fields, expected at least 15", fields.len());
+        }
+
+        Ok(Self {
+            hash: keccak(raw),
+            parent_hash: fields[0].as_hash()?,
+            number: fields[8].as_u64()?,
+            state_root: fields[3].as_hash()?,
+        })
+    }
+}
+
+/// State of an Ethereum account as stored in the state trie.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Account {
+    pub nonce: u64,
+    /// Big-endian balance in wei.
+    pub balance: [u8; 32],
+    pub storage_root: [u8; 32],
+    pub code_hash: [u8; 32],
+}
+
+/// Verifies the proof for `address` against `state_root`, returning `None` if the proof shows the
+/// account doesn't exist.
+pub fn verify_account(
+    state_root: [u8; 32],
+    address: [u8; 20],
+    proof: &[Vec<u8>],
+) -> Result<Option<Account>> {
+    let Some(value) = verify_proof(state_root, &address, proof)? else {
+        return Ok(None);
+    };
+    let Item::List(_, fields) = rlp::decode(&value)? else {
+        bail!("account is not an RLP list");
+    };
+    if fields.len() != 4 {
+        bail!("account has {} fields, expected 4", fields.len());
+    }
+
+    Ok(Some(Account {
+        nonce: fields[0].as_u64()?,
+        balance: fields[1].as_u256()?,
+        storage_root: fields[2].as_hash()?,
+        code_hash: fields[3].as_hash()?,
+    }))
+}
+
+/// Verifies the proof for the storage `slot` against `storage_root`, returning the big-endian
+/// value of the slot. Slots absent from the trie hold zero.
+pub fn verify_storage(
+    storage_root: [u8; 32],
+    slot: [u8; 32],
+    proof: &[Vec<u8>],
+) -> Result<[u8; 32]> {
+    match verify_proof(storage_root, &slot, proof)? {
+        None => Ok([0; 32]),
+        Some(value) => rlp::decode(&value)?.as_u256(),
+    }
+}
+
+/// Storage slot of `key` in a Solidity `mapping(address => _)` declared at `slot`.
+pub fn mapping_slot(key: [u8; 20], slot: u64) -> [u8; 32] {
+    let mut preimage = [0u8; 64];
+    preimage[12..32].copy_from_slice(&key);
+    preimage[56..].copy_from_slice(&slot.to_be_bytes());
+
+    keccak(&preimage)
+}
+
+/// Divides a big-endian 256-bit value by `10^decimals`, saturating at `u64::MAX`.
+pub fn scale_down(value: [u8; 32], decimals: u32) -> u64 {
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(value.chunks_exact(8)) {
+        *limb = u64::from_be_bytes(chunk.try_into().expect("chunks are 8 bytes"));
+    }
+
+    let mut remaining = decimals;
+    while remaining > 0 {
+        let step = remaining.min(19);
+        let divisor = 10u64.pow(step);
+        let mut rem = 0u128;
+        for limb in limbs.iter_mut() {
+            let acc = (rem << 64) | u128::from(*limb);
+            *limb = (acc / u128::from(divisor)) as u64;
+            rem = acc % u128::from(divisor);
+        }
+        remaining -= step;
+    }
+
+    if limbs[..3].iter().any(|limb| *limb != 0) {
+        u64::MAX
+    } else {
+        limbs[3]
+    }
+}
+
+/// Walks a Merkle-Patricia trie proof for `key` from `root`, returning the value stored at the key
+/// or `None` if the proof shows its absence.
+pub fn verify_proof(root: [u8; 32], key: &[u8], proof: &[Vec<u8>]) -> Result<Option<Vec<u8>>> {
+    let path = nibbles(&keccak(key));
+    let mut offset = 0;
+    let mut nodes = proof.iter();
+    let mut next = NodeRef::Hash(root);
+
+    let value = loop {
+        let node = match next {
+            NodeRef::Hash(hash) => {
+                let node = nodes.next().ok_or(anyhow!("proof is missing nodes"))?;
+                if keccak(node) != hash {
+                    bail!("proof node does not match its reference");
+                }
+                node.as_slice()
+            },
+            NodeRef::Inline(raw) => raw,
+        };
+
+        let Item::List(_, items) = rlp::decode(node)? else {
+            bail!("proof node is not an RLP list");
+        };
+        match items.len() {
+            // Branch node.
+            17 => {
+                if offset == path.len() {
+                    let value = items[16].as_bytes()?;
+                    break (!value.is_empty()).then(|| value.to_vec());
+                }
+
+                match NodeRef::from_item(&items[usize::from(path[offset])])? {
+                    None => break None,
+                    Some(child) => next = child,
+                }
+                offset += 1;
+            },
+            // Leaf or extension node.
+            2 => {
+                let (node_path, is_leaf) = decode_hex_prefix(items[0].as_bytes()?)?;
+                let rest = &path[offset..];
+
+                if is_leaf {
+                    if rest != node_path.as_slice() {
+                        break None;
+                    }
+                    break Some(items[1].as_bytes()?.to_vec());
+                }
+
+                if !rest.starts_with(&node_path) {
+                    break None;
+                }
+                offset += node_path.len();
+                next = NodeRef::from_item(&items[1])?
+                    .ok_or(anyhow!("extension node without child"))?;
+            },
+            len => bail!("invalid proof node with {len} items"),
+        }
+    };
+
+    // Every node has to be on the path, so a proof can't smuggle in unchecked data.
+    if nodes.next().is_some() {
+        bail!("proof has unused nodes");
+    }
+
+    Ok(value)
+}
+
+/// Reference to a child node, either by hash or embedded when its encoding is shorter than 32
+/// bytes.
+enum NodeRef<'a> {
+    Hash([u8; 32]),
+    Inline(&'a [u8]),
+}
+
+impl<'a> NodeRef<'a> {
+    fn from_item(item: &Item<'a>) -> Result<Option<Self>> {
+        match item {
+            Item::Bytes(bytes) if bytes.is_empty() => Ok(None),
+            Item::Bytes(_) => Ok(Some(Self::Hash(item.as_hash()?))),
+            Item::List(raw, _) => Ok(Some(Self::Inline(*raw))),
+        }
+    }
+}
+
+fn nibbles(bytes: &[u8]) -> Vec<u8> {
+    bytes.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect()
+}
+
+fn decode_hex_prefix(encoded: &[u8]) -> Result<(Vec<u8>, bool)> {
+    let first = *encoded.first().ok_or(anyhow!("empty hex-prefix path"))?;
+    let is_leaf = match first >> 4 {
+        0 | 1 => false,
+        2 | 3 => true,
+        flag => bail!("invalid hex-prefix flag {flag}"),
+    };
+
+    let mut path = nibbles(&encoded[1..]);
+    // Odd paths carry their first nibble in the flag byte.
+    if first & 0x10 != 0 {
+        path.insert(0, first & 0x0f);
+    }
+
+    Ok((path, is_leaf))
+}
+
+pub fn keccak(bytes: &[u8]) -> [u8; 32] {
+    Keccak256::digest(bytes).into()
+}
+
+/// Decoded RLP item, borrowing from the encoded input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Item<'a> {
+    Bytes(&'a [u8]),
+    /// Raw encoding of the list and its decoded items.
+    List(&'a [u8], Vec<Item<'a>>),
+}
+
+impl<'a> Item<'a> {
+    pub fn as_bytes(&self) -> Result<&'a [u8]> {
+        match self {
+            Self::Bytes(bytes) => Ok(*bytes),
+            Self::List(..) => bail!("expected RLP bytes, found list"),
+        }
+    }
+
+    pub fn as_hash(&self) -> Result<[u8; 32]> {
+        self.as_bytes()?
+            .try_into()
+            .map_err(|_| anyhow!("expected 32 bytes"))
+    }
+
+    pub fn as_u64(&self) -> Result<u64> {
+        let bytes = self.as_bytes()?;
+        if bytes.len() > 8 {
+            bail!("integer does not fit into 64 bits");
+        }
+
+        Ok(bytes
+            .iter()
+            .fold(0u64, |acc, byte| (acc << 8) | u64::from(*byte)))
+    }
+
+    pub fn as_u256(&self) -> Result<[u8; 32]> {
+        let bytes = self.as_bytes()?;
+        if bytes.len() > 32 {
+            bail!("integer does not fit into 256 bits");
+        }
+
+        let mut value = [0u8; 32];
+        value[32 - bytes.len()..].copy_from_slice(bytes);
+        Ok(value)
+    }
+}
+
+/// Minimal recursive-length prefix codec.
+pub mod rlp {
+    use anyhow::{anyhow, bail, Result};
+
+    use super::Item;
+
+    /// Decodes a single item spanning the whole input.
+    pub fn decode(data: &[u8]) -> Result<Item<'_>> {
+        let (item, rest) = decode_item(data)?;
+        if !rest.is_empty() {
+            bail!("trailing bytes after RLP item");
+        }
+
+        Ok(item)
+    }
+
+    fn decode_item(data: &[u8]) -> Result<(Item<'_>, &[u8])> {
+        let prefix = *data.first().ok_or(anyhow!("unexpected end of RLP input"))?;
+
+        let (is_list, offset, len) = match prefix {
+            0x00..=0x7f => return Ok((Item::Bytes(&data[..1]), &data[1..])),
+            0x80..=0xb7 => (false, 1, usize::from(prefix - 0x80)),
+            0xb8..=0xbf => {
+                let len_of_len = usize::from(prefix - 0xb7);
+                (false, 1 + len_of_len, read_len(&data[1..], len_of_len)?)
+            },
+            0xc0..=0xf7 => (true, 1, usize::from(prefix - 0xc0)),
+            0xf8..=0xff => {
+                let len_of_len = usize::from(prefix - 0xf7);
+                (true, 1 + len_of_len, read_len(&data[1..], len_of_len)?)
+            },
+        };
+
+        let end = offset
+            .checked_add(len)
+            .filter(|end| *end <= data.len())
+            .ok_or(anyhow!("RLP item exceeds input"))?;
+        let (raw, rest) = data.split_at(end);
+        let payload = &raw[offset..];
+
+        if !is_list {
+            return Ok((Item::Bytes(payload), rest));
+        }
+
+        let mut items = Vec::new();
+        let mut remaining = payload;
+        while !remaining.is_empty() {
+            let (item, next) = decode_item(remaining)?;
+            items.push(item);
+            remaining = next;
+        }
+
+        Ok((Item::List(raw, items), rest))
+    }
+
+    fn read_len(data: &[u8], len_of_len: usize) -> Result<usize> {
+        if len_of_len > data.len() || len_of_len > std::mem::size_of::<usize>() {
+            bail!("invalid RLP length prefix");
+        }
+
+        Ok(data[..len_of_len]
+            .iter()
+            .fold(0usize, |acc, byte| (acc << 8) | usize::from(*byte)))
+    }
+
+    /// Encodes a byte string.
+    pub fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
+        if bytes.len() == 1 && bytes[0] < 0x80 {
+            return bytes.to_vec();
+        }
+
+        [encode_len(bytes.len(), 0x80), bytes.to_vec()].concat()
+    }
+
+    /// Encodes an unsigned integer as a big-endian byte string without leading zeros.
+    pub fn encode_u64(value: u64) -> Vec<u8> {
+        let bytes = value.to_be_bytes();
+        let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+
+        encode_bytes(&bytes[start..])
+    }
+
+    /// Encodes a list of already encoded items.
+    pub fn encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+        let payload = items.concat();
+
+        [encode_len(payload.len(), 0xc0), payload].concat()
+    }
+
+    fn encode_len(len: usize, offset: u8) -> Vec<u8> {
+        if len <= 55 {
+            return vec![offset + len as u8];
+        }
+
+        let bytes = len.to_be_bytes();
+        let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+        [
+            vec![offset + 55 + (bytes.len() - start) as u8],
+            bytes[start..].to_vec(),
+        ]
+        .concat()
+    }
+}
@@ -0,0 +1,119 @@
+//! Transactions signed by a session key on behalf of the hub address that granted it. Session
+//! transactions use the [`Tx`] format, signed with the session key instead of the owner's key.
+use borsh::BorshDeserialize;
+use sov_modules_api::{
+    capabilities::{AuthenticationError, AuthenticationResult, AuthorizationData, FatalError},
+    transaction::{
+        AuthenticatedTransactionAndRawHash,
+        AuthenticatedTransactionData,
+        Credentials,
+        TransactionVerificationError,
+    },
+    CryptoSpec,
+    DispatchCall,
+    GasMeter,
+    MeteredHasher,
+    PreExecWorkingSet,
+    Spec,
+};
+use sov_rollup_interface::TxHash;
+
+use crate::{hub_addr_to_credential_id, vk_to_eth_address, Tx, CHAIN_ID};
+
+/// Message the Ethereum key of the hub address `addr` signs to grant the session key `key` the
+/// calls `allowed_calls` until `expires_at_slot` on `chain_id`.
+pub fn grant_session_key_msg(
+    addr: &str,
+    key: &str,
+    allowed_calls: &[&str],
+    expires_at_slot: u64,
+    chain_id: u64,
+) -> Vec<u8> {
+    format!(
+        "Grant Filament Hub session key {key} for {addr} the calls {} until slot \
+         {expires_at_slot} on chain {chain_id}",
+        allowed_calls.join(", ")
+    )
+    .into_bytes()
+}
+
+/// Authenticates a transaction signed by a session key. `owner` checks that the session key may
+/// send the call and returns the hub address the transaction is executed for.
+pub fn authenticate<S, D, Meter, F>(
+    raw_tx: &[u8],
+    owner: F,
+    state: &mut PreExecWorkingSet<S, Meter>,
+) -> AuthenticationResult<S, D::Decodable, AuthorizationData<S>>
+where
+    S: Spec,
+    D: DispatchCall<Spec = S>,
+    Meter: GasMeter<S::Gas>,
+    F: FnOnce(
+        &[u8; 20],
+        &D::Decodable,
+        &mut PreExecWorkingSet<S, Meter>,
+    ) -> anyhow::Result<S::Address>,
+{
+    let raw_tx_hash = MeteredHasher::<
+        S::Gas,
+        PreExecWorkingSet<S, Meter>,
+        <S::CryptoSpec as CryptoSpec>::Hasher,
+    >::digest(raw_tx, state)
+    .map(TxHash::new)
+    .map_err(|e| AuthenticationError::Invalid(e.to_string()))?;
+
+    let tx = <Tx<S> as BorshDeserialize>::try_from_slice(raw_tx).map_err(|e| {
+        AuthenticationError::FatalError(FatalError::DeserializationFailed(e.to_string()))
+    })?;
+
+    if tx.details.chain_id != CHAIN_ID {
+        return Err(AuthenticationError::FatalError(
+            FatalError::InvalidChainId {
+                expected: CHAIN_ID,
+                got: tx.details.chain_id,
+            },
+        ));
+    }
+
+    let (vk, _) = tx.verify().map_err(|e| match e {
+        TransactionVerificationError::BadSignature(_)
+        | TransactionVerificationError::TransactionDeserializationError(_) => {
+            AuthenticationError::FatalError(FatalError::SigVerificationFailed(e.to_string()))
+        },
+        TransactionVerificationError::GasError(_) => AuthenticationError::Invalid(e.to_string()),
+    })?;
+
+    let runtime_call = D::decode_call(&tx.runtime_msg, state).map_err(|e| {
+        AuthenticationError::FatalError(FatalError::MessageDecodingFailed(
+            e.to_string(),
+            raw_tx_hash,
+        ))
+    })?;
+
+    // The transaction is executed for, and uses the nonce of, the owner of the session key.
+    let address = owner(&vk_to_eth_address(&vk), &runtime_call, state).map_err(|e| {
+        AuthenticationError::FatalError(FatalError::SigVerificationFailed(e.to_string()))
+    })?;
+    let credential_id =
+        hub_addr_to_credential_id::<<S::CryptoSpec as CryptoSpec>::Hasher, S>(&address);
+    let credentials = Credentials::new(credential_id);
+
+    Ok((
+        AuthenticatedTransactionAndRawHash {
+            raw_tx_hash,
+            authenticated_tx: AuthenticatedTransactionData {
+                chain_id: tx.details.chain_id,
+                gas_limit: tx.details.gas_limit,
+                max_fee: tx.details.max_fee,
+                max_priority_fee_bips: tx.details.max_priority_fee_bips,
+            },
+        },
+        AuthorizationData {
+            nonce: tx.nonce,
+            credential_id,
+            credentials,
+            default_address: Some(address),
+        },
+        runtime_call,
+    ))
+}
@@ -0,0 +1,179 @@
+use filament_hub_eth::{
+    eip712::{sign, Eip712Tx, TypedData},
+    vk_to_eth_address,
+    CHAIN_ID,
+};
+use k256::ecdsa::SigningKey;
+use sov_mock_zkvm::MockZkVerifier;
+use sov_modules_api::{
+    default_spec::DefaultSpec,
+    execution_mode::Zk,
+    transaction::{PriorityFeeBips, TxDetails},
+};
+
+type TestSpec = DefaultSpec<MockZkVerifier, MockZkVerifier, Zk>;
+
+const INIT_CALL: &str = r#"{"core":{"init":{"campaign_id":1}}}"#;
+
+fn generate_test_tx(signature: Vec<u8>, call: &str) -> Eip712Tx<TestSpec> {
+    Eip712Tx {
+        signature,
+        call: call.to_string(),
+        nonce: 0,
+        details: TxDetails {
+            chain_id: CHAIN_ID,
+            max_priority_fee_bips: PriorityFeeBips::ZERO,
+            max_fee: 100,
+            gas_limit: None,
+        },
+    }
+}
+
+fn generate_signed_tx(signing_key: &SigningKey, call: &str) -> Eip712Tx<TestSpec> {
+    let tx = generate_test_tx(vec![], call);
+    let signature = sign(signing_key, &tx.signing_hash().unwrap()).unwrap();
+
+    Eip712Tx {
+        signature: signature.to_vec(),
+        ..tx
+    }
+}
+
+fn typed_data(call: &str) -> TypedData {
+    TypedData::transaction(&serde_json::from_str(call).unwrap(), 0, CHAIN_ID, 100, 0).unwrap()
+}
+
+/// The `Mail` example of the EIP-712 specification, whose hash wallets reproduce.
+#[test]
+fn hash_specification_example() {
+    let typed_data: TypedData = serde_json::from_value(serde_json::json!({
+        "types": {
+            "EIP712Domain": [
+                { "name": "name", "type": "string" },
+                { "name": "version", "type": "string" },
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" },
+            ],
+            "Person": [
+                { "name": "name", "type": "string" },
+                { "name": "wallet", "type": "address" },
+            ],
+            "Mail": [
+                { "name": "from", "type": "Person" },
+                { "name": "to", "type": "Person" },
+                { "name": "contents", "type": "string" },
+            ],
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC",
+        },
+        "message": {
+            "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+            "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+            "contents": "Hello, Bob!",
+        },
+    }))
+    .unwrap();
+
+    assert_eq!(
+        typed_data.encode_type("Mail").unwrap(),
+        "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+    );
+    assert_eq!(
+        hex::encode(
+            typed_data
+                .hash_struct("EIP712Domain", &typed_data.domain)
+                .unwrap()
+        ),
+        "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+    );
+    assert_eq!(
+        hex::encode(typed_data.signing_hash().unwrap()),
+        "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+    );
+}
+
+#[test]
+fn transaction_typed_data() {
+    let typed_data = typed_data(INIT_CALL);
+
+    assert_eq!(
+        typed_data.encode_type("Transaction").unwrap(),
+        "Transaction(Call call,uint64 nonce,uint256 maxFee,uint64 maxPriorityFeeBips)Call(CallCore \
+         core)CallCore(CallCoreInit init)CallCoreInit(uint256 campaign_id)"
+    );
+    assert_eq!(
+        typed_data.message["call"],
+        serde_json::json!({ "core": { "init": { "campaign_id": "1" } } })
+    );
+    assert_eq!(
+        hex::encode(typed_data.signing_hash().unwrap()),
+        "0fc3a6fc1f70d604bf4ff7dfc2e476343a37a32801e0b84d65da13486a569a4d"
+    );
+    assert_eq!(
+        generate_test_tx(vec![], INIT_CALL).signing_hash().unwrap(),
+        typed_data.signing_hash().unwrap()
+    );
+}
+
+#[test]
+fn transaction_typed_data_shapes() {
+    // Unset options are left out, as the runtime treats them like missing fields.
+    let confirm =
+        typed_data(r#"{"core":{"confirm_criteria":{"campaign_id":1,"proposal_id":null}}}"#);
+    assert_eq!(
+        confirm.encode_type("CallCoreConfirmCriteria").unwrap(),
+        "CallCoreConfirmCriteria(uint256 campaign_id)"
+    );
+
+    // Arrays of objects share the struct type of their path.
+    let updates = typed_data(
+        r#"{"core":{"update_voting_powers":{"eth_block":3,"updates":[{"power":1},{"power":2}]}}}"#,
+    );
+    assert_eq!(
+        updates.encode_type("CallCoreUpdateVotingPowers").unwrap(),
+        "CallCoreUpdateVotingPowers(uint256 eth_block,CallCoreUpdateVotingPowersUpdates[] \
+         updates)CallCoreUpdateVotingPowersUpdates(uint256 power)"
+    );
+
+    for call in [
+        r#"{"core":{"init":{"campaign_id":1.5}}}"#,
+        r#"{"core":{"init":{"campaign id":1}}}"#,
+        r#"{"core":{"evict":{"evictions":[1,"a"]}}}"#,
+        r#"{"core":{"evict":{"evictions":[{"a":1},{"b":1}]}}}"#,
+        r#"[{"core":{"init":{"campaign_id":1}}}]"#,
+    ] {
+        assert!(
+            TypedData::transaction(&serde_json::from_str(call).unwrap(), 0, CHAIN_ID, 100, 0)
+                .is_err(),
+            "{call}"
+        );
+    }
+}
+
+#[test]
+fn verify_signed_tx() {
+    let signing_key = SigningKey::from_slice(&[7u8; 32]).unwrap();
+    let eth_address = vk_to_eth_address(signing_key.verifying_key());
+
+    let tx = generate_signed_tx(&signing_key, INIT_CALL);
+    assert_eq!(vk_to_eth_address(&tx.verify().unwrap()), eth_address);
+
+    // A signature doesn't carry over to a different call.
+    let tampered = Eip712Tx {
+        call: r#"{"core":{"init":{"campaign_id":2}}}"#.to_string(),
+        ..tx.clone()
+    };
+    if let Ok(vk) = tampered.verify() {
+        assert_ne!(vk_to_eth_address(&vk), eth_address);
+    }
+
+    let tampered = Eip712Tx { nonce: 1, ..tx };
+    if let Ok(vk) = tampered.verify() {
+        assert_ne!(vk_to_eth_address(&vk), eth_address);
+    }
+}
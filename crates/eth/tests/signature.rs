@@ -0,0 +1,119 @@
+use filament_hub_eth::{recovery_id, sign, vk_to_eth_address, Tx, CHAIN_ID};
+use k256::ecdsa::SigningKey;
+use proptest::prelude::*;
+use sov_mock_zkvm::MockZkVerifier;
+use sov_modules_api::{
+    default_spec::DefaultSpec,
+    execution_mode::Zk,
+    transaction::{PriorityFeeBips, TxDetails},
+};
+
+type TestSpec = DefaultSpec<MockZkVerifier, MockZkVerifier, Zk>;
+
+fn generate_test_tx(signature: Vec<u8>, chain_id: u64) -> Tx<TestSpec> {
+    Tx {
+        signature,
+        runtime_msg: vec![2, 4, 27, 135],
+        nonce: 0,
+        details: TxDetails {
+            chain_id,
+            max_priority_fee_bips: PriorityFeeBips::ZERO,
+            max_fee: 100,
+            gas_limit: None,
+        },
+    }
+}
+
+fn generate_signed_tx(signing_key: &SigningKey, chain_id: u64) -> Tx<TestSpec> {
+    let tx = generate_test_tx(vec![], chain_id);
+    let msg = borsh::to_vec(&tx.to_unsigned_transaction()).unwrap();
+    let signature = sign(signing_key, msg).unwrap();
+
+    Tx {
+        signature: signature.to_vec(),
+        ..tx
+    }
+}
+
+/// Replaces the legacy `v` of a signature with `v`, encoded big-endian without leading zeros.
+fn with_v(signature: &[u8], v: u64) -> Vec<u8> {
+    let v = v.to_be_bytes();
+    let start = v.iter().position(|byte| *byte != 0).unwrap_or(7);
+
+    [&signature[..64], &v[start..]].concat()
+}
+
+#[test]
+fn recovery_ids() {
+    assert_eq!(recovery_id(0, CHAIN_ID).map(|id| id.to_byte()), Some(0));
+    assert_eq!(recovery_id(1, CHAIN_ID).map(|id| id.to_byte()), Some(1));
+    assert_eq!(recovery_id(27, CHAIN_ID).map(|id| id.to_byte()), Some(0));
+    assert_eq!(recovery_id(28, CHAIN_ID).map(|id| id.to_byte()), Some(1));
+    assert_eq!(
+        recovery_id(CHAIN_ID * 2 + 35, CHAIN_ID).map(|id| id.to_byte()),
+        Some(0)
+    );
+    assert_eq!(
+        recovery_id(CHAIN_ID * 2 + 36, CHAIN_ID).map(|id| id.to_byte()),
+        Some(1)
+    );
+
+    assert!(recovery_id(2, CHAIN_ID).is_none());
+    assert!(recovery_id(29, CHAIN_ID).is_none());
+    assert!(recovery_id(34, CHAIN_ID).is_none());
+    assert!(recovery_id((CHAIN_ID + 1) * 2 + 35, CHAIN_ID).is_none());
+    assert!(recovery_id(u64::MAX, CHAIN_ID).is_none());
+}
+
+#[test]
+fn verify_recovery_id_encodings() {
+    let signing_key = SigningKey::from_slice(&[7u8; 32]).unwrap();
+    let eth_address = vk_to_eth_address(signing_key.verifying_key());
+
+    for chain_id in [0, 1, CHAIN_ID, 4321, u64::MAX / 4] {
+        let tx = generate_signed_tx(&signing_key, chain_id);
+        let parity = u64::from(tx.signature[64] - 27);
+
+        for v in [parity, parity + 27, chain_id * 2 + 35 + parity] {
+            let tx = Tx {
+                signature: with_v(&tx.signature, v),
+                ..tx.clone()
+            };
+            let (vk, _) = tx.verify().unwrap();
+
+            assert_eq!(vk_to_eth_address(&vk), eth_address, "v = {v}");
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn verify_arbitrary_signature(signature in prop::collection::vec(any::<u8>(), 0..100)) {
+        let _ = generate_test_tx(signature, CHAIN_ID).verify();
+    }
+
+    #[test]
+    fn verify_arbitrary_recovery_id(v in any::<u64>()) {
+        let signing_key = SigningKey::from_slice(&[7u8; 32]).unwrap();
+        let eth_address = vk_to_eth_address(signing_key.verifying_key());
+        let tx = generate_signed_tx(&signing_key, CHAIN_ID);
+        let parity = tx.signature[64] - 27;
+        let tx = Tx {
+            signature: with_v(&tx.signature, v),
+            ..tx
+        };
+
+        match recovery_id(v, CHAIN_ID) {
+            None => prop_assert!(tx.verify().is_err()),
+            Some(recid) if recid.to_byte() == parity => {
+                let (vk, _) = tx.verify().unwrap();
+                prop_assert_eq!(vk_to_eth_address(&vk), eth_address);
+            },
+            Some(_) => {
+                if let Ok((vk, _)) = tx.verify() {
+                    prop_assert_ne!(vk_to_eth_address(&vk), eth_address);
+                }
+            },
+        }
+    }
+}
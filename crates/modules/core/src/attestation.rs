@@ -0,0 +1,55 @@
+use sov_mock_zkvm::MockZkVerifier;
+use sov_modules_api::{default_spec::DefaultSpec, execution_mode::Zk, Spec};
+
+use crate::{Power, Relayer};
+
+/// Observation of the voting power of an address at an Ethereum block, submitted by a relayer.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(bound = "S: ::sov_modules_api::Spec", rename = "Attestation")
+)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    ts_rs::TS,
+)]
+#[serde(bound = "S::Address: serde::Serialize + serde::de::DeserializeOwned")]
+#[ts(export, concrete(S = DefaultSpec<MockZkVerifier, MockZkVerifier, Zk>))]
+#[ts(export_to = "../../../../bindings/Attestation.ts")]
+pub struct Attestation<S: Spec> {
+    #[ts(type = "string")]
+    pub relayer: Relayer<S>,
+    pub power: Power,
+}
+
+/// Attestations for the same address and Ethereum block that disagree on the power.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(bound = "S: ::sov_modules_api::Spec", rename = "AttestationConflict")
+)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    ts_rs::TS,
+)]
+#[serde(bound = "S::Address: serde::Serialize + serde::de::DeserializeOwned")]
+#[ts(export, concrete(S = DefaultSpec<MockZkVerifier, MockZkVerifier, Zk>))]
+#[ts(export_to = "../../../../bindings/AttestationConflict.ts")]
+pub struct AttestationConflict<S: Spec> {
+    pub eth_block: u64,
+    pub attestations: Vec<Attestation<S>>,
+}
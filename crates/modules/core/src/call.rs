@@ -1,21 +1,30 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use anyhow::{anyhow, bail, Result};
+use sov_bank::Coins;
 use sov_mock_zkvm::MockZkVerifier;
 use sov_modules_api::{
     default_spec::DefaultSpec,
     execution_mode::Zk,
     EventEmitter as _,
     Spec,
+    StateAccessor,
+    StateReader,
     TxState,
 };
+use sov_state::User;
 
 use crate::{
+    attestation::{Attestation, AttestationConflict},
     campaign::{Campaign, Phase},
     criteria::{Criteria, CriteriaProposal},
     delegate::Eviction,
+    light_client::{EthHeader, HeaderSubmission},
+    multisig::{Multisig, MultisigSubmission},
+    rewards::{self, ObjectiveMeasurement, ObjectiveSubmission, RewardPool},
     segment::Segment,
-    voting::{CriteriaVote, DistributionVote},
+    session::{SessionCall, SessionKey},
+    voting::{self, CriteriaVote, DistributionVote, PowerCheckpoint, Tally},
     Core,
     Event,
     Power,
@@ -23,6 +32,8 @@ use crate::{
 
 /// This enumeration represents the available call messages for interacting with
 /// the `Core` module.
+///
+/// Variants are borsh-encoded by their position, new ones go at the end.
 #[cfg_attr(
     feature = "native",
     derive(schemars::JsonSchema),
@@ -105,11 +116,140 @@ pub enum CallMessage<S: Spec> {
     },
 
     // Voting
+    /// Untagged update, kept with its original encoding for relayers that don't send the eth
+    /// block yet. Rejected once tagged updates were applied, see
+    /// [`CallMessage::UpdateVotingPowerAt`].
     UpdateVotingPower {
         #[ts(type = "string")]
         address: S::Address,
         power: Power,
     },
+    UpdateVotingPowers {
+        #[ts(type = "Array<[string, number]>")]
+        updates: Vec<(S::Address, Power)>,
+        eth_block: u64,
+    },
+
+    // Rewards
+    FundRewardPool {
+        campaign_id: u64,
+        amount: u64,
+    },
+    /// Posts the objective measurement of a campaign. Rewards are paid out of the escrow once
+    /// the attestation threshold of registered relayers posted the same measurement.
+    PostObjective {
+        campaign_id: u64,
+        measurement: ObjectiveMeasurement,
+    },
+
+    // Attestation
+    SetAttestationThreshold {
+        threshold: u64,
+    },
+    AttestVotingPower {
+        #[ts(type = "string")]
+        address: S::Address,
+        power: Power,
+        eth_block: u64,
+    },
+
+    // Light client
+    SubmitEthHeaders {
+        headers: Vec<Vec<u8>>,
+    },
+    ProveVotingPower {
+        eth_address: String,
+        eth_block: u64,
+        account_proof: Vec<Vec<u8>>,
+        storage_proof: Vec<Vec<u8>>,
+    },
+
+    // Accounts
+    LinkEthAddress {
+        eth_address: String,
+        signature: Vec<u8>,
+    },
+    UnlinkEthAddress {},
+
+    // Multisig
+    UpdateMultisig {
+        address: String,
+        owners: Vec<String>,
+        threshold: u64,
+        eth_block: u64,
+        /// Proof that the wallet has code at `eth_block`, required with attestation mode
+        /// disabled.
+        account_proof: Vec<Vec<u8>>,
+    },
+
+    // Session
+    GrantSessionKey {
+        key: String,
+        allowed_calls: Vec<SessionCall>,
+        expires_at_slot: u64,
+        /// Signature of the sender's Ethereum key over
+        /// [`filament_hub_eth::session::grant_session_key_msg`].
+        signature: Vec<u8>,
+    },
+    RevokeSessionKey {
+        key: String,
+    },
+    ExecuteSession {
+        key: String,
+        call: Box<CallMessage<S>>,
+    },
+
+    // Campaign
+    ConfirmDistribution {
+        campaign_id: u64,
+    },
+
+    // Voting
+    /// Update of the voting power of `address` as of `eth_block`, rejected if older than the
+    /// last applied one.
+    UpdateVotingPowerAt {
+        #[ts(type = "string")]
+        address: S::Address,
+        power: Power,
+        eth_block: u64,
+    },
+}
+
+impl<S: Spec> CallMessage<S> {
+    /// Name of the call as it is serialized, e.g. `vote_criteria`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Draft { .. } => "draft",
+            Self::Init { .. } => "init",
+            Self::ProposeCriteria { .. } => "propose_criteria",
+            Self::VoteCriteria { .. } => "vote_criteria",
+            Self::ConfirmCriteria { .. } => "confirm_criteria",
+            Self::RejectCriteria { .. } => "reject_criteria",
+            Self::IndexCampaign { .. } => "index_campaign",
+            Self::PostSegment { .. } => "post_segment",
+            Self::VoteDistribution { .. } => "vote_distribution",
+            Self::ConfirmDistribution { .. } => "confirm_distribution",
+            Self::RegisterIndexer { .. } => "register_indexer",
+            Self::UnregisterIndexer { .. } => "unregister_indexer",
+            Self::RegisterRelayer { .. } => "register_relayer",
+            Self::UnregisterRelayer { .. } => "unregister_relayer",
+            Self::UpdateVotingPower { .. } => "update_voting_power",
+            Self::UpdateVotingPowerAt { .. } => "update_voting_power_at",
+            Self::UpdateVotingPowers { .. } => "update_voting_powers",
+            Self::FundRewardPool { .. } => "fund_reward_pool",
+            Self::PostObjective { .. } => "post_objective",
+            Self::SetAttestationThreshold { .. } => "set_attestation_threshold",
+            Self::AttestVotingPower { .. } => "attest_voting_power",
+            Self::SubmitEthHeaders { .. } => "submit_eth_headers",
+            Self::ProveVotingPower { .. } => "prove_voting_power",
+            Self::LinkEthAddress { .. } => "link_eth_address",
+            Self::UnlinkEthAddress { .. } => "unlink_eth_address",
+            Self::UpdateMultisig { .. } => "update_multisig",
+            Self::GrantSessionKey { .. } => "grant_session_key",
+            Self::RevokeSessionKey { .. } => "revoke_session_key",
+            Self::ExecuteSession { .. } => "execute_session",
+        }
+    }
 }
 
 impl<S: Spec> Core<S> {
@@ -119,6 +259,7 @@ impl<S: Spec> Core<S> {
         description: String,
         criteria: Criteria,
         evictions: Vec<Eviction<S>>,
+        slot: u64,
         sender: &S::Address,
         state: &mut impl TxState<S>,
     ) -> Result<u64> {
@@ -157,32 +298,25 @@ impl<S: Spec> Core<S> {
             }
             delegates
         };
-        self.campaigns.set(
-            &campaign_id,
-            &Campaign {
-                id: campaign_id,
-                campaigner: sender.clone(),
-                phase: Phase::Draft,
+        let campaign = Campaign {
+            id: campaign_id,
+            campaigner: sender.clone(),
+            phase: Phase::Draft,
 
-                title,
-                description,
+            title,
+            description,
 
-                criteria,
+            criteria,
 
-                evictions: evictions.clone(),
-                delegates,
+            evictions: evictions.clone(),
+            delegates,
+            snapshot_slot: slot,
 
-                indexer: None,
-            },
-            state,
-        )?;
-        let mut ids = self
-            .campaigns_by_addr
-            .get(sender, state)?
-            .unwrap_or_default();
-        ids.push(campaign_id);
-        self.campaigns_index.push(&campaign_id, state)?;
-        self.campaigns_by_addr.set(sender, &ids, state)?;
+            indexer: None,
+        };
+        self.campaigns.set(&campaign_id, &campaign, state)?;
+        self.index_new_campaign(&campaign, state)?;
+        self.count_campaign(None, &campaign.phase, state)?;
 
         self.next_campaign_id.set(&(campaign_id + 1), state)?;
 
@@ -228,7 +362,7 @@ impl<S: Spec> Core<S> {
 
         // TODO(xla): Settle payment in case of evictions.
 
-        campaign.phase = Phase::Criteria;
+        self.set_campaign_phase(&mut campaign, Phase::Criteria, state)?;
 
         self.campaigns.set(&campaign_id, &campaign, state)?;
 
@@ -357,7 +491,19 @@ impl<S: Spec> Core<S> {
             bail!("invalid criteria proposal, campaign '{campaign_id}' is not in criteria phase");
         }
 
-        campaign.phase = Phase::Publish;
+        let tally = self.criteria_tally(&campaign, state)?;
+        self.criteria_tallies.set(&campaign_id, &tally, state)?;
+        self.update_stats(
+            |stats| {
+                stats.tallies += 1;
+                stats.turnout_bps_sum = stats
+                    .turnout_bps_sum
+                    .saturating_add(tally.participation_bps());
+            },
+            state,
+        )?;
+
+        self.set_campaign_phase(&mut campaign, Phase::Publish, state)?;
 
         self.campaigns.set(&campaign_id, &campaign, state)?;
 
@@ -369,7 +515,7 @@ impl<S: Spec> Core<S> {
             },
         );
 
-        tracing::info!(%campaign_id, ?proposal_id, "Criteria voted");
+        tracing::info!(%campaign_id, ?proposal_id, ?tally, "Criteria voted");
 
         Ok(())
     }
@@ -416,7 +562,12 @@ impl<S: Spec> Core<S> {
             );
         }
 
-        campaign.phase = Phase::Indexing;
+        // Campaigns without a registered indexer are indexed by their campaigner.
+        if campaign.indexer.is_none() {
+            campaign.indexer = Some(sender.clone());
+            self.index_campaign_indexer(campaign_id, sender, state)?;
+        }
+        self.set_campaign_phase(&mut campaign, Phase::Indexing, state)?;
         self.campaigns.set(&campaign_id, &campaign, state)?;
 
         self.emit_event(
@@ -469,7 +620,7 @@ impl<S: Spec> Core<S> {
             bail!("segment for '{campaign_id}' exists");
         }
 
-        campaign.phase = Phase::Distribution;
+        self.set_campaign_phase(&mut campaign, Phase::Distribution, state)?;
         self.campaigns.set(&campaign_id, &campaign, state)?;
         self.segments.set(&campaign_id, &segment, state)?;
 
@@ -532,6 +683,43 @@ impl<S: Spec> Core<S> {
 
         Ok(())
     }
+
+    pub(crate) fn confirm_distribution(
+        &self,
+        campaign_id: u64,
+        sender: &S::Address,
+        state: &mut impl TxState<S>,
+    ) -> Result<()> {
+        tracing::info!(%sender, %campaign_id, "Distribution confirm request");
+
+        let mut campaign = self
+            .campaigns
+            .get(&campaign_id, state)?
+            .ok_or(anyhow!("campaign '{campaign_id}' not found"))?;
+
+        if campaign.campaigner != *sender {
+            bail!("sender '{sender}' is not the campaigner");
+        }
+
+        if campaign.phase != Phase::Distribution {
+            bail!(
+                "invalid distribution confirmation, campaign '{campaign_id}' is not in \
+                 distribution phase"
+            );
+        }
+
+        let tally = self.distribution_tally(&campaign, state)?;
+        self.distribution_tallies.set(&campaign_id, &tally, state)?;
+
+        self.set_campaign_phase(&mut campaign, Phase::Settle, state)?;
+        self.campaigns.set(&campaign_id, &campaign, state)?;
+
+        self.emit_event(state, Event::DistributionConfirmed { campaign_id });
+
+        tracing::info!(%campaign_id, ?tally, "Distribution confirmed");
+
+        Ok(())
+    }
 }
 
 // Indexer handlers.
@@ -674,6 +862,19 @@ impl<S: Spec> Core<S> {
             .iter()
             .position(|each| *each == relayer)
             .ok_or(anyhow!("relayer '{relayer}' is not registered"))?;
+
+        // The remaining relayers have to be able to reach the attestation threshold.
+        let remaining = self.relayers.len(state)?.saturating_sub(1) as u64;
+        let threshold = self.attestation_threshold.get(state)?.unwrap_or_default();
+        if threshold > remaining {
+            bail!(
+                "unregistering relayer '{}' would leave {} relayers, below the attestation threshold {}",
+                relayer,
+                remaining,
+                threshold
+            );
+        }
+
         self.relayers.remove(pos, state)?;
 
         self.emit_event(
@@ -691,14 +892,17 @@ impl<S: Spec> Core<S> {
 
 // Voting
 impl<S: Spec> Core<S> {
+    /// Updates the voting power of `addr`, as of `eth_block` for tagged updates.
     pub(crate) fn update_voting_power(
         &self,
         addr: S::Address,
         power: u64,
+        eth_block: Option<u64>,
+        slot: u64,
         sender: S::Address,
         state: &mut impl TxState<S>,
     ) -> Result<()> {
-        tracing::info!(%addr, %power, %sender, "Update voting power request");
+        tracing::info!(%addr, %power, ?eth_block, %slot, %sender, "Update voting power request");
 
         // Only registered relayers are allowed to update voting power.
         self.relayers
@@ -708,21 +912,20 @@ impl<S: Spec> Core<S> {
             .find(|each| *each == sender)
             .ok_or(anyhow!("sender '{}' is not a registered relayer", sender))?;
 
-        self.powers.set(&addr, &power, state)?;
-        let mut index = self
-            .powers_index
-            .iter(state)?
-            .collect::<Result<Vec<_>, _>>()?;
-
-        if let Some((_, stored)) = index.iter_mut().find(|(stored, _)| *stored == addr) {
-            *stored = power;
-        } else {
-            index.push((addr.clone(), power));
+        self.ensure_direct_power_updates(state)?;
+        match eth_block {
+            Some(eth_block) => self.ensure_fresh_eth_block(eth_block, state)?,
+            // Untagged updates can't be ordered against tagged ones.
+            None if self.last_eth_block.get(state)?.is_some() => {
+                bail!("voting power updates have to be tagged with an eth block")
+            },
+            None => {},
         }
 
-        index.sort_unstable_by(|a, b| b.1.cmp(&a.1));
-
-        self.powers_index.set_all(index, state)?;
+        self.set_voting_power(&addr, power, slot, state)?;
+        if let Some(eth_block) = eth_block {
+            self.last_eth_block.set(&eth_block, state)?;
+        }
 
         self.emit_event(
             state,
@@ -736,4 +939,1334 @@ impl<S: Spec> Core<S> {
 
         Ok(())
     }
+
+    pub(crate) fn update_voting_powers(
+        &self,
+        updates: Vec<(S::Address, Power)>,
+        eth_block: u64,
+        slot: u64,
+        sender: S::Address,
+        state: &mut impl TxState<S>,
+    ) -> Result<()> {
+        tracing::info!(updates = updates.len(), %eth_block, %slot, %sender, "Update voting powers request");
+
+        // Only registered relayers are allowed to update voting power.
+        self.relayers
+            .iter(state)?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .find(|each| *each == sender)
+            .ok_or(anyhow!("sender '{}' is not a registered relayer", sender))?;
+
+        self.ensure_direct_power_updates(state)?;
+
+        if updates.is_empty() {
+            bail!("no voting power updates provided");
+        }
+
+        self.ensure_fresh_eth_block(eth_block, state)?;
+
+        let mut seen = HashSet::with_capacity(updates.len());
+        for (addr, _) in &updates {
+            if !seen.insert(addr) {
+                bail!("duplicate voting power update for '{}'", addr);
+            }
+        }
+
+        for (addr, power) in &updates {
+            self.set_voting_power(addr, *power, slot, state)?;
+
+            self.emit_event(
+                state,
+                Event::<S>::VotingPowerUpdated {
+                    addr: addr.clone(),
+                    power: *power,
+                    relayer: sender.clone(),
+                },
+            );
+        }
+
+        self.last_eth_block.set(&eth_block, state)?;
+
+        self.emit_event(
+            state,
+            Event::<S>::VotingPowersUpdated {
+                count: updates.len() as u64,
+                eth_block,
+                relayer: sender.clone(),
+            },
+        );
+        tracing::info!(updates = updates.len(), %eth_block, %sender, "Voting powers updated");
+
+        Ok(())
+    }
+
+    /// Direct voting power updates by a single relayer are only accepted while attestation mode
+    /// is disabled.
+    fn ensure_direct_power_updates(&self, state: &mut impl TxState<S>) -> Result<()> {
+        match self.attestation_threshold.get(state)? {
+            Some(threshold) if threshold > 0 => bail!(
+                "direct voting power updates are disabled, attestation threshold is {}",
+                threshold
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Direct updates must not be older than the ones already applied.
+    fn ensure_fresh_eth_block(&self, eth_block: u64, state: &mut impl TxState<S>) -> Result<()> {
+        match self.last_eth_block.get(state)? {
+            Some(last_eth_block) if eth_block < last_eth_block => bail!(
+                "eth block {} is older than the last applied eth block {}",
+                eth_block,
+                last_eth_block
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the total voting power at `slot`, the quorum denominator of campaigns snapshotted
+    /// at that slot.
+    pub(crate) fn total_voting_power_at<Accessor: StateAccessor>(
+        &self,
+        slot: u64,
+        state: &mut Accessor,
+    ) -> Result<Power, <Accessor as StateReader<User>>::Error> {
+        // Binary search for the last checkpoint at or before the slot.
+        let (mut lo, mut hi) = (0, self.total_power_checkpoints.len(state)?);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            match self.total_power_checkpoints.get(mid, state)? {
+                Some(checkpoint) if checkpoint.slot <= slot => lo = mid + 1,
+                _ => hi = mid,
+            }
+        }
+
+        match lo.checked_sub(1) {
+            Some(idx) => Ok(self
+                .total_power_checkpoints
+                .get(idx, state)?
+                .map(|checkpoint| checkpoint.power)
+                .unwrap_or_default()),
+            None => Ok(0),
+        }
+    }
+
+    /// Tallies the current criteria votes of `campaign` against the total voting power at its
+    /// snapshot slot.
+    pub(crate) fn criteria_tally<Accessor: StateAccessor>(
+        &self,
+        campaign: &Campaign<S>,
+        state: &mut Accessor,
+    ) -> Result<Tally, <Accessor as StateReader<User>>::Error> {
+        let votes = self
+            .criteria_votes
+            .get(&campaign.id, state)?
+            .unwrap_or_default();
+        let total = self.total_voting_power_at(campaign.snapshot_slot, state)?;
+
+        Ok(voting::tally_criteria(&campaign.delegates, &votes, total))
+    }
+
+    /// Tallies the current distribution votes of `campaign` against the total voting power at
+    /// its snapshot slot.
+    pub(crate) fn distribution_tally<Accessor: StateAccessor>(
+        &self,
+        campaign: &Campaign<S>,
+        state: &mut Accessor,
+    ) -> Result<Tally, <Accessor as StateReader<User>>::Error> {
+        let votes = self
+            .distribution_votes
+            .get(&campaign.id, state)?
+            .unwrap_or_default();
+        let total = self.total_voting_power_at(campaign.snapshot_slot, state)?;
+
+        Ok(voting::tally_distribution(
+            &campaign.delegates,
+            &votes,
+            total,
+        ))
+    }
+
+    /// Records `power` for `addr` at `slot` in the current powers, the checkpoints and the index.
+    fn set_voting_power(
+        &self,
+        addr: &S::Address,
+        power: Power,
+        slot: u64,
+        state: &mut impl TxState<S>,
+    ) -> Result<()> {
+        let old_power = self.powers.get(addr, state)?.unwrap_or_default();
+        self.powers.set(addr, &power, state)?;
+
+        let total = self
+            .total_voting_power
+            .get(state)?
+            .unwrap_or_default()
+            .saturating_sub(old_power)
+            .saturating_add(power);
+        self.total_voting_power.set(&total, state)?;
+
+        let last = match self.total_power_checkpoints.len(state)?.checked_sub(1) {
+            Some(idx) => self
+                .total_power_checkpoints
+                .get(idx, state)?
+                .map(|checkpoint| (idx, checkpoint)),
+            None => None,
+        };
+        match last {
+            Some((idx, checkpoint)) if checkpoint.slot == slot => {
+                self.total_power_checkpoints.set(
+                    idx,
+                    &PowerCheckpoint { slot, power: total },
+                    state,
+                )?;
+            },
+            _ => self
+                .total_power_checkpoints
+                .push(&PowerCheckpoint { slot, power: total }, state)?,
+        }
+
+        self.push_power_checkpoint(addr, PowerCheckpoint { slot, power }, state)?;
+
+        self.index_power(addr, power, state)?;
+
+        Ok(())
+    }
+
+    /// Appends `checkpoint` to the history of `addr`, replacing the last checkpoint if it is of
+    /// the same slot.
+    pub(crate) fn push_power_checkpoint<Accessor: StateAccessor>(
+        &self,
+        addr: &S::Address,
+        checkpoint: PowerCheckpoint,
+        state: &mut Accessor,
+    ) -> Result<(), <Accessor as StateReader<User>>::Error> {
+        let len = self
+            .power_checkpoints_len
+            .get(addr, state)?
+            .unwrap_or_default();
+        if let Some(idx) = len.checked_sub(1) {
+            let last = self.power_checkpoints.get(&(addr.clone(), idx), state)?;
+            if last.is_some_and(|last| last.slot == checkpoint.slot) {
+                return self
+                    .power_checkpoints
+                    .set(&(addr.clone(), idx), &checkpoint, state);
+            }
+        }
+
+        self.power_checkpoints
+            .set(&(addr.clone(), len), &checkpoint, state)?;
+        self.power_checkpoints_len.set(addr, &(len + 1), state)
+    }
+
+    /// Returns the voting power of `addr` at `slot` from its checkpoints.
+    pub(crate) fn voting_power_at<Accessor: StateAccessor>(
+        &self,
+        addr: &S::Address,
+        slot: u64,
+        state: &mut Accessor,
+    ) -> Result<Power, <Accessor as StateReader<User>>::Error> {
+        // Binary search for the last checkpoint at or before the slot.
+        let (mut lo, mut hi) = (
+            0,
+            self.power_checkpoints_len
+                .get(addr, state)?
+                .unwrap_or_default(),
+        );
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            match self.power_checkpoints.get(&(addr.clone(), mid), state)? {
+                Some(checkpoint) if checkpoint.slot <= slot => lo = mid + 1,
+                _ => hi = mid,
+            }
+        }
+
+        match lo.checked_sub(1) {
+            Some(idx) => Ok(self
+                .power_checkpoints
+                .get(&(addr.clone(), idx), state)?
+                .map(|checkpoint| checkpoint.power)
+                .unwrap_or_default()),
+            None => Ok(0),
+        }
+    }
+}
+
+// Reward handlers.
+impl<S: Spec> Core<S> {
+    pub(crate) fn fund_reward_pool(
+        &self,
+        campaign_id: u64,
+        amount: u64,
+        sender: &S::Address,
+        state: &mut impl TxState<S>,
+    ) -> Result<()> {
+        tracing::info!(%sender, %campaign_id, %amount, "Fund reward pool request");
+
+        let campaign = self
+            .campaigns
+            .get(&campaign_id, state)?
+            .ok_or(anyhow!("campaign '{campaign_id}' not found"))?;
+
+        if campaign.campaigner != *sender {
+            bail!("sender '{sender}' is not the campaigner");
+        }
+
+        if amount == 0 {
+            bail!("reward pool amount must be positive");
+        }
+
+        if self.objectives.get(&campaign_id, state)?.is_some() {
+            bail!("rewards for campaign '{campaign_id}' are already distributed");
+        }
+
+        let mut pool = self
+            .reward_pools
+            .get(&campaign_id, state)?
+            .unwrap_or(RewardPool {
+                funder: sender.clone(),
+                amount: 0,
+            });
+        pool.amount = pool.amount.checked_add(amount).ok_or(anyhow!(
+            "reward pool for campaign '{campaign_id}' overflows"
+        ))?;
+
+        // The pool is escrowed by the module until the rewards are distributed.
+        self.bank
+            .transfer_from(sender, self.id.to_payable(), reward_coins(amount), state)?;
+
+        self.reward_pools.set(&campaign_id, &pool, state)?;
+        self.update_stats(
+            |stats| stats.escrowed_budget = stats.escrowed_budget.saturating_add(amount),
+            state,
+        )?;
+
+        self.emit_event(
+            state,
+            Event::RewardPoolFunded {
+                campaign_id,
+                amount,
+                total: pool.amount,
+            },
+        );
+
+        tracing::info!(%campaign_id, %amount, total = %pool.amount, "Reward pool funded");
+
+        Ok(())
+    }
+
+    pub(crate) fn post_objective(
+        &self,
+        campaign_id: u64,
+        measurement: ObjectiveMeasurement,
+        sender: &S::Address,
+        state: &mut impl TxState<S>,
+    ) -> Result<()> {
+        tracing::info!(%sender, %campaign_id, ?measurement, "Post objective request");
+
+        // Only registered relayers are allowed to post objective measurements.
+        let relayers = self.relayers.iter(state)?.collect::<Result<Vec<_>, _>>()?;
+        if !relayers.contains(sender) {
+            bail!("sender '{}' is not a registered relayer", sender);
+        }
+
+        let threshold = match self.attestation_threshold.get(state)? {
+            Some(threshold) if threshold > 0 => threshold,
+            _ => bail!("attestation mode is disabled"),
+        };
+
+        let campaign = self
+            .campaigns
+            .get(&campaign_id, state)?
+            .ok_or(anyhow!("campaign '{campaign_id}' not found"))?;
+
+        if !matches!(
+            campaign.phase,
+            Phase::Distribution | Phase::Settle | Phase::Settled
+        ) {
+            bail!(
+                "invalid objective, campaign '{campaign_id}' is in phase '{:?}'",
+                campaign.phase
+            );
+        }
+
+        if measurement.attributions.len() != campaign.criteria.len() {
+            bail!(
+                "invalid objective, expected {} attributions but got {}",
+                campaign.criteria.len(),
+                measurement.attributions.len()
+            );
+        }
+
+        if self.objectives.get(&campaign_id, state)?.is_some() {
+            bail!("objective for campaign '{campaign_id}' already posted");
+        }
+
+        let mut submissions = self
+            .pending_objectives
+            .get(&campaign_id, state)?
+            .unwrap_or_default();
+        if submissions
+            .iter()
+            .any(|submission| submission.relayers.contains(sender))
+        {
+            bail!(
+                "relayer '{}' already posted an objective for campaign '{campaign_id}'",
+                sender
+            );
+        }
+        let index = match submissions
+            .iter()
+            .position(|submission| submission.measurement == measurement)
+        {
+            Some(index) => index,
+            None => {
+                submissions.push(ObjectiveSubmission {
+                    measurement: measurement.clone(),
+                    relayers: vec![],
+                });
+                submissions.len() - 1
+            },
+        };
+        submissions[index].relayers.push(sender.clone());
+
+        // Only submissions of relayers that are still registered count towards the threshold.
+        let agreeing = submissions[index]
+            .relayers
+            .iter()
+            .filter(|each| relayers.contains(each))
+            .count() as u64;
+
+        self.emit_event(
+            state,
+            Event::ObjectiveAttested {
+                campaign_id,
+                relayer: sender.clone(),
+                attestations: agreeing,
+            },
+        );
+
+        if agreeing < threshold {
+            self.pending_objectives
+                .set(&campaign_id, &submissions, state)?;
+            tracing::info!(%campaign_id, %agreeing, %threshold, "Objective attested");
+
+            return Ok(());
+        }
+        self.pending_objectives.delete(&campaign_id, state)?;
+
+        let reward_pool = self.reward_pools.get(&campaign_id, state)?;
+        let pool = reward_pool
+            .as_ref()
+            .map(|pool| pool.amount)
+            .unwrap_or_default();
+        let votes = self
+            .criteria_votes
+            .get(&campaign_id, state)?
+            .unwrap_or_default();
+        let rewards = rewards::delegate_rewards(&campaign.delegates, &votes, &measurement, pool);
+
+        self.objectives.set(&campaign_id, &measurement, state)?;
+        self.delegate_rewards.set(&campaign_id, &rewards, state)?;
+
+        // Pay the rewards out of the escrow and return what rounding leaves to the funder.
+        let mut distributed = 0u64;
+        for reward in rewards.iter().filter(|reward| reward.reward > 0) {
+            let delegate = reward
+                .delegate
+                .parse::<S::Address>()
+                .map_err(|_| anyhow!("invalid delegate address '{}'", reward.delegate))?;
+            self.bank.transfer_from(
+                self.id.to_payable(),
+                &delegate,
+                reward_coins(reward.reward),
+                state,
+            )?;
+            distributed = distributed
+                .checked_add(reward.reward)
+                .ok_or(anyhow!("rewards for campaign '{campaign_id}' overflow"))?;
+        }
+        let refund = pool.checked_sub(distributed).ok_or(anyhow!(
+            "rewards for campaign '{campaign_id}' exceed the reward pool. This is a bug"
+        ))?;
+        if let Some(reward_pool) = reward_pool.filter(|_| refund > 0) {
+            self.bank.transfer_from(
+                self.id.to_payable(),
+                &reward_pool.funder,
+                reward_coins(refund),
+                state,
+            )?;
+        }
+
+        self.update_stats(
+            |stats| {
+                stats.escrowed_budget = stats.escrowed_budget.saturating_sub(pool);
+                stats.distributions += 1;
+                stats.distributed_rewards = stats.distributed_rewards.saturating_add(distributed);
+            },
+            state,
+        )?;
+
+        self.emit_event(
+            state,
+            Event::ObjectivePosted {
+                campaign_id,
+                relayer: sender.clone(),
+            },
+        );
+        self.emit_event(
+            state,
+            Event::DelegateRewardsDistributed {
+                campaign_id,
+                rewards: rewards.clone(),
+                refund,
+            },
+        );
+
+        tracing::info!(%campaign_id, ?rewards, "Delegate rewards distributed");
+
+        Ok(())
+    }
+}
+
+// Attestation handlers.
+impl<S: Spec> Core<S> {
+    pub(crate) fn set_attestation_threshold(
+        &self,
+        threshold: u64,
+        sender: S::Address,
+        state: &mut impl TxState<S>,
+    ) -> Result<()> {
+        tracing::info!(%threshold, %sender, "Set attestation threshold request");
+
+        let admin = self
+            .admin
+            .get(state)?
+            .ok_or(anyhow!("module admin is not set"))?;
+        if sender != admin {
+            bail!("sender '{sender}' is not an admin");
+        }
+
+        let relayers = self.relayers.len(state)? as u64;
+        if threshold > relayers {
+            bail!(
+                "attestation threshold {} exceeds the number of registered relayers {}",
+                threshold,
+                relayers
+            );
+        }
+
+        self.attestation_threshold.set(&threshold, state)?;
+
+        self.emit_event(
+            state,
+            Event::<S>::AttestationThresholdUpdated {
+                threshold,
+                sender: sender.clone(),
+            },
+        );
+        tracing::info!(%threshold, %sender, "Attestation threshold updated");
+
+        Ok(())
+    }
+
+    pub(crate) fn attest_voting_power(
+        &self,
+        addr: S::Address,
+        power: Power,
+        eth_block: u64,
+        slot: u64,
+        sender: S::Address,
+        state: &mut impl TxState<S>,
+    ) -> Result<()> {
+        tracing::info!(%addr, %power, %eth_block, %sender, "Attest voting power request");
+
+        // Only registered relayers are allowed to attest voting power.
+        let relayers = self.relayers.iter(state)?.collect::<Result<Vec<_>, _>>()?;
+        if !relayers.contains(&sender) {
+            bail!("sender '{}' is not a registered relayer", sender);
+        }
+
+        let threshold = match self.attestation_threshold.get(state)? {
+            Some(threshold) if threshold > 0 => threshold,
+            _ => bail!("attestation mode is disabled"),
+        };
+
+        if let Some(last_eth_block) = self.attested_eth_blocks.get(&addr, state)? {
+            if eth_block <= last_eth_block {
+                bail!(
+                    "eth block {} is not newer than the last attested eth block {} for '{}'",
+                    eth_block,
+                    last_eth_block,
+                    addr
+                );
+            }
+        }
+
+        let key = (addr.clone(), eth_block);
+        let mut attestations = self.attestations.get(&key, state)?.unwrap_or_default();
+        if attestations
+            .iter()
+            .any(|attestation| attestation.relayer == sender)
+        {
+            bail!(
+                "relayer '{}' already attested the voting power of '{}' at eth block {}",
+                sender,
+                addr,
+                eth_block
+            );
+        }
+
+        attestations.push(Attestation {
+            relayer: sender.clone(),
+            power,
+        });
+
+        self.emit_event(
+            state,
+            Event::<S>::VotingPowerAttested {
+                addr: addr.clone(),
+                power,
+                eth_block,
+                relayer: sender.clone(),
+            },
+        );
+
+        // Record observations that disagree with any previous one for the same block.
+        if attestations
+            .iter()
+            .any(|attestation| attestation.power != power)
+        {
+            let mut conflicts = self
+                .attestation_conflicts
+                .get(&addr, state)?
+                .unwrap_or_default();
+            let conflict = AttestationConflict {
+                eth_block,
+                attestations: attestations.clone(),
+            };
+            match conflicts
+                .iter_mut()
+                .find(|conflict| conflict.eth_block == eth_block)
+            {
+                Some(existing) => *existing = conflict,
+                None => conflicts.push(conflict),
+            }
+            self.attestation_conflicts.set(&addr, &conflicts, state)?;
+
+            self.emit_event(
+                state,
+                Event::<S>::AttestationConflictDetected {
+                    addr: addr.clone(),
+                    eth_block,
+                    attestations: attestations.clone(),
+                },
+            );
+            tracing::warn!(%addr, %eth_block, ?attestations, "Conflicting voting power attestations");
+        }
+
+        // Only attestations of relayers that are still registered count towards the threshold.
+        let agreeing = attestations
+            .iter()
+            .filter(|attestation| {
+                attestation.power == power && relayers.contains(&attestation.relayer)
+            })
+            .count() as u64;
+
+        if agreeing < threshold {
+            self.attestations.set(&key, &attestations, state)?;
+            let mut pending = self
+                .pending_attestation_blocks
+                .get(&addr, state)?
+                .unwrap_or_default();
+            if !pending.contains(&eth_block) {
+                pending.push(eth_block);
+                self.pending_attestation_blocks
+                    .set(&addr, &pending, state)?;
+            }
+            tracing::info!(%addr, %power, %eth_block, %agreeing, %threshold, "Voting power attested");
+
+            return Ok(());
+        }
+
+        self.set_voting_power(&addr, power, slot, state)?;
+        self.set_attested_eth_block(&addr, eth_block, state)?;
+
+        self.emit_event(
+            state,
+            Event::<S>::VotingPowerUpdated {
+                addr: addr.clone(),
+                power,
+                relayer: sender.clone(),
+            },
+        );
+        tracing::info!(%addr, %power, %eth_block, %agreeing, "Voting power updated");
+
+        Ok(())
+    }
+
+    /// Records `eth_block` as the last block the power of `addr` was applied at, dropping the
+    /// pending attestations it supersedes.
+    fn set_attested_eth_block(
+        &self,
+        addr: &S::Address,
+        eth_block: u64,
+        state: &mut impl TxState<S>,
+    ) -> Result<()> {
+        self.attested_eth_blocks.set(addr, &eth_block, state)?;
+
+        let mut pending = self
+            .pending_attestation_blocks
+            .get(addr, state)?
+            .unwrap_or_default();
+        for block in pending.iter().filter(|block| **block <= eth_block) {
+            self.attestations.delete(&(addr.clone(), *block), state)?;
+        }
+        pending.retain(|block| *block > eth_block);
+
+        if pending.is_empty() {
+            self.pending_attestation_blocks.delete(addr, state)?;
+        } else {
+            self.pending_attestation_blocks.set(addr, &pending, state)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Light client handlers.
+impl<S: Spec> Core<S> {
+    pub(crate) fn submit_eth_headers(
+        &self,
+        headers: Vec<Vec<u8>>,
+        sender: S::Address,
+        state: &mut impl TxState<S>,
+    ) -> Result<()> {
+        tracing::info!(headers = headers.len(), %sender, "Submit eth headers request");
+
+        // Only registered relayers are allowed to feed the header store.
+        let relayers = self.relayers.iter(state)?.collect::<Result<Vec<_>, _>>()?;
+        if !relayers.contains(&sender) {
+            bail!("sender '{}' is not a registered relayer", sender);
+        }
+
+        if headers.is_empty() {
+            bail!("no eth headers provided");
+        }
+
+        let config = self
+            .light_client
+            .get(state)?
+            .ok_or(anyhow!("light client is not configured"))?;
+        let checkpoint = filament_hub_eth::proof::Header::decode_hex(&config.checkpoint)?.number;
+        let mut head_number = self
+            .eth_head
+            .get(state)?
+            .ok_or(anyhow!("light client is not configured"))?;
+        // A header is stored once as many registered relayers as the attestation threshold
+        // submitted it, a single one is enough with attestation mode disabled.
+        let threshold = self
+            .attestation_threshold
+            .get(state)?
+            .unwrap_or_default()
+            .max(1);
+
+        let decoded = headers
+            .iter()
+            .map(|raw| filament_hub_eth::proof::Header::decode(raw).map(EthHeader::from))
+            .collect::<Result<Vec<_>>>()?;
+        let from = decoded[0].number;
+        let to = decoded[decoded.len() - 1].number;
+
+        // Headers have to extend the stored chain, or the headers before them in the batch, one
+        // by one. A header at or below the head competes with the stored one and replaces it,
+        // and its descendants, once it is attested by the threshold.
+        let mut previous: Option<EthHeader> = None;
+        for header in decoded {
+            let parent = match previous.take() {
+                Some(previous) if previous.number + 1 == header.number => Some(previous),
+                _ if header.number > checkpoint && header.number <= head_number + 1 => {
+                    self.eth_headers.get(&(header.number - 1), state)?
+                },
+                _ => None,
+            }
+            .ok_or(anyhow!(
+                "eth header {} does not extend the header chain at {}",
+                header.number,
+                head_number
+            ))?;
+            if header.parent_hash != parent.hash {
+                bail!(
+                    "eth header {} parent hash does not match header {}",
+                    header.number,
+                    parent.number
+                );
+            }
+            previous = Some(header.clone());
+
+            if let Some(stored) = self.eth_headers.get(&header.number, state)? {
+                if stored.hash == header.hash {
+                    let mut attesting = self
+                        .eth_header_relayers
+                        .get(&header.number, state)?
+                        .unwrap_or_default();
+                    if !attesting.contains(&sender) {
+                        attesting.push(sender.clone());
+                        self.eth_header_relayers
+                            .set(&header.number, &attesting, state)?;
+                    }
+                    continue;
+                }
+            }
+
+            let mut submissions = self
+                .pending_eth_headers
+                .get(&header.number, state)?
+                .unwrap_or_default();
+            let index = match submissions
+                .iter()
+                .position(|submission| submission.header == header)
+            {
+                Some(index) => index,
+                None => {
+                    submissions.push(HeaderSubmission {
+                        header: header.clone(),
+                        relayers: vec![],
+                    });
+                    submissions.len() - 1
+                },
+            };
+            if !submissions[index].relayers.contains(&sender) {
+                submissions[index].relayers.push(sender.clone());
+            }
+
+            // Only submissions of relayers that are still registered count towards the threshold.
+            let agreeing = submissions[index]
+                .relayers
+                .iter()
+                .filter(|relayer| relayers.contains(relayer))
+                .count() as u64;
+            // The parent may still be pending, in which case the header has to wait for it.
+            let parent_stored = self
+                .eth_headers
+                .get(&(header.number - 1), state)?
+                .is_some_and(|stored| stored.hash == header.parent_hash);
+            if agreeing < threshold || !parent_stored {
+                self.pending_eth_headers
+                    .set(&header.number, &submissions, state)?;
+                continue;
+            }
+
+            // Drop the branch the header replaces.
+            let replaced = header.number <= head_number;
+            for number in header.number..=head_number {
+                self.eth_headers.delete(&number, state)?;
+                self.eth_header_relayers.delete(&number, state)?;
+            }
+            if replaced {
+                tracing::warn!(number = %header.number, old_head = %head_number, "Eth header chain reorganized");
+            }
+
+            let submission = submissions.swap_remove(index);
+            self.eth_headers.set(&header.number, &header, state)?;
+            self.eth_header_relayers
+                .set(&header.number, &submission.relayers, state)?;
+            self.pending_eth_headers.delete(&header.number, state)?;
+            head_number = header.number;
+
+            self.emit_event(
+                state,
+                Event::<S>::EthHeaderAccepted {
+                    number: header.number,
+                    replaced,
+                },
+            );
+        }
+
+        self.eth_head.set(&head_number, state)?;
+
+        self.emit_event(
+            state,
+            Event::<S>::EthHeadersSubmitted {
+                from,
+                to,
+                relayer: sender.clone(),
+            },
+        );
+        tracing::info!(%from, %to, head = %head_number, %sender, "Eth headers submitted");
+
+        Ok(())
+    }
+
+    pub(crate) fn prove_voting_power(
+        &self,
+        eth_address: String,
+        eth_block: u64,
+        account_proof: Vec<Vec<u8>>,
+        storage_proof: Vec<Vec<u8>>,
+        slot: u64,
+        sender: S::Address,
+        state: &mut impl TxState<S>,
+    ) -> Result<()> {
+        tracing::info!(%eth_address, %eth_block, %sender, "Prove voting power request");
+
+        let config = self
+            .light_client
+            .get(state)?
+            .ok_or(anyhow!("light client is not configured"))?;
+        let header = self
+            .eth_headers
+            .get(&eth_block, state)?
+            .ok_or(anyhow!("eth header {eth_block} is unknown"))?;
+
+        // In attestation mode the header has to be backed by as many registered relayers as a
+        // direct attestation would need. The checkpoint is trusted from genesis.
+        let threshold = self.attestation_threshold.get(state)?.unwrap_or_default();
+        let checkpoint = filament_hub_eth::proof::Header::decode_hex(&config.checkpoint)?.number;
+        if threshold > 0 && eth_block != checkpoint {
+            let relayers = self.relayers.iter(state)?.collect::<Result<Vec<_>, _>>()?;
+            let attesting = self
+                .eth_header_relayers
+                .get(&eth_block, state)?
+                .unwrap_or_default()
+                .iter()
+                .filter(|relayer| relayers.contains(relayer))
+                .count() as u64;
+            if attesting < threshold {
+                bail!(
+                    "eth header {} is attested by {} relayers, below the attestation threshold {}",
+                    eth_block,
+                    attesting,
+                    threshold
+                );
+            }
+        }
+
+        let holder = filament_hub_eth::parse_eth_address(&eth_address)?;
+        let addr = filament_hub_eth::addr_to_hub_address::<S>(&eth_address)?;
+
+        if let Some(last_eth_block) = self.attested_eth_blocks.get(&addr, state)? {
+            if eth_block <= last_eth_block {
+                bail!(
+                    "eth block {} is not newer than the last attested eth block {} for '{}'",
+                    eth_block,
+                    last_eth_block,
+                    addr
+                );
+            }
+        }
+
+        let vault = filament_hub_eth::parse_eth_address(&config.vault_address)?;
+        let account =
+            filament_hub_eth::proof::verify_account(header.state_root, vault, &account_proof)?
+                .ok_or(anyhow!(
+                    "vault account does not exist at eth block {eth_block}"
+                ))?;
+        let balance = filament_hub_eth::proof::verify_storage(
+            account.storage_root,
+            filament_hub_eth::proof::mapping_slot(holder, config.balances_slot),
+            &storage_proof,
+        )?;
+        let power = filament_hub_eth::proof::scale_down(balance, config.decimals);
+
+        self.set_voting_power(&addr, power, slot, state)?;
+        self.set_attested_eth_block(&addr, eth_block, state)?;
+
+        self.emit_event(
+            state,
+            Event::<S>::VotingPowerProven {
+                addr: addr.clone(),
+                power,
+                eth_block,
+                sender: sender.clone(),
+            },
+        );
+        tracing::info!(%addr, %power, %eth_block, %sender, "Voting power proven");
+
+        Ok(())
+    }
+}
+
+// Account handlers.
+impl<S: Spec> Core<S> {
+    pub(crate) fn link_eth_address(
+        &self,
+        eth_address: String,
+        signature: Vec<u8>,
+        sender: S::Address,
+        state: &mut impl TxState<S>,
+    ) -> Result<()> {
+        tracing::info!(%eth_address, %sender, "Link eth address request");
+
+        let eth_address = normalize_eth_address(&eth_address)?;
+
+        // The Ethereum account has to sign over the hub address and chain it is linked to.
+        let msg =
+            filament_hub_eth::link_eth_address_msg(&sender.to_string(), filament_hub_eth::CHAIN_ID);
+        let signer = filament_hub_eth::recover_signer(msg, &signature)?;
+        if format!("0x{}", hex::encode(signer)) != eth_address {
+            bail!("signature for eth address '{eth_address}' is invalid");
+        }
+
+        if let Some(linked) = self.eth_addresses.get(&sender, state)? {
+            bail!("'{sender}' is already linked to eth address '{linked}'");
+        }
+        if let Some(linked) = self.hub_addresses.get(&eth_address, state)? {
+            bail!("eth address '{eth_address}' is already linked to '{linked}'");
+        }
+
+        self.eth_addresses.set(&sender, &eth_address, state)?;
+        self.hub_addresses.set(&eth_address, &sender, state)?;
+
+        self.emit_event(
+            state,
+            Event::<S>::EthAddressLinked {
+                addr: sender.clone(),
+                eth_address: eth_address.clone(),
+            },
+        );
+        tracing::info!(%eth_address, %sender, "Eth address linked");
+
+        Ok(())
+    }
+
+    pub(crate) fn unlink_eth_address(
+        &self,
+        sender: S::Address,
+        state: &mut impl TxState<S>,
+    ) -> Result<()> {
+        tracing::info!(%sender, "Unlink eth address request");
+
+        let eth_address = self
+            .eth_addresses
+            .get(&sender, state)?
+            .ok_or(anyhow!("'{sender}' is not linked to an eth address"))?;
+
+        self.eth_addresses.delete(&sender, state)?;
+        self.hub_addresses
+            .delete(&normalize_eth_address(&eth_address)?, state)?;
+
+        self.emit_event(
+            state,
+            Event::<S>::EthAddressUnlinked {
+                addr: sender.clone(),
+                eth_address: eth_address.clone(),
+            },
+        );
+        tracing::info!(%eth_address, %sender, "Eth address unlinked");
+
+        Ok(())
+    }
+}
+
+// Multisig handlers.
+impl<S: Spec> Core<S> {
+    pub(crate) fn update_multisig(
+        &self,
+        address: String,
+        owners: Vec<String>,
+        threshold: u64,
+        eth_block: u64,
+        account_proof: Vec<Vec<u8>>,
+        sender: S::Address,
+        state: &mut impl TxState<S>,
+    ) -> Result<()> {
+        tracing::info!(%address, ?owners, %threshold, %eth_block, %sender, "Update multisig request");
+
+        let relayers = self.relayers.iter(state)?.collect::<Result<Vec<_>, _>>()?;
+        let relayer = relayers
+            .iter()
+            .find(|each| **each == sender)
+            .cloned()
+            .ok_or(anyhow!("sender '{}' is not a registered relayer", sender))?;
+
+        let address = normalize_eth_address(&address)?;
+        let owners = owners
+            .iter()
+            .map(|owner| normalize_eth_address(owner))
+            .collect::<Result<Vec<_>>>()?;
+
+        if let Some(multisig) = self.multisigs.get(&address, state)? {
+            if eth_block <= multisig.eth_block {
+                bail!(
+                    "eth block {} is not newer than the last synced eth block {} for multisig '{}'",
+                    eth_block,
+                    multisig.eth_block,
+                    address
+                );
+            }
+        }
+        for (i, owner) in owners.iter().enumerate() {
+            if owners[..i].contains(owner) {
+                bail!("duplicate owner '{}' for multisig '{}'", owner, address);
+            }
+        }
+        if threshold == 0 || threshold > owners.len() as u64 {
+            bail!(
+                "threshold {} must be between 1 and the number of owners {}",
+                threshold,
+                owners.len()
+            );
+        }
+
+        let multisig = Multisig {
+            owners: owners.clone(),
+            threshold,
+            eth_block,
+        };
+        match self.attestation_threshold.get(state)?.unwrap_or_default() {
+            // A single relayer can only sync owner sets of accounts proven to be contracts.
+            0 => self.ensure_contract(&address, eth_block, &account_proof, state)?,
+            attestation_threshold => {
+                let mut submissions = self
+                    .pending_multisigs
+                    .get(&address, state)?
+                    .unwrap_or_default();
+                let index = match submissions
+                    .iter()
+                    .position(|submission| submission.multisig == multisig)
+                {
+                    Some(index) => index,
+                    None => {
+                        submissions.push(MultisigSubmission {
+                            multisig: multisig.clone(),
+                            relayers: vec![],
+                        });
+                        submissions.len() - 1
+                    },
+                };
+                if !submissions[index].relayers.contains(&relayer) {
+                    submissions[index].relayers.push(relayer.clone());
+                }
+
+                // Only submissions of relayers that are still registered count towards the
+                // threshold.
+                let agreeing = submissions[index]
+                    .relayers
+                    .iter()
+                    .filter(|each| relayers.contains(each))
+                    .count() as u64;
+                if agreeing < attestation_threshold {
+                    self.pending_multisigs.set(&address, &submissions, state)?;
+                    tracing::info!(%address, %eth_block, %agreeing, %attestation_threshold, "Multisig attested");
+
+                    return Ok(());
+                }
+
+                // Drop the submissions the accepted owner set supersedes.
+                submissions.retain(|submission| submission.multisig.eth_block > eth_block);
+                if submissions.is_empty() {
+                    self.pending_multisigs.delete(&address, state)?;
+                } else {
+                    self.pending_multisigs.set(&address, &submissions, state)?;
+                }
+            },
+        }
+
+        self.multisigs.set(&address, &multisig, state)?;
+
+        self.emit_event(
+            state,
+            Event::<S>::MultisigUpdated {
+                address: address.clone(),
+                owners: owners.clone(),
+                threshold,
+                eth_block,
+                relayer: relayer.clone(),
+            },
+        );
+        tracing::info!(%address, ?owners, %threshold, %eth_block, %relayer, "Multisig updated");
+
+        Ok(())
+    }
+
+    /// Checks `account_proof` shows the account at `address` has code at `eth_block`.
+    fn ensure_contract(
+        &self,
+        address: &str,
+        eth_block: u64,
+        account_proof: &[Vec<u8>],
+        state: &mut impl TxState<S>,
+    ) -> Result<()> {
+        let header = self
+            .eth_headers
+            .get(&eth_block, state)?
+            .ok_or(anyhow!("eth header {eth_block} is unknown"))?;
+
+        let account = filament_hub_eth::proof::verify_account(
+            header.state_root,
+            filament_hub_eth::parse_eth_address(address)?,
+            account_proof,
+        )?
+        .ok_or(anyhow!(
+            "account '{address}' does not exist at eth block {eth_block}"
+        ))?;
+        if account.code_hash == filament_hub_eth::proof::EMPTY_CODE_HASH {
+            bail!("account '{address}' has no code at eth block {eth_block}");
+        }
+
+        Ok(())
+    }
+}
+
+// Session handlers.
+impl<S: Spec> Core<S> {
+    pub(crate) fn grant_session_key(
+        &self,
+        key: String,
+        allowed_calls: Vec<SessionCall>,
+        expires_at_slot: u64,
+        signature: Vec<u8>,
+        slot: u64,
+        sender: S::Address,
+        state: &mut impl TxState<S>,
+    ) -> Result<()> {
+        tracing::info!(%key, ?allowed_calls, %expires_at_slot, %sender, "Grant session key request");
+
+        let key = normalize_eth_address(&key)?;
+
+        // The Ethereum key of the sender has to sign over the grant, so a compromised hub
+        // credential alone can't hand out session keys.
+        let msg = filament_hub_eth::session::grant_session_key_msg(
+            &sender.to_string(),
+            &key,
+            &allowed_calls
+                .iter()
+                .map(SessionCall::name)
+                .collect::<Vec<_>>(),
+            expires_at_slot,
+            filament_hub_eth::CHAIN_ID,
+        );
+        let signer = filament_hub_eth::recover_signer(msg, &signature)?;
+        let eth_address = format!("0x{}", hex::encode(signer));
+        let linked = self.eth_addresses.get(&sender, state)?;
+        if linked.as_ref() != Some(&eth_address)
+            && filament_hub_eth::bytes_to_address::<S>(signer)? != sender
+        {
+            bail!("session key grant is not signed by the eth key of '{sender}'");
+        }
+
+        if expires_at_slot <= slot {
+            bail!(
+                "session key expiry slot {} is not after the current slot {}",
+                expires_at_slot,
+                slot
+            );
+        }
+        if allowed_calls.is_empty() {
+            bail!("no calls allowed for session key '{}'", key);
+        }
+        if let Some(session_key) = self.session_keys.get(&key, state)? {
+            if session_key.owner != sender {
+                bail!(
+                    "session key '{}' is already granted to '{}'",
+                    key,
+                    session_key.owner
+                );
+            }
+        }
+
+        self.session_keys.set(
+            &key,
+            &SessionKey {
+                owner: sender.clone(),
+                allowed_calls: allowed_calls.clone(),
+                expires_at_slot,
+            },
+            state,
+        )?;
+
+        self.emit_event(
+            state,
+            Event::<S>::SessionKeyGranted {
+                addr: sender.clone(),
+                key: key.clone(),
+                allowed_calls: allowed_calls.clone(),
+                expires_at_slot,
+            },
+        );
+        tracing::info!(%key, ?allowed_calls, %expires_at_slot, %sender, "Session key granted");
+
+        Ok(())
+    }
+
+    pub(crate) fn revoke_session_key(
+        &self,
+        key: String,
+        sender: S::Address,
+        state: &mut impl TxState<S>,
+    ) -> Result<()> {
+        tracing::info!(%key, %sender, "Revoke session key request");
+
+        let key = normalize_eth_address(&key)?;
+        match self.session_keys.get(&key, state)? {
+            Some(session_key) if session_key.owner == sender => {},
+            _ => bail!("session key '{}' is not granted to '{}'", key, sender),
+        }
+
+        self.session_keys.delete(&key, state)?;
+
+        self.emit_event(
+            state,
+            Event::<S>::SessionKeyRevoked {
+                addr: sender.clone(),
+                key: key.clone(),
+            },
+        );
+        tracing::info!(%key, %sender, "Session key revoked");
+
+        Ok(())
+    }
+
+    /// Checks that `call` may be executed with the session key `key` of `sender` at `slot`.
+    pub(crate) fn authorize_session_call(
+        &self,
+        key: &str,
+        call: &CallMessage<S>,
+        slot: u64,
+        sender: &S::Address,
+        state: &mut impl TxState<S>,
+    ) -> Result<()> {
+        tracing::info!(%key, call = call.name(), %sender, "Execute session request");
+
+        let key = normalize_eth_address(key)?;
+        let session_key = match self.session_keys.get(&key, state)? {
+            Some(session_key) if session_key.owner == *sender => session_key,
+            _ => bail!("session key '{}' is not granted to '{}'", key, sender),
+        };
+
+        if slot >= session_key.expires_at_slot {
+            bail!(
+                "session key '{}' expired at slot {}",
+                key,
+                session_key.expires_at_slot
+            );
+        }
+        if !SessionCall::of(call).is_some_and(|call| session_key.allowed_calls.contains(&call)) {
+            bail!(
+                "call '{}' is not allowed for session key '{}'",
+                call.name(),
+                key
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Reward pools are held in the gas token.
+fn reward_coins(amount: u64) -> Coins {
+    Coins {
+        amount,
+        token_id: sov_bank::config_gas_token_id(),
+    }
+}
+
+/// Lower-case, `0x` prefixed form of an Ethereum address used as key of the reverse lookup.
+pub(crate) fn normalize_eth_address(eth_address: &str) -> Result<String> {
+    Ok(format!(
+        "0x{}",
+        hex::encode(filament_hub_eth::parse_eth_address(eth_address)?)
+    ))
 }
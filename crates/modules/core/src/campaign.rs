@@ -44,6 +44,9 @@ pub struct Campaign<S: Spec> {
     pub evictions: Vec<Eviction<S>>,
     // TODO(xla): Rework into commitments in follow-up.
     pub delegates: HashMap<String, u64>,
+    /// Slot at which the voting powers of the delegates were snapshotted.
+    #[serde(default)]
+    pub snapshot_slot: u64,
 
     #[ts(type = "string | null")]
     pub indexer: Option<S::Address>,
@@ -79,3 +82,19 @@ pub enum Phase {
     Canceled,
     Rejected,
 }
+
+impl Phase {
+    /// Every phase, in the order campaigns go through them.
+    pub const ALL: [Phase; 10] = [
+        Phase::Draft,
+        Phase::Init,
+        Phase::Criteria,
+        Phase::Publish,
+        Phase::Indexing,
+        Phase::Distribution,
+        Phase::Settle,
+        Phase::Settled,
+        Phase::Canceled,
+        Phase::Rejected,
+    ];
+}
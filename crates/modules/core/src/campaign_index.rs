@@ -0,0 +1,397 @@
+//! Secondary indexes of campaigns kept in module state.
+//!
+//! Every campaign is recorded by campaigner, phase, delegate and indexer, so listing campaigns
+//! matching a filter only reads the ids of the matching campaigns instead of scanning all of
+//! them. Each index keeps its ids in ascending order.
+//!
+//! `campaigns_index` holds all campaigns in the order they were created, which is their id
+//! order, and `campaigns_by_slot` holds them ordered by snapshot slot, ties broken by id. Pages of
+//! either are read by position without loading the other campaigns.
+use std::collections::HashSet;
+
+use sov_modules_api::{Spec, StateAccessor, StateReader};
+use sov_state::User;
+
+use crate::{
+    campaign::{Campaign, Phase},
+    Core,
+};
+
+/// Number of campaigns returned per page unless requested otherwise.
+pub const DEFAULT_CAMPAIGNS_LIMIT: usize = 50;
+
+/// Maximum number of campaigns returned per page.
+pub const MAX_CAMPAIGNS_LIMIT: usize = 100;
+
+/// Filters of a campaign listing, all of which have to match.
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(
+    bound = "S::Address: serde::Serialize + serde::de::DeserializeOwned",
+    default
+)]
+pub struct CampaignFilter<S: Spec> {
+    pub phase: Option<Phase>,
+    pub campaigner: Option<S::Address>,
+    /// Address elected as a delegate of the campaign.
+    pub delegate: Option<S::Address>,
+    pub indexer: Option<S::Address>,
+}
+
+/// Key campaigns are listed by.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CampaignSort {
+    #[default]
+    Id,
+    /// Slot the delegate powers of the campaign are snapshotted at, which is the slot drafts
+    /// are drafted at. Ties are broken by id.
+    Slot,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Page of campaigns and the cursor to pass as `after` for the next one, if any.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(bound = "S::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct CampaignPage<S: Spec> {
+    pub campaigns: Vec<Campaign<S>>,
+    pub next: Option<u64>,
+}
+
+impl<S: Spec> Core<S> {
+    /// Adds a new campaign to all campaign indexes.
+    pub(crate) fn index_new_campaign<Accessor: StateAccessor>(
+        &self,
+        campaign: &Campaign<S>,
+        state: &mut Accessor,
+    ) -> Result<(), <Accessor as StateReader<User>>::Error> {
+        self.campaigns_index.push(&campaign.id, state)?;
+        self.index_campaign_slot(campaign, state)?;
+
+        let mut ids = self
+            .campaigns_by_addr
+            .get(&campaign.campaigner, state)?
+            .unwrap_or_default();
+        insert_id(&mut ids, campaign.id);
+        self.campaigns_by_addr
+            .set(&campaign.campaigner, &ids, state)?;
+
+        let mut ids = self
+            .campaigns_by_phase
+            .get(&campaign.phase, state)?
+            .unwrap_or_default();
+        insert_id(&mut ids, campaign.id);
+        self.campaigns_by_phase.set(&campaign.phase, &ids, state)?;
+
+        for delegate in campaign.delegates.keys() {
+            let Ok(delegate) = delegate.parse::<S::Address>() else {
+                continue;
+            };
+            let mut ids = self
+                .campaigns_by_delegate
+                .get(&delegate, state)?
+                .unwrap_or_default();
+            insert_id(&mut ids, campaign.id);
+            self.campaigns_by_delegate.set(&delegate, &ids, state)?;
+        }
+
+        if let Some(indexer) = &campaign.indexer {
+            self.index_campaign_indexer(campaign.id, indexer, state)?;
+        }
+
+        Ok(())
+    }
+
+    /// Inserts the campaign into `campaigns_by_slot`. Drafts are snapshotted at the current slot
+    /// and are appended, only genesis campaigns snapshotted later than a draft shift entries.
+    fn index_campaign_slot<Accessor: StateAccessor>(
+        &self,
+        campaign: &Campaign<S>,
+        state: &mut Accessor,
+    ) -> Result<(), <Accessor as StateReader<User>>::Error> {
+        let key = (campaign.snapshot_slot, campaign.id);
+        let len = self.campaigns_by_slot.len(state)?;
+
+        let (mut low, mut high) = (0, len);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.campaigns_by_slot.get(mid, state)?.unwrap_or_default() <= key {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        if low == len {
+            return self.campaigns_by_slot.push(&key, state);
+        }
+        let mut carried = key;
+        for position in low..len {
+            let entry = self
+                .campaigns_by_slot
+                .get(position, state)?
+                .unwrap_or_default();
+            self.campaigns_by_slot.set(position, &carried, state)?;
+            carried = entry;
+        }
+        self.campaigns_by_slot.push(&carried, state)
+    }
+
+    /// Records `indexer` as the indexer of the campaign `campaign_id`.
+    pub(crate) fn index_campaign_indexer<Accessor: StateAccessor>(
+        &self,
+        campaign_id: u64,
+        indexer: &S::Address,
+        state: &mut Accessor,
+    ) -> Result<(), <Accessor as StateReader<User>>::Error> {
+        let mut ids = self
+            .campaigns_by_indexer
+            .get(indexer, state)?
+            .unwrap_or_default();
+        insert_id(&mut ids, campaign_id);
+        self.campaigns_by_indexer.set(indexer, &ids, state)
+    }
+
+    /// Moves the campaign to `phase`, keeping the phase index and counts in sync.
+    pub(crate) fn set_campaign_phase<Accessor: StateAccessor>(
+        &self,
+        campaign: &mut Campaign<S>,
+        phase: Phase,
+        state: &mut Accessor,
+    ) -> Result<(), <Accessor as StateReader<User>>::Error> {
+        self.count_campaign(Some(&campaign.phase), &phase, state)?;
+
+        let mut ids = self
+            .campaigns_by_phase
+            .get(&campaign.phase, state)?
+            .unwrap_or_default();
+        if let Some(position) = ids.iter().position(|id| *id == campaign.id) {
+            ids.remove(position);
+            self.campaigns_by_phase.set(&campaign.phase, &ids, state)?;
+
+            let mut ids = self
+                .campaigns_by_phase
+                .get(&phase, state)?
+                .unwrap_or_default();
+            insert_id(&mut ids, campaign.id);
+            self.campaigns_by_phase.set(&phase, &ids, state)?;
+        }
+
+        campaign.phase = phase;
+
+        Ok(())
+    }
+
+    /// Returns up to `limit` campaigns matching `filter` in the requested order, starting after
+    /// the campaign `after` when paginating.
+    pub(crate) fn indexed_campaigns<Accessor: StateAccessor>(
+        &self,
+        filter: &CampaignFilter<S>,
+        sort: CampaignSort,
+        order: SortOrder,
+        after: Option<u64>,
+        limit: usize,
+        state: &mut Accessor,
+    ) -> Result<CampaignPage<S>, <Accessor as StateReader<User>>::Error> {
+        let mut candidates: Option<Vec<u64>> = None;
+        let mut narrow = |ids: Vec<u64>| {
+            candidates = Some(match candidates.take() {
+                None => ids,
+                Some(candidates) => {
+                    let ids = ids.into_iter().collect::<HashSet<_>>();
+                    candidates
+                        .into_iter()
+                        .filter(|id| ids.contains(id))
+                        .collect()
+                },
+            });
+        };
+        if let Some(phase) = &filter.phase {
+            narrow(
+                self.campaigns_by_phase
+                    .get(phase, state)?
+                    .unwrap_or_default(),
+            );
+        }
+        if let Some(campaigner) = &filter.campaigner {
+            narrow(
+                self.campaigns_by_addr
+                    .get(campaigner, state)?
+                    .unwrap_or_default(),
+            );
+        }
+        if let Some(delegate) = &filter.delegate {
+            narrow(
+                self.campaigns_by_delegate
+                    .get(delegate, state)?
+                    .unwrap_or_default(),
+            );
+        }
+        if let Some(indexer) = &filter.indexer {
+            narrow(
+                self.campaigns_by_indexer
+                    .get(indexer, state)?
+                    .unwrap_or_default(),
+            );
+        }
+
+        let (ids, next) = match (sort, candidates) {
+            (CampaignSort::Id, Some(ids)) => page_ids(
+                ids.len(),
+                |position| -> Result<(u64, u64), <Accessor as StateReader<User>>::Error> {
+                    Ok((ids[position], ids[position]))
+                },
+                order,
+                after,
+                limit,
+            )?,
+            (CampaignSort::Id, None) => {
+                let len = self.campaigns_index.len(state)?;
+                page_ids(
+                    len,
+                    |position| -> Result<(u64, u64), <Accessor as StateReader<User>>::Error> {
+                        let id = self
+                            .campaigns_index
+                            .get(position, state)?
+                            .unwrap_or_default();
+                        Ok((id, id))
+                    },
+                    order,
+                    after,
+                    limit,
+                )?
+            },
+            (CampaignSort::Slot, candidates) => {
+                // The cursor is a campaign id, which is paged from by its position in slot order.
+                let after = match after {
+                    None => None,
+                    Some(after) => match self.campaigns.get(&after, state)? {
+                        Some(campaign) => Some((campaign.snapshot_slot, after)),
+                        None => {
+                            return Ok(CampaignPage {
+                                campaigns: vec![],
+                                next: None,
+                            })
+                        },
+                    },
+                };
+
+                match candidates {
+                    Some(ids) => {
+                        let mut keys = Vec::with_capacity(ids.len());
+                        for id in ids {
+                            if let Some(campaign) = self.campaigns.get(&id, state)? {
+                                keys.push((campaign.snapshot_slot, id));
+                            }
+                        }
+                        keys.sort_unstable();
+                        page_ids(
+                            keys.len(),
+                            |position| -> Result<_, <Accessor as StateReader<User>>::Error> {
+                                Ok((keys[position], keys[position].1))
+                            },
+                            order,
+                            after,
+                            limit,
+                        )?
+                    },
+                    None => {
+                        let len = self.campaigns_by_slot.len(state)?;
+                        page_ids(
+                            len,
+                            |position| -> Result<_, <Accessor as StateReader<User>>::Error> {
+                                let key = self
+                                    .campaigns_by_slot
+                                    .get(position, state)?
+                                    .unwrap_or_default();
+                                Ok((key, key.1))
+                            },
+                            order,
+                            after,
+                            limit,
+                        )?
+                    },
+                }
+            },
+        };
+
+        let mut campaigns = Vec::with_capacity(ids.len());
+        for id in &ids {
+            if let Some(campaign) = self.campaigns.get(id, state)? {
+                campaigns.push(campaign);
+            }
+        }
+
+        Ok(CampaignPage { campaigns, next })
+    }
+}
+
+/// Inserts `id` into the ascending `ids`, unless it is already present.
+fn insert_id(ids: &mut Vec<u64>, id: u64) {
+    if let Err(position) = ids.binary_search(&id) {
+        ids.insert(position, id);
+    }
+}
+
+/// Pages through `len` entries ascending by key, reading only the entries the binary search for
+/// the key `after` and the page itself visit. Returns the campaign ids of the page and the cursor
+/// of the next one.
+fn page_ids<K: Ord, E>(
+    len: usize,
+    mut entry_at: impl FnMut(usize) -> Result<(K, u64), E>,
+    order: SortOrder,
+    after: Option<K>,
+    limit: usize,
+) -> Result<(Vec<u64>, Option<u64>), E> {
+    let limit = limit.clamp(1, MAX_CAMPAIGNS_LIMIT);
+
+    // Number of entries for which `below` holds, which is a prefix of the entries.
+    let mut partition_point = |below: &dyn Fn(&K) -> bool| -> Result<usize, E> {
+        let (mut low, mut high) = (0, len);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if below(&entry_at(mid)?.0) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(low)
+    };
+
+    let positions = match order {
+        SortOrder::Asc => {
+            let start = match &after {
+                Some(after) => partition_point(&|key| key <= after)?,
+                None => 0,
+            };
+            (start..len.min(start + limit)).collect::<Vec<_>>()
+        },
+        SortOrder::Desc => {
+            let end = match &after {
+                Some(after) => partition_point(&|key| key < after)?,
+                None => len,
+            };
+            (end.saturating_sub(limit)..end).rev().collect::<Vec<_>>()
+        },
+    };
+    let more = match (order, positions.last()) {
+        (SortOrder::Asc, Some(last)) => last + 1 < len,
+        (SortOrder::Desc, Some(last)) => *last > 0,
+        (_, None) => false,
+    };
+
+    let ids = positions
+        .into_iter()
+        .map(|position| entry_at(position).map(|(_, id)| id))
+        .collect::<Result<Vec<_>, _>>()?;
+    let next = if more { ids.last().copied() } else { None };
+
+    Ok((ids, next))
+}
@@ -2,7 +2,10 @@ use sov_mock_zkvm::MockZkVerifier;
 use sov_modules_api::{default_spec::DefaultSpec, execution_mode::Zk, Spec};
 
 use crate::{
+    attestation::Attestation,
     delegate::Eviction,
+    rewards::DelegateReward,
+    session::SessionCall,
     voting::{CriteriaVote, DistributionVote},
     Power,
     Relayer,
@@ -109,4 +112,285 @@ pub enum Event<S: Spec> {
         #[ts(type = "string")]
         relayer: Relayer<S>,
     },
+
+    // Rewards
+    RewardPoolFunded {
+        campaign_id: u64,
+        amount: u64,
+        total: u64,
+    },
+    ObjectivePosted {
+        campaign_id: u64,
+        #[ts(type = "string")]
+        relayer: Relayer<S>,
+    },
+    DelegateRewardsDistributed {
+        campaign_id: u64,
+        rewards: Vec<DelegateReward>,
+        /// Part of the pool left over by rounding, returned to the funder.
+        refund: u64,
+    },
+
+    // Voting
+    VotingPowersUpdated {
+        count: u64,
+        eth_block: u64,
+        #[ts(type = "string")]
+        relayer: Relayer<S>,
+    },
+
+    // Attestation
+    AttestationThresholdUpdated {
+        threshold: u64,
+        #[ts(type = "string")]
+        sender: S::Address,
+    },
+    VotingPowerAttested {
+        #[ts(type = "string")]
+        addr: S::Address,
+        power: Power,
+        eth_block: u64,
+        #[ts(type = "string")]
+        relayer: Relayer<S>,
+    },
+    AttestationConflictDetected {
+        #[ts(type = "string")]
+        addr: S::Address,
+        eth_block: u64,
+        attestations: Vec<Attestation<S>>,
+    },
+    // Light client
+    EthHeadersSubmitted {
+        from: u64,
+        to: u64,
+        #[ts(type = "string")]
+        relayer: Relayer<S>,
+    },
+    EthHeaderAccepted {
+        number: u64,
+        /// Whether the header replaced a stored one, dropping its descendants.
+        replaced: bool,
+    },
+    VotingPowerProven {
+        #[ts(type = "string")]
+        addr: S::Address,
+        power: Power,
+        eth_block: u64,
+        #[ts(type = "string")]
+        sender: S::Address,
+    },
+    // Accounts
+    EthAddressLinked {
+        #[ts(type = "string")]
+        addr: S::Address,
+        eth_address: String,
+    },
+    EthAddressUnlinked {
+        #[ts(type = "string")]
+        addr: S::Address,
+        eth_address: String,
+    },
+    // Multisig
+    MultisigUpdated {
+        address: String,
+        owners: Vec<String>,
+        threshold: u64,
+        eth_block: u64,
+        #[ts(type = "string")]
+        relayer: Relayer<S>,
+    },
+    // Session
+    SessionKeyGranted {
+        #[ts(type = "string")]
+        addr: S::Address,
+        key: String,
+        allowed_calls: Vec<SessionCall>,
+        expires_at_slot: u64,
+    },
+    SessionKeyRevoked {
+        #[ts(type = "string")]
+        addr: S::Address,
+        key: String,
+    },
+    // Campaign
+    DistributionConfirmed {
+        campaign_id: u64,
+    },
+    // Rewards
+    ObjectiveAttested {
+        campaign_id: u64,
+        #[ts(type = "string")]
+        relayer: Relayer<S>,
+        /// Registered relayers that posted the same measurement so far.
+        attestations: u64,
+    },
+}
+
+impl<S: Spec> Event<S> {
+    /// Name of the event variant, as it is serialized, e.g. `campaign_drafted`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Event::CampaignDrafted { .. } => "campaign_drafted",
+            Event::CampaignInitialized { .. } => "campaign_initialized",
+            Event::CampaignIndexing { .. } => "campaign_indexing",
+            Event::CriteriaProposed { .. } => "criteria_proposed",
+            Event::CriteriaVoted { .. } => "criteria_voted",
+            Event::CriteriaConfirmed { .. } => "criteria_confirmed",
+            Event::SegmentPosted { .. } => "segment_posted",
+            Event::DistributionVoted { .. } => "distribution_voted",
+            Event::DistributionConfirmed { .. } => "distribution_confirmed",
+            Event::IndexerRegistered { .. } => "indexer_registered",
+            Event::IndexerUnregistered { .. } => "indexer_unregistered",
+            Event::RelayerRegistered { .. } => "relayer_registered",
+            Event::RelayerUnregistered { .. } => "relayer_unregistered",
+            Event::VotingPowerUpdated { .. } => "voting_power_updated",
+            Event::RewardPoolFunded { .. } => "reward_pool_funded",
+            Event::ObjectivePosted { .. } => "objective_posted",
+            Event::ObjectiveAttested { .. } => "objective_attested",
+            Event::DelegateRewardsDistributed { .. } => "delegate_rewards_distributed",
+            Event::VotingPowersUpdated { .. } => "voting_powers_updated",
+            Event::AttestationThresholdUpdated { .. } => "attestation_threshold_updated",
+            Event::VotingPowerAttested { .. } => "voting_power_attested",
+            Event::AttestationConflictDetected { .. } => "attestation_conflict_detected",
+            Event::EthHeadersSubmitted { .. } => "eth_headers_submitted",
+            Event::EthHeaderAccepted { .. } => "eth_header_accepted",
+            Event::VotingPowerProven { .. } => "voting_power_proven",
+            Event::EthAddressLinked { .. } => "eth_address_linked",
+            Event::EthAddressUnlinked { .. } => "eth_address_unlinked",
+            Event::MultisigUpdated { .. } => "multisig_updated",
+            Event::SessionKeyGranted { .. } => "session_key_granted",
+            Event::SessionKeyRevoked { .. } => "session_key_revoked",
+        }
+    }
+
+    /// Campaign the event is about, if any.
+    pub fn campaign_id(&self) -> Option<u64> {
+        match self {
+            Event::CampaignDrafted { campaign_id, .. }
+            | Event::CampaignInitialized { campaign_id }
+            | Event::CampaignIndexing { campaign_id, .. }
+            | Event::CriteriaProposed { campaign_id, .. }
+            | Event::CriteriaVoted { campaign_id, .. }
+            | Event::CriteriaConfirmed { campaign_id, .. }
+            | Event::SegmentPosted { campaign_id, .. }
+            | Event::DistributionVoted { campaign_id, .. }
+            | Event::DistributionConfirmed { campaign_id }
+            | Event::RewardPoolFunded { campaign_id, .. }
+            | Event::ObjectivePosted { campaign_id, .. }
+            | Event::ObjectiveAttested { campaign_id, .. }
+            | Event::DelegateRewardsDistributed { campaign_id, .. } => Some(*campaign_id),
+            _ => None,
+        }
+    }
+
+    /// Whether `addr` takes part in the event, as sender, relayer or subject.
+    pub fn involves(&self, addr: &S::Address) -> bool {
+        match self {
+            Event::CampaignDrafted {
+                campaigner,
+                evictions,
+                ..
+            } => campaigner == addr || evictions.contains(addr),
+            Event::CampaignIndexing { indexer, .. } | Event::SegmentPosted { indexer, .. } => {
+                indexer == addr
+            },
+            Event::CriteriaProposed { proposer, .. } => proposer == addr,
+            Event::CriteriaVoted { delegate, .. } | Event::DistributionVoted { delegate, .. } => {
+                delegate == addr
+            },
+            Event::IndexerRegistered {
+                addr: subject,
+                sender,
+                ..
+            }
+            | Event::IndexerUnregistered {
+                addr: subject,
+                sender,
+            }
+            | Event::RelayerRegistered {
+                addr: subject,
+                sender,
+            }
+            | Event::RelayerUnregistered {
+                addr: subject,
+                sender,
+            }
+            | Event::VotingPowerProven {
+                addr: subject,
+                sender,
+                ..
+            } => subject == addr || sender == addr,
+            Event::VotingPowerUpdated {
+                addr: subject,
+                relayer,
+                ..
+            }
+            | Event::VotingPowerAttested {
+                addr: subject,
+                relayer,
+                ..
+            } => subject == addr || relayer == addr,
+            Event::ObjectivePosted { relayer, .. }
+            | Event::ObjectiveAttested { relayer, .. }
+            | Event::VotingPowersUpdated { relayer, .. }
+            | Event::EthHeadersSubmitted { relayer, .. }
+            | Event::MultisigUpdated { relayer, .. } => relayer == addr,
+            Event::DelegateRewardsDistributed { rewards, .. } => {
+                let addr = addr.to_string();
+                rewards.iter().any(|reward| reward.delegate == addr)
+            },
+            Event::AttestationThresholdUpdated { sender, .. } => sender == addr,
+            Event::AttestationConflictDetected {
+                addr: subject,
+                attestations,
+                ..
+            } => {
+                subject == addr
+                    || attestations
+                        .iter()
+                        .any(|attestation| attestation.relayer == *addr)
+            },
+            Event::EthAddressLinked { addr: subject, .. }
+            | Event::EthAddressUnlinked { addr: subject, .. }
+            | Event::SessionKeyGranted { addr: subject, .. }
+            | Event::SessionKeyRevoked { addr: subject, .. } => subject == addr,
+            Event::CampaignInitialized { .. }
+            | Event::CriteriaConfirmed { .. }
+            | Event::DistributionConfirmed { .. }
+            | Event::RewardPoolFunded { .. }
+            | Event::EthHeaderAccepted { .. } => false,
+        }
+    }
+}
+
+/// Filters of an event subscription, all of which have to match.
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(
+    bound = "S::Address: serde::Serialize + serde::de::DeserializeOwned",
+    default
+)]
+pub struct EventFilter<S: Spec> {
+    pub campaign_id: Option<u64>,
+    /// Event kinds to match, see [`Event::kind`]. Matches every kind when empty.
+    pub kinds: Vec<String>,
+    pub addr: Option<S::Address>,
+}
+
+impl<S: Spec> EventFilter<S> {
+    pub fn matches(&self, event: &Event<S>) -> bool {
+        if self
+            .campaign_id
+            .is_some_and(|campaign_id| event.campaign_id() != Some(campaign_id))
+        {
+            return false;
+        }
+        if !self.kinds.is_empty() && !self.kinds.iter().any(|kind| kind == event.kind()) {
+            return false;
+        }
+        if let Some(addr) = &self.addr {
+            return event.involves(addr);
+        }
+
+        true
+    }
 }
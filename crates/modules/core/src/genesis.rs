@@ -3,7 +3,17 @@ use std::collections::HashMap;
 use anyhow::Result;
 use sov_modules_api::{GenesisState, Spec};
 
-use crate::{delegate::Delegate, Campaign, Core, Indexer, Power, Relayer};
+use crate::{
+    call::normalize_eth_address,
+    delegate::Delegate,
+    light_client::LightClientConfig,
+    voting::PowerCheckpoint,
+    Campaign,
+    Core,
+    Indexer,
+    Power,
+    Relayer,
+};
 
 #[cfg_attr(
     feature = "native",
@@ -21,6 +31,9 @@ pub struct CoreConfig<S: Spec> {
     pub indexers: Vec<Indexer<S>>,
     pub powers: HashMap<S::Address, Power>,
     pub relayers: Vec<Relayer<S>>,
+
+    #[serde(default)]
+    pub light_client: Option<LightClientConfig>,
 }
 
 impl<S: Spec> Core<S> {
@@ -35,7 +48,13 @@ impl<S: Spec> Core<S> {
 
         let mut id = 0;
         for campaign in config.campaigns.iter() {
-            self.campaigns.set(&id, campaign, state)?;
+            let campaign = Campaign {
+                id,
+                ..campaign.clone()
+            };
+            self.campaigns.set(&id, &campaign, state)?;
+            self.index_new_campaign(&campaign, state)?;
+            self.count_campaign(None, &campaign.phase, state)?;
             id += 1;
         }
         self.next_campaign_id.set(&id, state)?;
@@ -46,6 +65,8 @@ impl<S: Spec> Core<S> {
 
         for (addr, eth_addr) in &config.eth_addresses {
             self.eth_addresses.set(addr, eth_addr, state)?;
+            self.hub_addresses
+                .set(&normalize_eth_address(eth_addr)?, addr, state)?;
         }
 
         for Indexer { addr, alias } in config.indexers.iter() {
@@ -57,16 +78,39 @@ impl<S: Spec> Core<S> {
             self.relayers.push(relayer, state)?;
         }
 
-        let mut index = config
-            .powers
-            .iter()
-            .map(|(addr, power)| (addr.clone(), *power))
-            .collect::<Vec<_>>();
-        index.sort_unstable_by(|a, b| b.1.cmp(&a.1));
-        for (addr, power) in &index {
+        for (addr, power) in &config.powers {
             self.powers.set(addr, power, state)?;
+            self.push_power_checkpoint(
+                addr,
+                PowerCheckpoint {
+                    slot: 0,
+                    power: *power,
+                },
+                state,
+            )?;
+            self.index_power(addr, *power, state)?;
+        }
+
+        let total = config
+            .powers
+            .values()
+            .fold(0 as Power, |acc, power| acc.saturating_add(*power));
+        self.total_voting_power.set(&total, state)?;
+        self.total_power_checkpoints.push(
+            &PowerCheckpoint {
+                slot: 0,
+                power: total,
+            },
+            state,
+        )?;
+
+        if let Some(light_client) = &config.light_client {
+            let checkpoint = filament_hub_eth::proof::Header::decode_hex(&light_client.checkpoint)?;
+            self.light_client.set(light_client, state)?;
+            self.eth_head.set(&checkpoint.number, state)?;
+            self.eth_headers
+                .set(&checkpoint.number, &checkpoint.into(), state)?;
         }
-        self.powers_index.set_all(index, state)?;
 
         tracing::info!("completed core genesis");
 
@@ -21,11 +21,16 @@ use sov_modules_api::{
 mod account;
 pub use account::*;
 
+pub mod attestation;
+use attestation::{Attestation, AttestationConflict};
+
 mod call;
 pub use call::*;
 
 pub mod campaign;
-use campaign::Campaign;
+use campaign::{Campaign, Phase};
+
+pub mod campaign_index;
 
 pub mod criteria;
 use criteria::CriteriaProposal;
@@ -35,7 +40,7 @@ pub mod crypto;
 pub mod delegate;
 
 mod event;
-pub use event::Event;
+pub use event::{Event, EventFilter};
 
 mod genesis;
 pub use genesis::CoreConfig;
@@ -43,9 +48,18 @@ pub use genesis::CoreConfig;
 mod indexer;
 pub use indexer::{Alias, Indexer};
 
+pub mod light_client;
+use light_client::{EthHeader, HeaderSubmission, LightClientConfig};
+
+pub mod multisig;
+use multisig::{Multisig, MultisigSubmission};
+
 pub mod playbook;
 pub use playbook::{Budget, Playbook};
 
+pub mod power_index;
+use power_index::PowerIndexNode;
+
 #[cfg(feature = "native")]
 pub mod query;
 #[cfg(feature = "native")]
@@ -54,12 +68,21 @@ pub use query::*;
 pub mod relayer;
 pub use relayer::Relayer;
 
+pub mod rewards;
+use rewards::{DelegateReward, ObjectiveMeasurement, ObjectiveSubmission, RewardPool};
+
 pub mod segment;
 pub use segment::Segment;
 
+pub mod session;
+use session::SessionKey;
+
+pub mod stats;
+use stats::Stats;
+
 pub mod voting;
 pub use voting::Power;
-use voting::{CriteriaVote, DistributionVote};
+use voting::{CriteriaVote, DistributionVote, PowerCheckpoint, Tally};
 
 #[derive(Clone, ModuleInfo, ModuleRestApi)]
 pub struct Core<S: Spec> {
@@ -79,9 +102,26 @@ pub struct Core<S: Spec> {
     #[state]
     pub(crate) campaigns_index: StateVec<u64>,
 
+    /// Snapshot slot and id of every campaign, in ascending order.
+    #[state]
+    pub(crate) campaigns_by_slot: StateVec<(u64, u64)>,
+
     #[state]
     pub(crate) campaigns_by_addr: StateMap<S::Address, Vec<u64>>,
 
+    #[state]
+    pub(crate) campaigns_by_phase: StateMap<Phase, Vec<u64>>,
+
+    #[state]
+    pub(crate) campaigns_by_delegate: StateMap<S::Address, Vec<u64>>,
+
+    #[state]
+    pub(crate) campaigns_by_indexer: StateMap<S::Address, Vec<u64>>,
+
+    /// Number of campaigns in each phase, including the ones created at genesis.
+    #[state]
+    pub(crate) campaign_counts: StateMap<Phase, u64>,
+
     #[state]
     pub(crate) criteria_proposals: StateMap<u64, Vec<CriteriaProposal<S>>>,
 
@@ -117,12 +157,113 @@ pub struct Core<S: Spec> {
     pub(crate) powers: StateMap<S::Address, Power>,
 
     #[state]
-    pub(crate) powers_index: StateVec<(S::Address, Power)>,
+    pub(crate) power_index: StateMap<S::Address, PowerIndexNode<S>>,
+
+    #[state]
+    pub(crate) power_index_head: StateValue<Vec<Option<S::Address>>>,
+
+    #[state]
+    pub(crate) power_index_len: StateValue<u64>,
+
+    /// Voting power history of each address, keyed by address and position in the history.
+    #[state]
+    pub(crate) power_checkpoints: StateMap<(S::Address, u64), PowerCheckpoint>,
+
+    #[state]
+    pub(crate) power_checkpoints_len: StateMap<S::Address, u64>,
+
+    #[state]
+    pub(crate) total_power_checkpoints: StateVec<PowerCheckpoint>,
+
+    #[state]
+    pub(crate) criteria_tallies: StateMap<u64, Tally>,
+
+    #[state]
+    pub(crate) distribution_tallies: StateMap<u64, Tally>,
+
+    // Attestation
+    #[state]
+    pub(crate) attestation_threshold: StateValue<u64>,
+
+    #[state]
+    pub(crate) attestations: StateMap<(S::Address, u64), Vec<Attestation<S>>>,
+
+    /// Ethereum blocks with pending attestations for each address.
+    #[state]
+    pub(crate) pending_attestation_blocks: StateMap<S::Address, Vec<u64>>,
+
+    #[state]
+    pub(crate) attestation_conflicts: StateMap<S::Address, Vec<AttestationConflict<S>>>,
+
+    #[state]
+    pub(crate) attested_eth_blocks: StateMap<S::Address, u64>,
+
+    // Light client
+    #[state]
+    pub(crate) light_client: StateValue<LightClientConfig>,
+
+    #[state]
+    pub(crate) eth_headers: StateMap<u64, EthHeader>,
+
+    #[state]
+    pub(crate) eth_head: StateValue<u64>,
+
+    /// Relayers that submitted each stored header.
+    #[state]
+    pub(crate) eth_header_relayers: StateMap<u64, Vec<Relayer<S>>>,
+
+    /// Headers below the attestation threshold, by number.
+    #[state]
+    pub(crate) pending_eth_headers: StateMap<u64, Vec<HeaderSubmission<S>>>,
+
+    /// Ethereum block of the last batch of voting power updates applied.
+    #[state]
+    pub(crate) last_eth_block: StateValue<u64>,
+
+    // Rewards
+    #[state]
+    pub(crate) reward_pools: StateMap<u64, RewardPool<S>>,
+
+    #[state]
+    pub(crate) objectives: StateMap<u64, ObjectiveMeasurement>,
+
+    /// Measurements below the attestation threshold, by campaign.
+    #[state]
+    pub(crate) pending_objectives: StateMap<u64, Vec<ObjectiveSubmission<S>>>,
+
+    #[state]
+    pub(crate) delegate_rewards: StateMap<u64, Vec<DelegateReward>>,
 
     // Accounts
     #[state]
     pub(crate) eth_addresses: StateMap<S::Address, String>,
 
+    #[state]
+    pub(crate) hub_addresses: StateMap<String, S::Address>,
+
+    // Multisig
+    #[state]
+    pub(crate) multisigs: StateMap<String, Multisig>,
+
+    /// Owner sets below the attestation threshold, by wallet address.
+    #[state]
+    pub(crate) pending_multisigs: StateMap<String, Vec<MultisigSubmission<S>>>,
+
+    // Session
+    #[state]
+    pub(crate) session_keys: StateMap<String, SessionKey<S>>,
+
+    /// Latest visible slot the module executed a call in, read by the session authenticator.
+    #[state]
+    pub(crate) last_slot: StateValue<u64>,
+
+    // Stats
+    #[state]
+    pub(crate) stats: StateValue<Stats>,
+
+    #[module]
+    pub(crate) bank: sov_bank::Bank<S>,
+
     #[module]
     pub(crate) nonces: sov_nonces::Nonces<S>,
 }
@@ -147,6 +288,10 @@ impl<S: Spec> Module for Core<S> {
         context: &Context<Self::Spec>,
         state: &mut impl TxState<S>,
     ) -> Result<CallResponse, Error> {
+        self.last_slot
+            .set(&context.visible_slot_number(), state)
+            .map_err(anyhow::Error::from)?;
+
         match msg {
             // Campaign
             call::CallMessage::Draft {
@@ -160,6 +305,7 @@ impl<S: Spec> Module for Core<S> {
                     description,
                     criteria,
                     evictions,
+                    context.visible_slot_number(),
                     context.sender(),
                     state,
                 )?;
@@ -206,6 +352,10 @@ impl<S: Spec> Module for Core<S> {
                 self.vote_distribution(campaign_id, vote, context.sender(), state)?;
                 Ok(CallResponse::default())
             },
+            call::CallMessage::ConfirmDistribution { campaign_id } => {
+                self.confirm_distribution(campaign_id, context.sender(), state)?;
+                Ok(CallResponse::default())
+            },
 
             // Indexer
             call::CallMessage::RegisterIndexer { address, alias } => {
@@ -229,9 +379,167 @@ impl<S: Spec> Module for Core<S> {
 
             // Voting
             call::CallMessage::UpdateVotingPower { address, power } => {
-                self.update_voting_power(address, power, context.sender().clone(), state)?;
+                self.update_voting_power(
+                    address,
+                    power,
+                    None,
+                    context.visible_slot_number(),
+                    context.sender().clone(),
+                    state,
+                )?;
                 Ok(CallResponse::default())
             },
+            call::CallMessage::UpdateVotingPowerAt {
+                address,
+                power,
+                eth_block,
+            } => {
+                self.update_voting_power(
+                    address,
+                    power,
+                    Some(eth_block),
+                    context.visible_slot_number(),
+                    context.sender().clone(),
+                    state,
+                )?;
+                Ok(CallResponse::default())
+            },
+            call::CallMessage::UpdateVotingPowers { updates, eth_block } => {
+                self.update_voting_powers(
+                    updates,
+                    eth_block,
+                    context.visible_slot_number(),
+                    context.sender().clone(),
+                    state,
+                )?;
+                Ok(CallResponse::default())
+            },
+
+            // Rewards
+            call::CallMessage::FundRewardPool {
+                campaign_id,
+                amount,
+            } => {
+                self.fund_reward_pool(campaign_id, amount, context.sender(), state)?;
+                Ok(CallResponse::default())
+            },
+            call::CallMessage::PostObjective {
+                campaign_id,
+                measurement,
+            } => {
+                self.post_objective(campaign_id, measurement, context.sender(), state)?;
+                Ok(CallResponse::default())
+            },
+
+            // Attestation
+            call::CallMessage::SetAttestationThreshold { threshold } => {
+                self.set_attestation_threshold(threshold, context.sender().clone(), state)?;
+                Ok(CallResponse::default())
+            },
+            call::CallMessage::AttestVotingPower {
+                address,
+                power,
+                eth_block,
+            } => {
+                self.attest_voting_power(
+                    address,
+                    power,
+                    eth_block,
+                    context.visible_slot_number(),
+                    context.sender().clone(),
+                    state,
+                )?;
+                Ok(CallResponse::default())
+            },
+
+            // Light client
+            call::CallMessage::SubmitEthHeaders { headers } => {
+                self.submit_eth_headers(headers, context.sender().clone(), state)?;
+                Ok(CallResponse::default())
+            },
+            call::CallMessage::ProveVotingPower {
+                eth_address,
+                eth_block,
+                account_proof,
+                storage_proof,
+            } => {
+                self.prove_voting_power(
+                    eth_address,
+                    eth_block,
+                    account_proof,
+                    storage_proof,
+                    context.visible_slot_number(),
+                    context.sender().clone(),
+                    state,
+                )?;
+                Ok(CallResponse::default())
+            },
+
+            // Accounts
+            call::CallMessage::LinkEthAddress {
+                eth_address,
+                signature,
+            } => {
+                self.link_eth_address(eth_address, signature, context.sender().clone(), state)?;
+                Ok(CallResponse::default())
+            },
+            call::CallMessage::UnlinkEthAddress {} => {
+                self.unlink_eth_address(context.sender().clone(), state)?;
+                Ok(CallResponse::default())
+            },
+
+            // Multisig
+            call::CallMessage::UpdateMultisig {
+                address,
+                owners,
+                threshold,
+                eth_block,
+                account_proof,
+            } => {
+                self.update_multisig(
+                    address,
+                    owners,
+                    threshold,
+                    eth_block,
+                    account_proof,
+                    context.sender().clone(),
+                    state,
+                )?;
+                Ok(CallResponse::default())
+            },
+
+            // Session
+            call::CallMessage::GrantSessionKey {
+                key,
+                allowed_calls,
+                expires_at_slot,
+                signature,
+            } => {
+                self.grant_session_key(
+                    key,
+                    allowed_calls,
+                    expires_at_slot,
+                    signature,
+                    context.visible_slot_number(),
+                    context.sender().clone(),
+                    state,
+                )?;
+                Ok(CallResponse::default())
+            },
+            call::CallMessage::RevokeSessionKey { key } => {
+                self.revoke_session_key(key, context.sender().clone(), state)?;
+                Ok(CallResponse::default())
+            },
+            call::CallMessage::ExecuteSession { key, call } => {
+                self.authorize_session_call(
+                    &key,
+                    &call,
+                    context.visible_slot_number(),
+                    context.sender(),
+                    state,
+                )?;
+                self.call(*call, context, state)
+            },
         }
     }
 }
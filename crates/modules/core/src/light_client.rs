@@ -0,0 +1,100 @@
+use sov_mock_zkvm::MockZkVerifier;
+use sov_modules_api::{default_spec::DefaultSpec, execution_mode::Zk, Spec};
+
+use crate::Relayer;
+
+/// Configuration of the Ethereum light client used to verify voting power proofs.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(rename = "LightClientConfig")
+)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    ts_rs::TS,
+)]
+#[serde(rename_all = "snake_case")]
+#[ts(export_to = "../../../../bindings/LightClientConfig.ts")]
+pub struct LightClientConfig {
+    /// Address of the `VotingVault` contract on Ethereum.
+    pub vault_address: String,
+    /// Storage slot of the balances mapping of the vault.
+    pub balances_slot: u64,
+    /// Decimals of the vault shares, balances are divided by `10^decimals` to get voting power.
+    pub decimals: u32,
+    /// Hex encoded RLP of the trusted header the header chain is built from.
+    pub checkpoint: String,
+}
+
+/// Ethereum block header accepted into the header store.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(rename = "EthHeader")
+)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    ts_rs::TS,
+)]
+#[serde(rename_all = "snake_case")]
+#[ts(export_to = "../../../../bindings/EthHeader.ts")]
+pub struct EthHeader {
+    pub number: u64,
+    #[ts(type = "Array<number>")]
+    pub hash: [u8; 32],
+    #[ts(type = "Array<number>")]
+    pub parent_hash: [u8; 32],
+    #[ts(type = "Array<number>")]
+    pub state_root: [u8; 32],
+}
+
+impl From<filament_hub_eth::proof::Header> for EthHeader {
+    fn from(header: filament_hub_eth::proof::Header) -> Self {
+        Self {
+            number: header.number,
+            hash: header.hash,
+            parent_hash: header.parent_hash,
+            state_root: header.state_root,
+        }
+    }
+}
+
+/// Header submitted by relayers that has not reached the attestation threshold yet.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(bound = "S: ::sov_modules_api::Spec", rename = "HeaderSubmission")
+)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    ts_rs::TS,
+)]
+#[serde(bound = "S::Address: serde::Serialize + serde::de::DeserializeOwned")]
+#[ts(export, concrete(S = DefaultSpec<MockZkVerifier, MockZkVerifier, Zk>))]
+#[ts(export_to = "../../../../bindings/HeaderSubmission.ts")]
+pub struct HeaderSubmission<S: Spec> {
+    pub header: EthHeader,
+    #[ts(type = "Array<string>")]
+    pub relayers: Vec<Relayer<S>>,
+}
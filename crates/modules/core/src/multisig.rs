@@ -0,0 +1,87 @@
+use sov_mock_zkvm::MockZkVerifier;
+use sov_modules_api::{default_spec::DefaultSpec, execution_mode::Zk, Spec, StateReader};
+use sov_state::User;
+
+use crate::{Core, Relayer};
+
+/// Owner set of a smart contract wallet on Ethereum, mirrored by relayers.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(rename = "Multisig")
+)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    ts_rs::TS,
+)]
+#[serde(rename_all = "snake_case")]
+#[ts(export_to = "../../../../bindings/Multisig.ts")]
+pub struct Multisig {
+    /// Lower-case, `0x` prefixed Ethereum addresses of the owners.
+    pub owners: Vec<String>,
+    /// Number of owners required to sign a transaction.
+    pub threshold: u64,
+    /// Ethereum block the owner set was read at.
+    pub eth_block: u64,
+}
+
+/// Owner set submitted by relayers that has not reached the attestation threshold yet.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(bound = "S: ::sov_modules_api::Spec", rename = "MultisigSubmission")
+)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    ts_rs::TS,
+)]
+#[serde(bound = "S::Address: serde::Serialize + serde::de::DeserializeOwned")]
+#[ts(export, concrete(S = DefaultSpec<MockZkVerifier, MockZkVerifier, Zk>))]
+#[ts(export_to = "../../../../bindings/MultisigSubmission.ts")]
+pub struct MultisigSubmission<S: Spec> {
+    pub multisig: Multisig,
+    #[ts(type = "Array<string>")]
+    pub relayers: Vec<Relayer<S>>,
+}
+
+impl<S: Spec> Core<S> {
+    /// Owners of the wallet contract at `wallet`, used to authenticate its transactions.
+    pub fn multisig_owners<Reader: StateReader<User>>(
+        &self,
+        wallet: &[u8; 20],
+        state: &mut Reader,
+    ) -> anyhow::Result<Option<filament_hub_eth::multisig::Owners>> {
+        let Some(multisig) = self
+            .multisigs
+            .get(&format!("0x{}", hex::encode(wallet)), state)
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?
+        else {
+            return Ok(None);
+        };
+
+        let owners = multisig
+            .owners
+            .iter()
+            .map(|owner| filament_hub_eth::parse_eth_address(owner))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(Some(filament_hub_eth::multisig::Owners {
+            owners,
+            threshold: multisig.threshold,
+        }))
+    }
+}
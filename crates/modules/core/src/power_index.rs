@@ -0,0 +1,221 @@
+//! Ordered index of voting powers, kept as a skip list in module state.
+//!
+//! Entries are ordered by power from highest to lowest, ties broken by address bytes. Updates
+//! touch `O(log n)` entries in expectation and the top of the index can be read page by page,
+//! instead of rewriting the whole index on every change.
+use sov_mock_zkvm::MockZkVerifier;
+use sov_modules_api::{
+    default_spec::DefaultSpec,
+    execution_mode::Zk,
+    Spec,
+    StateAccessor,
+    StateReader,
+};
+use sov_state::User;
+
+use crate::{Core, Power};
+
+/// Number of levels of the skip list. With a promotion probability of 1/4 this comfortably
+/// covers millions of entries.
+pub const MAX_LEVEL: usize = 12;
+
+/// Number of voting powers returned per page unless requested otherwise.
+pub const DEFAULT_POWERS_LIMIT: usize = 50;
+
+/// Maximum number of voting powers returned per page.
+pub const MAX_POWERS_LIMIT: usize = 100;
+
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(bound = "S: ::sov_modules_api::Spec", rename = "PowerIndexNode")
+)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    ts_rs::TS,
+)]
+#[serde(bound = "S::Address: serde::Serialize + serde::de::DeserializeOwned")]
+#[ts(export, concrete(S = DefaultSpec<MockZkVerifier, MockZkVerifier, Zk>))]
+#[ts(export_to = "../../../../bindings/PowerIndexNode.ts")]
+pub struct PowerIndexNode<S: Spec> {
+    pub power: Power,
+    /// Successor of the entry on each of its levels.
+    #[ts(type = "Array<string | null>")]
+    pub next: Vec<Option<S::Address>>,
+}
+
+/// Level of the entry for `addr`, derived from its bytes so every node builds the same list.
+pub fn level<S: Spec>(addr: &S::Address) -> usize {
+    // FNV-1a, the levels only need to be well distributed, not unpredictable.
+    let hash = addr
+        .as_ref()
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+
+    (1 + hash.trailing_zeros() as usize / 2).min(MAX_LEVEL)
+}
+
+/// Whether the entry `(a, a_power)` comes before `(b, b_power)` in the index.
+fn precedes<S: Spec>(a: &S::Address, a_power: Power, b: &S::Address, b_power: Power) -> bool {
+    a_power > b_power || (a_power == b_power && a.as_ref() < b.as_ref())
+}
+
+impl<S: Spec> Core<S> {
+    /// Moves `addr` to the position of `power` in the index, inserting it if necessary.
+    ///
+    /// Only [`CallMessage::UpdateVotingPower`](crate::CallMessage::UpdateVotingPower) and genesis
+    /// index powers, this is public for the index benchmark of the core tests.
+    pub fn index_power<Accessor: StateAccessor>(
+        &self,
+        addr: &S::Address,
+        power: Power,
+        state: &mut Accessor,
+    ) -> Result<(), <Accessor as StateReader<User>>::Error> {
+        let mut head = self.power_index_head(state)?;
+
+        if let Some(node) = self.power_index.get(addr, state)? {
+            let predecessors = self.power_index_predecessors(&head, addr, node.power, state)?;
+            for (level, next) in node.next.iter().enumerate() {
+                self.link_power_index(&mut head, &predecessors[level], level, next, state)?;
+            }
+        } else {
+            let len = self.power_index_len.get(state)?.unwrap_or_default();
+            self.power_index_len.set(&(len + 1), state)?;
+        }
+
+        let predecessors = self.power_index_predecessors(&head, addr, power, state)?;
+        let mut node = PowerIndexNode::<S> {
+            power,
+            next: vec![None; level::<S>(addr)],
+        };
+        for level in 0..node.next.len() {
+            node.next[level] = match &predecessors[level] {
+                None => head[level].clone(),
+                Some(predecessor) => self
+                    .power_index
+                    .get(predecessor, state)?
+                    .and_then(|predecessor| predecessor.next[level].clone()),
+            };
+            self.link_power_index(
+                &mut head,
+                &predecessors[level],
+                level,
+                &Some(addr.clone()),
+                state,
+            )?;
+        }
+
+        self.power_index.set(addr, &node, state)?;
+        self.power_index_head.set(&head, state)?;
+
+        Ok(())
+    }
+
+    /// Returns up to `limit` entries of the index ordered by power, starting after `after`.
+    pub(crate) fn indexed_powers<Accessor: StateAccessor>(
+        &self,
+        after: Option<&S::Address>,
+        limit: usize,
+        state: &mut Accessor,
+    ) -> Result<Vec<(S::Address, Power)>, <Accessor as StateReader<User>>::Error> {
+        let mut next = match after {
+            None => self.power_index_head(state)?[0].clone(),
+            Some(after) => match self.power_index.get(after, state)? {
+                Some(node) => node.next[0].clone(),
+                None => return Ok(vec![]),
+            },
+        };
+
+        let mut powers = Vec::new();
+        while let Some(addr) = next {
+            if powers.len() >= limit {
+                break;
+            }
+            let Some(node) = self.power_index.get(&addr, state)? else {
+                break;
+            };
+
+            next = node.next[0].clone();
+            powers.push((addr, node.power));
+        }
+
+        Ok(powers)
+    }
+
+    fn power_index_head<Accessor: StateAccessor>(
+        &self,
+        state: &mut Accessor,
+    ) -> Result<Vec<Option<S::Address>>, <Accessor as StateReader<User>>::Error> {
+        Ok(self
+            .power_index_head
+            .get(state)?
+            .unwrap_or_else(|| vec![None; MAX_LEVEL]))
+    }
+
+    /// Finds the last entry before `(addr, power)` on every level, `None` standing for the head.
+    fn power_index_predecessors<Accessor: StateAccessor>(
+        &self,
+        head: &[Option<S::Address>],
+        addr: &S::Address,
+        power: Power,
+        state: &mut Accessor,
+    ) -> Result<Vec<Option<S::Address>>, <Accessor as StateReader<User>>::Error> {
+        let mut predecessors = vec![None; MAX_LEVEL];
+        let mut current: Option<(S::Address, PowerIndexNode<S>)> = None;
+
+        for level in (0..MAX_LEVEL).rev() {
+            loop {
+                let next = match &current {
+                    None => head[level].clone(),
+                    Some((_, node)) => node.next[level].clone(),
+                };
+                let Some(next) = next else {
+                    break;
+                };
+                let Some(node) = self.power_index.get(&next, state)? else {
+                    break;
+                };
+                if !precedes::<S>(&next, node.power, addr, power) {
+                    break;
+                }
+
+                current = Some((next, node));
+            }
+
+            predecessors[level] = current.as_ref().map(|(addr, _)| addr.clone());
+        }
+
+        Ok(predecessors)
+    }
+
+    /// Points `predecessor` to `next` on `level`.
+    fn link_power_index<Accessor: StateAccessor>(
+        &self,
+        head: &mut [Option<S::Address>],
+        predecessor: &Option<S::Address>,
+        level: usize,
+        next: &Option<S::Address>,
+        state: &mut Accessor,
+    ) -> Result<(), <Accessor as StateReader<User>>::Error> {
+        match predecessor {
+            None => head[level] = next.clone(),
+            Some(predecessor) => {
+                if let Some(mut node) = self.power_index.get(predecessor, state)? {
+                    node.next[level] = next.clone();
+                    self.power_index.set(predecessor, &node, state)?;
+                }
+            },
+        }
+
+        Ok(())
+    }
+}
@@ -1,20 +1,29 @@
 use std::collections::HashMap;
 
-use jsonrpsee::core::RpcResult;
+use jsonrpsee::{
+    core::RpcResult,
+    types::{error::INVALID_PARAMS_CODE, ErrorObjectOwned},
+};
 use sov_modules_api::{
     macros::rpc_gen,
     prelude::{
-        axum::{http::Method, routing::get, Router},
+        axum::{
+            extract::{rejection::QueryRejection, Query},
+            http::Method,
+            routing::get,
+            Router,
+        },
         serde_yaml,
         utoipa::openapi::OpenApi,
         UnwrapInfallible as _,
     },
     rest::{
-        utils::{errors, ApiResult, Path},
+        utils::{errors, ApiResult, ErrorObject, Path},
         ApiState,
         HasCustomRestApi,
     },
     ApiStateAccessor,
+    CredentialId,
     CryptoSpec,
     Spec,
     StateAccessor,
@@ -25,9 +34,22 @@ use tower_http::cors::{Any, CorsLayer};
 
 use crate::{
     account::Account,
+    attestation::{Attestation, AttestationConflict},
     campaign::Phase,
+    campaign_index::{
+        CampaignFilter,
+        CampaignPage,
+        CampaignSort,
+        SortOrder,
+        DEFAULT_CAMPAIGNS_LIMIT,
+    },
     criteria::{Criteria, CriteriaProposal},
-    voting::{CriteriaVote, DistributionVote},
+    light_client::EthHeader,
+    multisig::Multisig,
+    power_index::{DEFAULT_POWERS_LIMIT, MAX_POWERS_LIMIT},
+    rewards::{self, DelegateReward, ObjectiveMeasurement, RewardPool},
+    session::SessionKey,
+    voting::{CriteriaVote, DistributionVote, PowerCheckpoint, Tally},
     Campaign,
     Core,
     Indexer,
@@ -36,12 +58,40 @@ use crate::{
     Segment,
 };
 
+/// Hub address and the eth address linked to it, if any.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+#[ts(export_to = "../../../../bindings/AddressResponse.ts")]
+pub struct AddressResponse {
+    pub addr: String,
+    pub eth_addr: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+#[ts(export_to = "../../../../bindings/DelegateResponse.ts")]
+pub struct DelegateResponse {
+    pub addr: String,
+    pub eth_addr: Option<String>,
+    pub power: u64,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+#[ts(export_to = "../../../../bindings/VoteResponse.ts")]
+pub struct VoteResponse<V> {
+    pub addr: String,
+    pub eth_addr: Option<String>,
+    pub vote: V,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, ts_rs::TS)]
+#[ts(export)]
 #[ts(export_to = "../../../../bindings/CampaignResponse.ts")]
 pub struct CampaignResponse {
     pub id: u64,
 
-    pub campaigner: String,
+    pub campaigner: AddressResponse,
     pub phase: Phase,
 
     pub title: String,
@@ -49,10 +99,216 @@ pub struct CampaignResponse {
 
     pub criteria: Criteria,
 
-    pub evictions: Vec<String>,
-    pub delegates: HashMap<String, u64>,
+    pub evictions: Vec<AddressResponse>,
+    /// Delegates ordered by hub address.
+    pub delegates: Vec<DelegateResponse>,
+    pub snapshot_slot: u64,
+
+    pub indexer: Option<AddressResponse>,
+}
+
+/// Page of `/campaigns`, `next` being the cursor to pass as `after` for the following page.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+#[ts(export_to = "../../../../bindings/CampaignsPage.ts")]
+pub struct CampaignsPage {
+    pub campaigns: Vec<CampaignResponse>,
+    pub next: Option<u64>,
+}
+
+/// Query parameters of `/campaigns`.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(bound = "S::Address: serde::de::DeserializeOwned")]
+pub struct CampaignsQuery<S: Spec> {
+    pub phase: Option<Phase>,
+    pub campaigner: Option<S::Address>,
+    pub delegate: Option<S::Address>,
+    pub indexer: Option<S::Address>,
+    #[serde(default)]
+    pub sort: CampaignSort,
+    #[serde(default)]
+    pub order: SortOrder,
+    pub after: Option<u64>,
+    pub limit: Option<usize>,
+}
+
+/// Tally of the votes of a campaign phase, computed like the state machine does when
+/// confirming.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+#[ts(export_to = "../../../../bindings/TallyResponse.ts")]
+pub struct TallyResponse {
+    pub approved: Power,
+    pub rejected: Power,
+    /// Quorum denominator, the total voting power at the campaign snapshot.
+    pub total: Power,
+    /// Share of the total voting power that voted, in basis points.
+    pub turnout_bps: u64,
+    /// Whether the turnout reached [`QUORUM_BPS`](crate::voting::QUORUM_BPS). Informational,
+    /// confirming the phase doesn't require it.
+    pub quorum: bool,
+    /// Whether voting ended, in which case the tally is final.
+    pub closed: bool,
+    /// Weight of each criterion of the campaign aggregated over the approving votes by voting
+    /// power, like the delegate rewards are, scaled to
+    /// [`WEIGHT_PRECISION`](crate::rewards::WEIGHT_PRECISION).
+    pub criteria_weights: Vec<u64>,
+    /// Delegates of the campaign which haven't voted, ordered by hub address.
+    pub non_voters: Vec<DelegateResponse>,
+}
+
+/// Vote a delegate still owes on a campaign.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, ts_rs::TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+#[ts(export_to = "../../../../bindings/DelegateAction.ts")]
+pub enum DelegateAction {
+    VoteCriteria,
+    VoteDistribution,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+#[ts(export_to = "../../../../bindings/PendingAction.ts")]
+pub struct PendingAction {
+    pub campaign_id: u64,
+    pub title: String,
+    pub action: DelegateAction,
+}
+
+/// Number of campaigns in a phase.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+#[ts(export_to = "../../../../bindings/PhaseCount.ts")]
+pub struct PhaseCount {
+    pub phase: Phase,
+    pub campaigns: u64,
+}
+
+/// Protocol statistics served at `/modules/core/stats`, which the rollup also serves at
+/// `/core/stats`.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+#[ts(export_to = "../../../../bindings/StatsResponse.ts")]
+pub struct StatsResponse {
+    /// Campaigns per phase, in the order campaigns go through them.
+    pub campaigns: Vec<PhaseCount>,
+    /// Funds held in reward pools whose rewards are not distributed yet.
+    pub escrowed_budget: u64,
+    /// Number of campaigns whose delegate rewards were distributed.
+    pub distributions: u64,
+    pub distributed_rewards: u64,
+    pub indexers: u64,
+    pub relayers: u64,
+    pub delegates: u64,
+    pub total_voting_power: Power,
+    /// Average participation of the criteria tallies, in basis points.
+    pub average_turnout_bps: u64,
+}
+
+/// Query parameter of every route, the slot to read the state at. The latest state is read
+/// when it is not set.
+#[derive(Clone, Copy, Debug, Default, serde::Deserialize)]
+pub struct HeightQuery {
+    pub height: Option<u64>,
+}
+
+/// The state at the height is not stored by the node.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UnavailableHeight {
+    /// The node has pruned the state at the height.
+    Pruned(u64),
+    /// The height is above the head of the rollup, `latest` being the last slot core executed a
+    /// call in.
+    Future { height: u64, latest: u64 },
+}
+
+impl UnavailableHeight {
+    pub fn height(&self) -> u64 {
+        match self {
+            UnavailableHeight::Pruned(height) | UnavailableHeight::Future { height, .. } => *height,
+        }
+    }
+}
+
+impl std::fmt::Display for UnavailableHeight {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnavailableHeight::Pruned(height) => {
+                write!(f, "state at height {height} has been pruned")
+            },
+            UnavailableHeight::Future { height, latest } => write!(
+                f,
+                "state at height {height} has not been produced yet, the latest known slot is \
+                 {latest}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for UnavailableHeight {}
+
+impl From<UnavailableHeight> for ErrorObjectOwned {
+    fn from(e: UnavailableHeight) -> Self {
+        ErrorObjectOwned::owned(INVALID_PARAMS_CODE, e.to_string(), Some(e.height()))
+    }
+}
+
+/// Query parameters of `/voting_powers`.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(bound = "S::Address: serde::de::DeserializeOwned")]
+pub struct VotingPowersQuery<S: Spec> {
+    pub after: Option<S::Address>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+#[ts(export_to = "../../../../bindings/ProposalResponse.ts")]
+pub struct ProposalResponse {
+    pub id: u64,
+    pub proposer: AddressResponse,
+    pub criteria: Criteria,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+#[ts(export_to = "../../../../bindings/IndexerResponse.ts")]
+pub struct IndexerResponse {
+    pub addr: String,
+    pub eth_addr: Option<String>,
+    pub alias: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+#[ts(export_to = "../../../../bindings/VotingPowerResponse.ts")]
+pub struct VotingPowerResponse {
+    pub addr: String,
+    pub eth_addr: Option<String>,
+    pub power: Power,
+}
+
+/// Page of `/voting_powers` ordered from highest to lowest power, `next` being the cursor to
+/// pass as `after` for the following page.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, ts_rs::TS)]
+#[ts(export)]
+#[ts(export_to = "../../../../bindings/VotingPowersPage.ts")]
+pub struct VotingPowersPage {
+    pub powers: Vec<VotingPowerResponse>,
+    pub next: Option<String>,
+    /// Number of addresses with a voting power.
+    pub total: u64,
+}
 
-    pub indexer: Option<String>,
+// Admin queries.
+impl<S: Spec> Core<S> {
+    pub fn get_admin<Accessor: StateAccessor>(
+        &self,
+        state: &mut Accessor,
+    ) -> Result<Option<S::Address>, <Accessor as StateReader<User>>::Error> {
+        self.admin.get(state)
+    }
 }
 
 // Account queries.
@@ -62,16 +318,88 @@ impl<S: Spec> Core<S> {
         eth_addr: &str,
         state: &mut Accessor,
     ) -> Result<Option<Account>, <Accessor as StateReader<User>>::Error> {
-        let addr = filament_hub_eth::addr_to_hub_address::<S>(eth_addr).unwrap();
+        let Ok(addr) = filament_hub_eth::addr_to_hub_address::<S>(eth_addr) else {
+            return Ok(None);
+        };
         let credential_id = filament_hub_eth::hub_addr_to_credential_id::<
             <S::CryptoSpec as CryptoSpec>::Hasher,
             S,
         >(&addr);
-        let account = self
+        self.get_account_by_credential_id(credential_id, state)
+    }
+
+    /// Returns the account of the credential, `None` until its first transaction.
+    pub fn get_account_by_credential_id<Accessor: StateAccessor>(
+        &self,
+        credential_id: CredentialId,
+        state: &mut Accessor,
+    ) -> Result<Option<Account>, <Accessor as StateReader<User>>::Error> {
+        Ok(self
             .nonces
             .nonce(&credential_id, state)?
-            .map(|nonce| Account { nonce });
-        Ok(account)
+            .map(|nonce| Account { nonce }))
+    }
+
+    /// Returns the eth address linked to the hub address.
+    pub fn get_linked_eth_address<Accessor: StateAccessor>(
+        &self,
+        addr: S::Address,
+        state: &mut Accessor,
+    ) -> Result<Option<String>, <Accessor as StateReader<User>>::Error> {
+        self.eth_addresses.get(&addr, state)
+    }
+
+    /// Returns the hub address linked to the eth address, if any.
+    pub fn get_linked_hub_address<Accessor: StateAccessor>(
+        &self,
+        eth_addr: &str,
+        state: &mut Accessor,
+    ) -> Result<Option<S::Address>, <Accessor as StateReader<User>>::Error> {
+        match crate::call::normalize_eth_address(eth_addr) {
+            Ok(eth_addr) => self.hub_addresses.get(&eth_addr, state),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Returns the hub address the eth address acts as: the linked hub address if any, the
+    /// address derived from the eth address otherwise. `None` if the eth address is malformed.
+    pub fn get_hub_address_by_eth_addr<Accessor: StateAccessor>(
+        &self,
+        eth_addr: &str,
+        state: &mut Accessor,
+    ) -> Result<Option<S::Address>, <Accessor as StateReader<User>>::Error> {
+        let Ok(derived) = filament_hub_eth::addr_to_hub_address::<S>(eth_addr) else {
+            return Ok(None);
+        };
+
+        Ok(Some(
+            self.get_linked_hub_address(eth_addr, state)?
+                .unwrap_or(derived),
+        ))
+    }
+
+    /// Returns the owner set of the multisig at the eth address, if synced.
+    pub fn get_multisig<Accessor: StateAccessor>(
+        &self,
+        eth_addr: &str,
+        state: &mut Accessor,
+    ) -> Result<Option<Multisig>, <Accessor as StateReader<User>>::Error> {
+        match crate::call::normalize_eth_address(eth_addr) {
+            Ok(eth_addr) => self.multisigs.get(&eth_addr, state),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Returns the session key with the eth address, if granted.
+    pub fn get_session_key<Accessor: StateAccessor>(
+        &self,
+        key: &str,
+        state: &mut Accessor,
+    ) -> Result<Option<SessionKey<S>>, <Accessor as StateReader<User>>::Error> {
+        match crate::call::normalize_eth_address(key) {
+            Ok(key) => self.session_keys.get(&key, state),
+            Err(_) => Ok(None),
+        }
     }
 }
 
@@ -104,6 +432,20 @@ impl<S: Spec> Core<S> {
         Ok(campaigns)
     }
 
+    /// Returns up to `limit` campaigns matching `filter`, starting after the campaign `after`
+    /// when paginating.
+    pub fn get_campaigns_page<Accessor: StateAccessor>(
+        &self,
+        filter: &CampaignFilter<S>,
+        sort: CampaignSort,
+        order: SortOrder,
+        after: Option<u64>,
+        limit: usize,
+        state: &mut Accessor,
+    ) -> Result<CampaignPage<S>, <Accessor as StateReader<User>>::Error> {
+        self.indexed_campaigns(filter, sort, order, after, limit, state)
+    }
+
     pub fn get_campaigns_by_addr<Accessor: StateAccessor>(
         &self,
         addr: S::Address,
@@ -123,6 +465,17 @@ impl<S: Spec> Core<S> {
         Ok(campaigns)
     }
 
+    pub fn get_criteria_proposals<Accessor: StateAccessor>(
+        &self,
+        campaign_id: u64,
+        state: &mut Accessor,
+    ) -> Result<Vec<CriteriaProposal<S>>, <Accessor as StateReader<User>>::Error> {
+        Ok(self
+            .criteria_proposals
+            .get(&campaign_id, state)?
+            .unwrap_or_default())
+    }
+
     pub fn get_criteria_proposal<Accessor: StateAccessor>(
         &self,
         campaign_id: u64,
@@ -166,6 +519,29 @@ impl<S: Spec> Core<S> {
     ) -> Result<Option<Segment>, <Accessor as StateReader<User>>::Error> {
         self.segments.get(&campaign_id, state)
     }
+
+    /// Recomputes the voting powers of the campaign delegates from the power history at the
+    /// snapshot slot of the campaign.
+    pub fn get_campaign_powers<Accessor: StateAccessor>(
+        &self,
+        campaign_id: u64,
+        state: &mut Accessor,
+    ) -> Result<Option<HashMap<String, Power>>, <Accessor as StateReader<User>>::Error> {
+        let Some(campaign) = self.campaigns.get(&campaign_id, state)? else {
+            return Ok(None);
+        };
+
+        let mut powers = HashMap::new();
+        for delegate in campaign.delegates.keys() {
+            let power = match delegate.parse::<S::Address>() {
+                Ok(addr) => self.get_voting_power_at(addr, campaign.snapshot_slot, state)?,
+                Err(_) => 0,
+            };
+            powers.insert(delegate.clone(), power);
+        }
+
+        Ok(Some(powers))
+    }
 }
 
 // Indexer queries.
@@ -202,8 +578,81 @@ impl<S: Spec> Core<S> {
     }
 }
 
+// Delegate queries.
+impl<S: Spec> Core<S> {
+    pub fn get_delegates<Accessor: StateAccessor>(
+        &self,
+        state: &mut Accessor,
+    ) -> Result<Vec<S::Address>, <Accessor as StateReader<User>>::Error> {
+        self.delegates.iter(state)?.collect()
+    }
+
+    /// Returns the campaigns the address was elected a delegate of, in drafting order.
+    pub fn get_campaigns_by_delegate<Accessor: StateAccessor>(
+        &self,
+        addr: S::Address,
+        state: &mut Accessor,
+    ) -> Result<Vec<Campaign<S>>, <Accessor as StateReader<User>>::Error> {
+        let mut campaigns = vec![];
+        for id in self
+            .campaigns_by_delegate
+            .get(&addr, state)?
+            .unwrap_or_default()
+        {
+            if let Some(campaign) = self.campaigns.get(&id, state)? {
+                campaigns.push(campaign);
+            }
+        }
+
+        Ok(campaigns)
+    }
+
+    /// Returns the votes the delegate still owes, on its campaigns in the criteria or
+    /// distribution phase.
+    pub fn get_pending_actions<Accessor: StateAccessor>(
+        &self,
+        addr: S::Address,
+        state: &mut Accessor,
+    ) -> Result<Vec<PendingAction>, <Accessor as StateReader<User>>::Error> {
+        let delegate = addr.to_string();
+
+        let mut actions = vec![];
+        for campaign in self.get_campaigns_by_delegate(addr, state)? {
+            let (action, voted) = match campaign.phase {
+                Phase::Criteria => (
+                    DelegateAction::VoteCriteria,
+                    self.get_criteria_votes(campaign.id, state)?
+                        .contains_key(&delegate),
+                ),
+                Phase::Distribution => (
+                    DelegateAction::VoteDistribution,
+                    self.get_distribution_votes(campaign.id, state)?
+                        .contains_key(&delegate),
+                ),
+                _ => continue,
+            };
+            if !voted {
+                actions.push(PendingAction {
+                    campaign_id: campaign.id,
+                    title: campaign.title,
+                    action,
+                });
+            }
+        }
+
+        Ok(actions)
+    }
+}
+
 // Relayer queries.
 impl<S: Spec> Core<S> {
+    pub fn get_relayers<Accessor: StateAccessor>(
+        &self,
+        state: &mut Accessor,
+    ) -> Result<Vec<Relayer<S>>, <Accessor as StateReader<User>>::Error> {
+        self.relayers.iter(state)?.collect()
+    }
+
     pub fn get_relayer<Accessor: StateAccessor>(
         &self,
         addr: S::Address,
@@ -229,270 +678,1638 @@ impl<S: Spec> Core<S> {
         Ok(self.powers.get(&addr, state)?.unwrap_or_default())
     }
 
+    pub fn get_voting_power_at<Accessor: StateAccessor>(
+        &self,
+        addr: S::Address,
+        slot: u64,
+        state: &mut Accessor,
+    ) -> Result<Power, <Accessor as StateReader<User>>::Error> {
+        self.voting_power_at(&addr, slot, state)
+    }
+
+    pub fn get_power_checkpoints<Accessor: StateAccessor>(
+        &self,
+        addr: S::Address,
+        state: &mut Accessor,
+    ) -> Result<Vec<PowerCheckpoint>, <Accessor as StateReader<User>>::Error> {
+        let len = self
+            .power_checkpoints_len
+            .get(&addr, state)?
+            .unwrap_or_default();
+        let mut checkpoints = Vec::with_capacity(len as usize);
+        for idx in 0..len {
+            if let Some(checkpoint) = self.power_checkpoints.get(&(addr.clone(), idx), state)? {
+                checkpoints.push(checkpoint);
+            }
+        }
+
+        Ok(checkpoints)
+    }
+
+    /// Returns up to `limit` voting powers from highest to lowest, starting after the address
+    /// `after` when paginating.
     pub fn get_voting_powers<Accessor: StateAccessor>(
         &self,
+        after: Option<S::Address>,
+        limit: usize,
         state: &mut Accessor,
     ) -> Result<Vec<(S::Address, Power)>, <Accessor as StateReader<User>>::Error> {
-        self.powers_index
-            .iter(state)?
-            .collect::<Result<Vec<_>, <Accessor as StateReader<User>>::Error>>()
+        self.indexed_powers(after.as_ref(), limit, state)
     }
-}
 
-// RPC
-#[rpc_gen(client, server, namespace = "core")]
-impl<S: Spec> Core<S> {
-    #[rpc_method(name = "getCampaign")]
-    pub fn rpc_get_campaign(
+    pub fn get_total_voting_power<Accessor: StateAccessor>(
         &self,
-        id: u64,
-        state: &mut ApiStateAccessor<S>,
-    ) -> RpcResult<Option<Campaign<S>>> {
-        Ok(self.get_campaign(id, state).unwrap_infallible())
+        state: &mut Accessor,
+    ) -> Result<Power, <Accessor as StateReader<User>>::Error> {
+        Ok(self.total_voting_power.get(state)?.unwrap_or_default())
     }
 
-    #[rpc_method(name = "getCriteriaProposal")]
-    pub fn rpc_get_criteria_proposal(
+    pub fn get_total_voting_power_at<Accessor: StateAccessor>(
+        &self,
+        slot: u64,
+        state: &mut Accessor,
+    ) -> Result<Power, <Accessor as StateReader<User>>::Error> {
+        self.total_voting_power_at(slot, state)
+    }
+
+    /// Returns the criteria tally recorded when the criteria of the campaign were confirmed.
+    pub fn get_criteria_tally<Accessor: StateAccessor>(
         &self,
         campaign_id: u64,
-        proposal_id: u64,
-        state: &mut ApiStateAccessor<S>,
-    ) -> RpcResult<Option<CriteriaProposal<S>>> {
-        Ok(self
-            .get_criteria_proposal(campaign_id, proposal_id, state)
-            .unwrap_infallible())
+        state: &mut Accessor,
+    ) -> Result<Option<Tally>, <Accessor as StateReader<User>>::Error> {
+        self.criteria_tallies.get(&campaign_id, state)
     }
 
-    /// Returns the list of currently registered indexers.
-    #[rpc_method(name = "getIndexer")]
-    pub fn rpc_get_indexer(
+    /// Returns the distribution tally recorded when the distribution of the campaign was
+    /// confirmed.
+    pub fn get_distribution_tally<Accessor: StateAccessor>(
         &self,
-        addr: S::Address,
-        state: &mut ApiStateAccessor<S>,
-    ) -> RpcResult<Option<Indexer<S>>> {
-        Ok(self.get_indexer(addr, state).unwrap_infallible())
+        campaign_id: u64,
+        state: &mut Accessor,
+    ) -> Result<Option<Tally>, <Accessor as StateReader<User>>::Error> {
+        self.distribution_tallies.get(&campaign_id, state)
     }
 
-    /// Returns the list of currently registered indexers.
-    #[rpc_method(name = "getIndexers")]
-    pub fn rpc_get_indexers(&self, state: &mut ApiStateAccessor<S>) -> RpcResult<Vec<Indexer<S>>> {
-        Ok(self.get_indexers(state).unwrap_infallible())
+    pub fn get_last_eth_block<Accessor: StateAccessor>(
+        &self,
+        state: &mut Accessor,
+    ) -> Result<Option<u64>, <Accessor as StateReader<User>>::Error> {
+        self.last_eth_block.get(state)
     }
 
-    #[rpc_method(name = "getSegment")]
-    pub fn rpc_get_segment(
+    pub fn get_voting_powers_count<Accessor: StateAccessor>(
         &self,
-        id: u64,
-        state: &mut ApiStateAccessor<S>,
-    ) -> RpcResult<Option<Segment>> {
-        Ok(self.get_segment(id, state).unwrap_infallible())
+        state: &mut Accessor,
+    ) -> Result<u64, <Accessor as StateReader<User>>::Error> {
+        Ok(self.power_index_len.get(state)?.unwrap_or_default())
     }
 }
 
-// Axum routes.
+// Attestation queries.
 impl<S: Spec> Core<S> {
-    fn campaign_to_response<Accessor: StateAccessor>(
+    pub fn get_attestation_threshold<Accessor: StateAccessor>(
         &self,
-        campaign: Campaign<S>,
         state: &mut Accessor,
-    ) -> anyhow::Result<CampaignResponse> {
-        let mut evictions = vec![];
-        for addr in &campaign.evictions {
-            evictions.push(self.eth_addresses.get(addr, state)?.unwrap());
-        }
-        let mut delegates = HashMap::new();
-        for (addr, power) in &campaign.delegates {
-            delegates.insert(
-                self.eth_addresses
-                    .get(&(*addr).parse::<S::Address>()?, state)?
-                    .unwrap(),
-                *power,
-            );
-        }
-        let mut indexer = None;
-        if campaign.indexer.is_some() {
-            indexer = Some(
-                self.eth_addresses
-                    .get(&campaign.indexer.unwrap(), state)?
-                    .unwrap(),
-            );
-        }
-
-        Ok(CampaignResponse {
-            id: campaign.id,
-            campaigner: self
-                .eth_addresses
-                .get(&campaign.campaigner, state)?
-                .unwrap(),
-            phase: campaign.phase,
-            title: campaign.title,
-            description: campaign.description,
-            criteria: campaign.criteria,
-            evictions,
-            delegates,
-            indexer,
-        })
+    ) -> Result<Option<u64>, <Accessor as StateReader<User>>::Error> {
+        self.attestation_threshold.get(state)
     }
 
-    fn get_eth_address<Accessor: StateAccessor>(
+    /// Returns the pending attestations for the power of `addr` at `eth_block`.
+    pub fn get_attestations<Accessor: StateAccessor>(
         &self,
-        addr: String,
+        addr: S::Address,
+        eth_block: u64,
         state: &mut Accessor,
-    ) -> anyhow::Result<Option<String>> {
+    ) -> Result<Vec<Attestation<S>>, <Accessor as StateReader<User>>::Error> {
         Ok(self
-            .eth_addresses
-            .get(&addr.parse::<<S as Spec>::Address>()?, state)?)
+            .attestations
+            .get(&(addr, eth_block), state)?
+            .unwrap_or_default())
     }
 
-    async fn route_get_account_by_eth_addr(
-        state: ApiState<Self, S>,
-        Path(eth_addr): Path<String>,
-    ) -> ApiResult<Account> {
-        let account = state
-            .get_account_by_eth_addr(&eth_addr, &mut state.api_state_accessor())
-            .unwrap_infallible()
-            .ok_or_else(|| errors::not_found_404("Account", eth_addr))?;
-        Ok(account.into())
+    pub fn get_attestation_conflicts<Accessor: StateAccessor>(
+        &self,
+        addr: S::Address,
+        state: &mut Accessor,
+    ) -> Result<Vec<AttestationConflict<S>>, <Accessor as StateReader<User>>::Error> {
+        Ok(self
+            .attestation_conflicts
+            .get(&addr, state)?
+            .unwrap_or_default())
     }
+}
 
-    #[allow(clippy::single_call_fn, clippy::unused_async)]
-    async fn route_get_campaign(
-        state: ApiState<Self, S>,
+// Light client queries.
+impl<S: Spec> Core<S> {
+    pub fn get_eth_head<Accessor: StateAccessor>(
+        &self,
+        state: &mut Accessor,
+    ) -> Result<Option<EthHeader>, <Accessor as StateReader<User>>::Error> {
+        match self.eth_head.get(state)? {
+            Some(number) => self.eth_headers.get(&number, state),
+            None => Ok(None),
+        }
+    }
+
+    pub fn get_eth_header<Accessor: StateAccessor>(
+        &self,
+        number: u64,
+        state: &mut Accessor,
+    ) -> Result<Option<EthHeader>, <Accessor as StateReader<User>>::Error> {
+        self.eth_headers.get(&number, state)
+    }
+}
+
+// Reward queries.
+impl<S: Spec> Core<S> {
+    pub fn get_reward_pool<Accessor: StateAccessor>(
+        &self,
+        campaign_id: u64,
+        state: &mut Accessor,
+    ) -> Result<Option<RewardPool<S>>, <Accessor as StateReader<User>>::Error> {
+        self.reward_pools.get(&campaign_id, state)
+    }
+
+    pub fn get_objective<Accessor: StateAccessor>(
+        &self,
+        campaign_id: u64,
+        state: &mut Accessor,
+    ) -> Result<Option<ObjectiveMeasurement>, <Accessor as StateReader<User>>::Error> {
+        self.objectives.get(&campaign_id, state)
+    }
+
+    pub fn get_delegate_rewards<Accessor: StateAccessor>(
+        &self,
+        campaign_id: u64,
+        state: &mut Accessor,
+    ) -> Result<Vec<DelegateReward>, <Accessor as StateReader<User>>::Error> {
+        Ok(self
+            .delegate_rewards
+            .get(&campaign_id, state)?
+            .unwrap_or_default())
+    }
+}
+
+// Stats queries.
+impl<S: Spec> Core<S> {
+    pub fn get_stats<Accessor: StateAccessor>(
+        &self,
+        state: &mut Accessor,
+    ) -> Result<StatsResponse, <Accessor as StateReader<User>>::Error> {
+        let campaigns = Phase::ALL
+            .into_iter()
+            .map(|phase| {
+                let campaigns = self.campaign_counts.get(&phase, state)?.unwrap_or_default();
+                Ok(PhaseCount { phase, campaigns })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let stats = self.stats.get(state)?.unwrap_or_default();
+
+        Ok(StatsResponse {
+            campaigns,
+            escrowed_budget: stats.escrowed_budget,
+            distributions: stats.distributions,
+            distributed_rewards: stats.distributed_rewards,
+            indexers: self.indexers.len(state)? as u64,
+            relayers: self.relayers.len(state)? as u64,
+            delegates: self.delegates.len(state)? as u64,
+            total_voting_power: self.get_total_voting_power(state)?,
+            average_turnout_bps: stats.average_turnout_bps(),
+        })
+    }
+}
+
+// Historical state.
+impl<S: Spec> Core<S> {
+    /// Returns the archival state at `height`. The admin is set at genesis, so it is missing
+    /// only from the versions the node doesn't store: the ones above the head and the pruned
+    /// ones. The head is at or above the last slot core executed a call in, so a missing version
+    /// above that slot has not been produced yet, while one at or below it has been pruned.
+    pub fn archival_state(
+        &self,
+        height: u64,
+        state: &mut ApiStateAccessor<S>,
+    ) -> Result<ApiStateAccessor<S>, UnavailableHeight> {
+        let mut archival = state.get_archival_at(height);
+        if self.admin.get(&mut archival).unwrap_infallible().is_some() {
+            return Ok(archival);
+        }
+
+        let latest = self
+            .last_slot
+            .get(state)
+            .unwrap_infallible()
+            .unwrap_or_default();
+        if height > latest {
+            Err(UnavailableHeight::Future { height, latest })
+        } else {
+            Err(UnavailableHeight::Pruned(height))
+        }
+    }
+
+    /// Runs `query` on the state at `height`, the latest state if `None`.
+    pub fn at_height<T>(
+        &self,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+        query: impl FnOnce(&mut ApiStateAccessor<S>) -> T,
+    ) -> Result<T, UnavailableHeight> {
+        match height {
+            None => Ok(query(state)),
+            Some(height) => Ok(query(&mut self.archival_state(height, state)?)),
+        }
+    }
+}
+
+// RPC
+#[rpc_gen(client, server, namespace = "core")]
+impl<S: Spec> Core<S> {
+    #[rpc_method(name = "getCampaign")]
+    pub fn rpc_get_campaign(
+        &self,
+        id: u64,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<Campaign<S>>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_campaign(id, state).unwrap_infallible()
+        })?)
+    }
+
+    #[rpc_method(name = "getCriteriaProposal")]
+    pub fn rpc_get_criteria_proposal(
+        &self,
+        campaign_id: u64,
+        proposal_id: u64,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<CriteriaProposal<S>>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_criteria_proposal(campaign_id, proposal_id, state)
+                .unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the list of currently registered indexers.
+    #[rpc_method(name = "getIndexer")]
+    pub fn rpc_get_indexer(
+        &self,
+        addr: S::Address,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<Indexer<S>>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_indexer(addr, state).unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the list of currently registered indexers.
+    #[rpc_method(name = "getIndexers")]
+    pub fn rpc_get_indexers(
+        &self,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Vec<Indexer<S>>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_indexers(state).unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the voting powers of the campaign delegates at the campaign snapshot slot.
+    #[rpc_method(name = "getCampaignPowers")]
+    pub fn rpc_get_campaign_powers(
+        &self,
+        campaign_id: u64,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<HashMap<String, Power>>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_campaign_powers(campaign_id, state)
+                .unwrap_infallible()
+        })?)
+    }
+
+    #[rpc_method(name = "getSegment")]
+    pub fn rpc_get_segment(
+        &self,
+        id: u64,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<Segment>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_segment(id, state).unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the rewards distributed to the delegates of a campaign.
+    #[rpc_method(name = "getDelegateRewards")]
+    pub fn rpc_get_delegate_rewards(
+        &self,
+        campaign_id: u64,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Vec<DelegateReward>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_delegate_rewards(campaign_id, state)
+                .unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the conflicting voting power attestations recorded for an address.
+    #[rpc_method(name = "getAttestationConflicts")]
+    pub fn rpc_get_attestation_conflicts(
+        &self,
+        addr: S::Address,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Vec<AttestationConflict<S>>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_attestation_conflicts(addr, state)
+                .unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the latest Ethereum header of the light client.
+    #[rpc_method(name = "getEthHead")]
+    pub fn rpc_get_eth_head(
+        &self,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<EthHeader>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_eth_head(state).unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the sum of the voting powers of all stakers.
+    #[rpc_method(name = "getTotalVotingPower")]
+    pub fn rpc_get_total_voting_power(
+        &self,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Power> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_total_voting_power(state).unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the voting power of an address at the given slot.
+    #[rpc_method(name = "getVotingPowerAt")]
+    pub fn rpc_get_voting_power_at(
+        &self,
+        addr: S::Address,
+        slot: u64,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Power> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_voting_power_at(addr, slot, state)
+                .unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the owner set of the multisig at the eth address.
+    #[rpc_method(name = "getMultisig")]
+    pub fn rpc_get_multisig(
+        &self,
+        eth_addr: String,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<Multisig>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_multisig(&eth_addr, state).unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the session key with the eth address.
+    #[rpc_method(name = "getSessionKey")]
+    pub fn rpc_get_session_key(
+        &self,
+        key: String,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<SessionKey<S>>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_session_key(&key, state).unwrap_infallible()
+        })?)
+    }
+
+    #[rpc_method(name = "getAdmin")]
+    pub fn rpc_get_admin(
+        &self,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<S::Address>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_admin(state).unwrap_infallible()
+        })?)
+    }
+
+    #[rpc_method(name = "getAccountByEthAddr")]
+    pub fn rpc_get_account_by_eth_addr(
+        &self,
+        eth_addr: String,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<Account>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_account_by_eth_addr(&eth_addr, state)
+                .unwrap_infallible()
+        })?)
+    }
+
+    #[rpc_method(name = "getAccountByCredentialId")]
+    pub fn rpc_get_account_by_credential_id(
+        &self,
+        credential_id: CredentialId,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<Account>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_account_by_credential_id(credential_id, state)
+                .unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the eth address linked to the hub address.
+    #[rpc_method(name = "getLinkedEthAddress")]
+    pub fn rpc_get_linked_eth_address(
+        &self,
+        addr: S::Address,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<String>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_linked_eth_address(addr, state).unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the hub address linked to the eth address.
+    #[rpc_method(name = "getLinkedHubAddress")]
+    pub fn rpc_get_linked_hub_address(
+        &self,
+        eth_addr: String,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<S::Address>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_linked_hub_address(&eth_addr, state)
+                .unwrap_infallible()
+        })?)
+    }
+
+    /// Returns a page of campaigns matching the filter.
+    #[rpc_method(name = "getCampaigns")]
+    pub fn rpc_get_campaigns(
+        &self,
+        filter: CampaignFilter<S>,
+        sort: Option<CampaignSort>,
+        order: Option<SortOrder>,
+        after: Option<u64>,
+        limit: Option<usize>,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<CampaignPage<S>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_campaigns_page(
+                &filter,
+                sort.unwrap_or_default(),
+                order.unwrap_or_default(),
+                after,
+                limit.unwrap_or(DEFAULT_CAMPAIGNS_LIMIT),
+                state,
+            )
+            .unwrap_infallible()
+        })?)
+    }
+
+    #[rpc_method(name = "getCampaignsByAddr")]
+    pub fn rpc_get_campaigns_by_addr(
+        &self,
+        addr: S::Address,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Vec<Campaign<S>>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_campaigns_by_addr(addr, state).unwrap_infallible()
+        })?)
+    }
+
+    #[rpc_method(name = "getCriteriaProposals")]
+    pub fn rpc_get_criteria_proposals(
+        &self,
+        campaign_id: u64,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Vec<CriteriaProposal<S>>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_criteria_proposals(campaign_id, state)
+                .unwrap_infallible()
+        })?)
+    }
+
+    #[rpc_method(name = "getCriteriaVotes")]
+    pub fn rpc_get_criteria_votes(
+        &self,
+        campaign_id: u64,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<HashMap<String, CriteriaVote>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_criteria_votes(campaign_id, state)
+                .unwrap_infallible()
+        })?)
+    }
+
+    #[rpc_method(name = "getDistributionVotes")]
+    pub fn rpc_get_distribution_votes(
+        &self,
+        campaign_id: u64,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<HashMap<String, DistributionVote>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_distribution_votes(campaign_id, state)
+                .unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the criteria tally recorded when the criteria of the campaign were confirmed.
+    #[rpc_method(name = "getCriteriaTally")]
+    pub fn rpc_get_criteria_tally(
+        &self,
+        campaign_id: u64,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<Tally>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_criteria_tally(campaign_id, state)
+                .unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the distribution tally recorded when the distribution of the campaign was
+    /// confirmed.
+    #[rpc_method(name = "getDistributionTally")]
+    pub fn rpc_get_distribution_tally(
+        &self,
+        campaign_id: u64,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<Tally>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_distribution_tally(campaign_id, state)
+                .unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the criteria tally of a campaign with its turnout, quorum and non-voters.
+    #[rpc_method(name = "getCriteriaTallyResult")]
+    pub fn rpc_get_criteria_tally_result(
+        &self,
+        campaign_id: u64,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<TallyResponse>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_criteria_tally_response(campaign_id, state)
+                .unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the distribution tally of a campaign with its turnout, quorum and non-voters.
+    #[rpc_method(name = "getDistributionTallyResult")]
+    pub fn rpc_get_distribution_tally_result(
+        &self,
+        campaign_id: u64,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<TallyResponse>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_distribution_tally_response(campaign_id, state)
+                .unwrap_infallible()
+        })?)
+    }
+
+    #[rpc_method(name = "getDelegates")]
+    pub fn rpc_get_delegates(
+        &self,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Vec<S::Address>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_delegates(state).unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the campaigns the address was elected a delegate of.
+    #[rpc_method(name = "getCampaignsByDelegate")]
+    pub fn rpc_get_campaigns_by_delegate(
+        &self,
+        addr: S::Address,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Vec<Campaign<S>>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_campaigns_by_delegate(addr, state)
+                .unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the campaigns the hub address of the eth address was elected a delegate of.
+    #[rpc_method(name = "getCampaignsByDelegateEthAddr")]
+    pub fn rpc_get_campaigns_by_delegate_eth_addr(
+        &self,
+        eth_addr: String,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Vec<Campaign<S>>> {
+        Ok(self.at_height(height, state, |state| {
+            match self
+                .get_hub_address_by_eth_addr(&eth_addr, state)
+                .unwrap_infallible()
+            {
+                Some(addr) => self
+                    .get_campaigns_by_delegate(addr, state)
+                    .unwrap_infallible(),
+                None => vec![],
+            }
+        })?)
+    }
+
+    /// Returns the votes the delegate still owes.
+    #[rpc_method(name = "getPendingActions")]
+    pub fn rpc_get_pending_actions(
+        &self,
+        addr: S::Address,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Vec<PendingAction>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_pending_actions(addr, state).unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the votes the hub address of the eth address still owes as a delegate.
+    #[rpc_method(name = "getPendingActionsByEthAddr")]
+    pub fn rpc_get_pending_actions_by_eth_addr(
+        &self,
+        eth_addr: String,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Vec<PendingAction>> {
+        Ok(self.at_height(height, state, |state| {
+            match self
+                .get_hub_address_by_eth_addr(&eth_addr, state)
+                .unwrap_infallible()
+            {
+                Some(addr) => self.get_pending_actions(addr, state).unwrap_infallible(),
+                None => vec![],
+            }
+        })?)
+    }
+
+    #[rpc_method(name = "getRelayers")]
+    pub fn rpc_get_relayers(
+        &self,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Vec<Relayer<S>>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_relayers(state).unwrap_infallible()
+        })?)
+    }
+
+    #[rpc_method(name = "getVotingPower")]
+    pub fn rpc_get_voting_power(
+        &self,
+        addr: S::Address,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Power> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_voting_power(addr, state).unwrap_infallible()
+        })?)
+    }
+
+    /// Returns up to `limit` voting powers from highest to lowest, starting after `after`.
+    #[rpc_method(name = "getVotingPowers")]
+    pub fn rpc_get_voting_powers(
+        &self,
+        after: Option<S::Address>,
+        limit: Option<usize>,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Vec<(S::Address, Power)>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_voting_powers(
+                after,
+                limit
+                    .unwrap_or(DEFAULT_POWERS_LIMIT)
+                    .clamp(1, MAX_POWERS_LIMIT),
+                state,
+            )
+            .unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the number of addresses with a voting power.
+    #[rpc_method(name = "getVotingPowersCount")]
+    pub fn rpc_get_voting_powers_count(
+        &self,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<u64> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_voting_powers_count(state).unwrap_infallible()
+        })?)
+    }
+
+    #[rpc_method(name = "getPowerCheckpoints")]
+    pub fn rpc_get_power_checkpoints(
+        &self,
+        addr: S::Address,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Vec<PowerCheckpoint>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_power_checkpoints(addr, state).unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the total voting power at the given slot.
+    #[rpc_method(name = "getTotalVotingPowerAt")]
+    pub fn rpc_get_total_voting_power_at(
+        &self,
+        slot: u64,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Power> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_total_voting_power_at(slot, state)
+                .unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the Ethereum block of the last batch of voting power updates applied.
+    #[rpc_method(name = "getLastEthBlock")]
+    pub fn rpc_get_last_eth_block(
+        &self,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<u64>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_last_eth_block(state).unwrap_infallible()
+        })?)
+    }
+
+    #[rpc_method(name = "getAttestationThreshold")]
+    pub fn rpc_get_attestation_threshold(
+        &self,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<u64>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_attestation_threshold(state).unwrap_infallible()
+        })?)
+    }
+
+    /// Returns the pending attestations for the power of an address at an Ethereum block.
+    #[rpc_method(name = "getAttestations")]
+    pub fn rpc_get_attestations(
+        &self,
+        addr: S::Address,
+        eth_block: u64,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Vec<Attestation<S>>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_attestations(addr, eth_block, state)
+                .unwrap_infallible()
+        })?)
+    }
+
+    #[rpc_method(name = "getEthHeader")]
+    pub fn rpc_get_eth_header(
+        &self,
+        number: u64,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<EthHeader>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_eth_header(number, state).unwrap_infallible()
+        })?)
+    }
+
+    #[rpc_method(name = "getRewardPool")]
+    pub fn rpc_get_reward_pool(
+        &self,
+        campaign_id: u64,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<RewardPool<S>>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_reward_pool(campaign_id, state).unwrap_infallible()
+        })?)
+    }
+
+    #[rpc_method(name = "getObjective")]
+    pub fn rpc_get_objective(
+        &self,
+        campaign_id: u64,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<Option<ObjectiveMeasurement>> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_objective(campaign_id, state).unwrap_infallible()
+        })?)
+    }
+
+    #[rpc_method(name = "getStats")]
+    pub fn rpc_get_stats(
+        &self,
+        height: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RpcResult<StatsResponse> {
+        Ok(self.at_height(height, state, |state| {
+            self.get_stats(state).unwrap_infallible()
+        })?)
+    }
+}
+
+// REST responses.
+impl<S: Spec> Core<S> {
+    /// Hub address with its linked eth address, if any.
+    pub fn address_response<Accessor: StateAccessor>(
+        &self,
+        addr: &S::Address,
+        state: &mut Accessor,
+    ) -> Result<AddressResponse, <Accessor as StateReader<User>>::Error> {
+        Ok(AddressResponse {
+            addr: addr.to_string(),
+            eth_addr: self.eth_addresses.get(addr, state)?,
+        })
+    }
+
+    /// Like [`Core::address_response`] for addresses stored as strings. Strings that are not
+    /// valid hub addresses are returned as is, without eth address.
+    fn address_str_response<Accessor: StateAccessor>(
+        &self,
+        addr: &str,
+        state: &mut Accessor,
+    ) -> Result<AddressResponse, <Accessor as StateReader<User>>::Error> {
+        match addr.parse::<S::Address>() {
+            Ok(addr) => self.address_response(&addr, state),
+            Err(_) => Ok(AddressResponse {
+                addr: addr.to_string(),
+                eth_addr: None,
+            }),
+        }
+    }
+
+    pub fn campaign_to_response<Accessor: StateAccessor>(
+        &self,
+        campaign: Campaign<S>,
+        state: &mut Accessor,
+    ) -> Result<CampaignResponse, <Accessor as StateReader<User>>::Error> {
+        let mut evictions = vec![];
+        for addr in &campaign.evictions {
+            evictions.push(self.address_response(addr, state)?);
+        }
+        let mut delegates = vec![];
+        for (addr, power) in &campaign.delegates {
+            let AddressResponse { addr, eth_addr } = self.address_str_response(addr, state)?;
+            delegates.push(DelegateResponse {
+                addr,
+                eth_addr,
+                power: *power,
+            });
+        }
+        delegates.sort_by(|a, b| a.addr.cmp(&b.addr));
+        let indexer = match &campaign.indexer {
+            Some(indexer) => Some(self.address_response(indexer, state)?),
+            None => None,
+        };
+
+        Ok(CampaignResponse {
+            id: campaign.id,
+            campaigner: self.address_response(&campaign.campaigner, state)?,
+            phase: campaign.phase,
+            title: campaign.title,
+            description: campaign.description,
+            criteria: campaign.criteria,
+            evictions,
+            delegates,
+            snapshot_slot: campaign.snapshot_slot,
+            indexer,
+        })
+    }
+
+    pub fn campaigns_to_response<Accessor: StateAccessor>(
+        &self,
+        campaigns: Vec<Campaign<S>>,
+        state: &mut Accessor,
+    ) -> Result<Vec<CampaignResponse>, <Accessor as StateReader<User>>::Error> {
+        campaigns
+            .into_iter()
+            .map(|campaign| self.campaign_to_response(campaign, state))
+            .collect()
+    }
+
+    /// Returns the criteria votes of the campaign ordered by voter, `None` if the campaign
+    /// doesn't exist.
+    pub fn get_criteria_votes_response<Accessor: StateAccessor>(
+        &self,
+        campaign_id: u64,
+        state: &mut Accessor,
+    ) -> Result<Option<Vec<VoteResponse<CriteriaVote>>>, <Accessor as StateReader<User>>::Error>
+    {
+        if self.campaigns.get(&campaign_id, state)?.is_none() {
+            return Ok(None);
+        }
+        let votes = self.get_criteria_votes(campaign_id, state)?;
+
+        self.votes_to_response(votes, state).map(Some)
+    }
+
+    /// Returns the distribution votes of the campaign ordered by voter, `None` if the campaign
+    /// doesn't exist.
+    pub fn get_distribution_votes_response<Accessor: StateAccessor>(
+        &self,
+        campaign_id: u64,
+        state: &mut Accessor,
+    ) -> Result<Option<Vec<VoteResponse<DistributionVote>>>, <Accessor as StateReader<User>>::Error>
+    {
+        if self.campaigns.get(&campaign_id, state)?.is_none() {
+            return Ok(None);
+        }
+        let votes = self.get_distribution_votes(campaign_id, state)?;
+
+        self.votes_to_response(votes, state).map(Some)
+    }
+
+    /// Returns the criteria proposals of the campaign, `None` if the campaign doesn't exist.
+    pub fn get_criteria_proposals_response<Accessor: StateAccessor>(
+        &self,
+        campaign_id: u64,
+        state: &mut Accessor,
+    ) -> Result<Option<Vec<ProposalResponse>>, <Accessor as StateReader<User>>::Error> {
+        if self.campaigns.get(&campaign_id, state)?.is_none() {
+            return Ok(None);
+        }
+
+        let mut proposals = vec![];
+        for (id, proposal) in self
+            .get_criteria_proposals(campaign_id, state)?
+            .into_iter()
+            .enumerate()
+        {
+            proposals.push(ProposalResponse {
+                id: id as u64,
+                proposer: self.address_response(&proposal.proposer, state)?,
+                criteria: proposal.criteria,
+            });
+        }
+
+        Ok(Some(proposals))
+    }
+
+    pub fn get_indexers_response<Accessor: StateAccessor>(
+        &self,
+        state: &mut Accessor,
+    ) -> Result<Vec<IndexerResponse>, <Accessor as StateReader<User>>::Error> {
+        let mut indexers = vec![];
+        for Indexer { addr, alias } in self.get_indexers(state)? {
+            let AddressResponse { addr, eth_addr } = self.address_response(&addr, state)?;
+            indexers.push(IndexerResponse {
+                addr,
+                eth_addr,
+                alias,
+            });
+        }
+
+        Ok(indexers)
+    }
+
+    pub fn get_relayers_response<Accessor: StateAccessor>(
+        &self,
+        state: &mut Accessor,
+    ) -> Result<Vec<AddressResponse>, <Accessor as StateReader<User>>::Error> {
+        let mut relayers = vec![];
+        for relayer in self.get_relayers(state)? {
+            relayers.push(self.address_response(&relayer, state)?);
+        }
+
+        Ok(relayers)
+    }
+
+    /// Returns the registered delegates with their current voting power.
+    pub fn get_delegates_response<Accessor: StateAccessor>(
+        &self,
+        state: &mut Accessor,
+    ) -> Result<Vec<DelegateResponse>, <Accessor as StateReader<User>>::Error> {
+        let mut delegates = vec![];
+        for delegate in self.get_delegates(state)? {
+            let power = self.get_voting_power(delegate.clone(), state)?;
+            let AddressResponse { addr, eth_addr } = self.address_response(&delegate, state)?;
+            delegates.push(DelegateResponse {
+                addr,
+                eth_addr,
+                power,
+            });
+        }
+
+        Ok(delegates)
+    }
+
+    /// Returns up to `limit` voting powers from highest to lowest, starting after the address
+    /// `after` when paginating.
+    pub fn get_voting_powers_response<Accessor: StateAccessor>(
+        &self,
+        after: Option<S::Address>,
+        limit: usize,
+        state: &mut Accessor,
+    ) -> Result<VotingPowersPage, <Accessor as StateReader<User>>::Error> {
+        let limit = limit.clamp(1, MAX_POWERS_LIMIT);
+        let page = self.get_voting_powers(after, limit, state)?;
+        let total = self.get_voting_powers_count(state)?;
+
+        let next = match page.last() {
+            Some((addr, _)) if page.len() == limit => self
+                .get_voting_powers(Some(addr.clone()), 1, state)?
+                .first()
+                .map(|_| addr.to_string()),
+            _ => None,
+        };
+        let mut powers = vec![];
+        for (addr, power) in page {
+            let AddressResponse { addr, eth_addr } = self.address_response(&addr, state)?;
+            powers.push(VotingPowerResponse {
+                addr,
+                eth_addr,
+                power,
+            });
+        }
+
+        Ok(VotingPowersPage {
+            powers,
+            next,
+            total,
+        })
+    }
+
+    /// Returns the criteria tally of the campaign, `None` if the campaign doesn't exist. Once
+    /// the criteria are confirmed, the tally recorded at confirmation is returned.
+    pub fn get_criteria_tally_response<Accessor: StateAccessor>(
+        &self,
+        campaign_id: u64,
+        state: &mut Accessor,
+    ) -> Result<Option<TallyResponse>, <Accessor as StateReader<User>>::Error> {
+        let Some(campaign) = self.campaigns.get(&campaign_id, state)? else {
+            return Ok(None);
+        };
+        let (tally, closed) = match self.criteria_tallies.get(&campaign_id, state)? {
+            Some(tally) => (tally, true),
+            None => (self.criteria_tally(&campaign, state)?, false),
+        };
+        let votes = self.get_criteria_votes(campaign_id, state)?;
+        let approved = votes.iter().filter_map(|(delegate, vote)| match vote {
+            CriteriaVote::Approved { weights } => Some((delegate, weights)),
+            CriteriaVote::Rejected => None,
+        });
+        let weights = Self::criteria_weights(&campaign, approved);
+
+        self.tally_to_response(
+            &campaign,
+            tally,
+            closed,
+            weights,
+            |addr| votes.contains_key(addr),
+            state,
+        )
+        .map(Some)
+    }
+
+    /// Returns the distribution tally of the campaign, `None` if the campaign doesn't exist. Once
+    /// the distribution is confirmed, the tally recorded at confirmation is returned.
+    pub fn get_distribution_tally_response<Accessor: StateAccessor>(
+        &self,
+        campaign_id: u64,
+        state: &mut Accessor,
+    ) -> Result<Option<TallyResponse>, <Accessor as StateReader<User>>::Error> {
+        let Some(campaign) = self.campaigns.get(&campaign_id, state)? else {
+            return Ok(None);
+        };
+        let (tally, closed) = match self.distribution_tallies.get(&campaign_id, state)? {
+            Some(tally) => (tally, true),
+            None => (self.distribution_tally(&campaign, state)?, false),
+        };
+        let votes = self.get_distribution_votes(campaign_id, state)?;
+        let approved = votes.iter().filter_map(|(delegate, vote)| match vote {
+            DistributionVote::Approved { weights } => Some((delegate, weights)),
+            DistributionVote::Rejected => None,
+        });
+        let weights = Self::criteria_weights(&campaign, approved);
+
+        self.tally_to_response(
+            &campaign,
+            tally,
+            closed,
+            weights,
+            |addr| votes.contains_key(addr),
+            state,
+        )
+        .map(Some)
+    }
+
+    fn tally_to_response<Accessor: StateAccessor>(
+        &self,
+        campaign: &Campaign<S>,
+        tally: Tally,
+        closed: bool,
+        criteria_weights: Vec<u64>,
+        voted: impl Fn(&str) -> bool,
+        state: &mut Accessor,
+    ) -> Result<TallyResponse, <Accessor as StateReader<User>>::Error> {
+        let mut non_voters = vec![];
+        for (addr, power) in &campaign.delegates {
+            if voted(addr) {
+                continue;
+            }
+            let AddressResponse { addr, eth_addr } = self.address_str_response(addr, state)?;
+            non_voters.push(DelegateResponse {
+                addr,
+                eth_addr,
+                power: *power,
+            });
+        }
+        non_voters.sort_by(|a, b| a.addr.cmp(&b.addr));
+
+        Ok(TallyResponse {
+            approved: tally.approved,
+            rejected: tally.rejected,
+            total: tally.total,
+            turnout_bps: tally.participation_bps(),
+            quorum: tally.has_quorum(),
+            closed,
+            non_voters,
+            criteria_weights,
+        })
+    }
+
+    /// Aggregates the approved weights like [`rewards::delegate_rewards`] does. The aggregated
+    /// weights are at most [`rewards::WEIGHT_PRECISION`], so they fit in a `u64`.
+    fn criteria_weights<'a>(
+        campaign: &Campaign<S>,
+        approved: impl Iterator<Item = (&'a String, &'a Vec<u64>)>,
+    ) -> Vec<u64> {
+        let criteria_len = campaign.criteria.len();
+        let ballots = rewards::ballots(&campaign.delegates, approved, criteria_len);
+
+        rewards::aggregate_criteria(&ballots, criteria_len, None)
+            .into_iter()
+            .map(|weight| weight as u64)
+            .collect()
+    }
+
+    fn votes_to_response<V, Accessor: StateAccessor>(
+        &self,
+        votes: HashMap<String, V>,
+        state: &mut Accessor,
+    ) -> Result<Vec<VoteResponse<V>>, <Accessor as StateReader<User>>::Error> {
+        let mut responses = vec![];
+        for (addr, vote) in votes {
+            let AddressResponse { addr, eth_addr } = self.address_str_response(&addr, state)?;
+            responses.push(VoteResponse {
+                addr,
+                eth_addr,
+                vote,
+            });
+        }
+        responses.sort_by(|a, b| a.addr.cmp(&b.addr));
+
+        Ok(responses)
+    }
+}
+
+// Axum routes.
+impl<S: Spec> Core<S> {
+    async fn route_get_account_by_eth_addr(
+        state: ApiState<Self, S>,
+        Path(eth_addr): Path<String>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<Account> {
+        let mut accessor = Self::height_accessor(&state, height)?;
+        filament_hub_eth::parse_eth_address(&eth_addr)
+            .map_err(|e| errors::bad_request_400("malformed address", e))?;
+
+        let account = state
+            .get_account_by_eth_addr(&eth_addr, &mut accessor)
+            .unwrap_infallible()
+            .ok_or_else(|| errors::not_found_404("Account", eth_addr))?;
+        Ok(account.into())
+    }
+
+    #[allow(clippy::single_call_fn, clippy::unused_async)]
+    async fn route_get_campaign(
+        state: ApiState<Self, S>,
         Path(campaign_id): Path<u64>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
     ) -> ApiResult<CampaignResponse> {
+        let mut accessor = Self::height_accessor(&state, height)?;
         let campaign = state
-            .get_campaign(campaign_id, &mut state.api_state_accessor())
+            .get_campaign(campaign_id, &mut accessor)
             .unwrap_infallible()
             .ok_or_else(|| errors::not_found_404("Campaign", campaign_id))?;
 
-        state
-            .campaign_to_response(campaign, &mut state.api_state_accessor())
-            .map(Into::into)
-            .map_err(|err| {
-                errors::internal_server_error_response_500(format!(
-                    "failed to populate campaign response: {}",
-                    err
-                ))
-            })
+        Ok(state
+            .campaign_to_response(campaign, &mut accessor)
+            .unwrap_infallible()
+            .into())
     }
 
-    async fn route_get_campaigns(state: ApiState<Self, S>) -> ApiResult<Vec<CampaignResponse>> {
-        let mut campaigns = vec![];
-        for campaign in state
-            .get_campaigns(&mut state.api_state_accessor())
-            .unwrap_infallible()
-            .into_iter()
-        {
-            campaigns.push(
-                state
-                    .campaign_to_response(campaign, &mut state.api_state_accessor())
-                    .map_err(|err| {
-                        errors::internal_server_error_response_500(format!(
-                            "failed to populate campaign response: {}",
-                            err
-                        ))
-                    })?,
-            );
-        }
+    async fn route_get_campaigns(
+        state: ApiState<Self, S>,
+        query: Result<Query<CampaignsQuery<S>>, QueryRejection>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<CampaignsPage> {
+        let Query(query) = query.map_err(|e| errors::bad_request_400("malformed query", e))?;
+        let filter = CampaignFilter {
+            phase: query.phase,
+            campaigner: query.campaigner,
+            delegate: query.delegate,
+            indexer: query.indexer,
+        };
+        let mut accessor = Self::height_accessor(&state, height)?;
+        let page = state
+            .get_campaigns_page(
+                &filter,
+                query.sort,
+                query.order,
+                query.after,
+                query.limit.unwrap_or(DEFAULT_CAMPAIGNS_LIMIT),
+                &mut accessor,
+            )
+            .unwrap_infallible();
 
-        Ok(campaigns.into())
+        Ok(CampaignsPage {
+            campaigns: state
+                .campaigns_to_response(page.campaigns, &mut accessor)
+                .unwrap_infallible(),
+            next: page.next,
+        }
+        .into())
     }
 
     async fn route_get_campaigns_by_addr(
         state: ApiState<Self, S>,
-        Path(addr): Path<S::Address>,
+        Path(addr): Path<String>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
     ) -> ApiResult<Vec<CampaignResponse>> {
-        let mut campaigns = vec![];
-        for campaign in state
-            .get_campaigns_by_addr(addr, &mut state.api_state_accessor())
-            .unwrap_infallible()
-            .into_iter()
-        {
-            campaigns.push(
-                state
-                    .campaign_to_response(campaign, &mut state.api_state_accessor())
-                    .map_err(|err| {
-                        errors::internal_server_error_response_500(format!(
-                            "failed to populate campaign response: {}",
-                            err
-                        ))
-                    })?,
-            );
-        }
+        let addr = Self::hub_address(&addr)?;
+        let mut accessor = Self::height_accessor(&state, height)?;
+        let campaigns = state
+            .get_campaigns_by_addr(addr, &mut accessor)
+            .unwrap_infallible();
 
-        Ok(campaigns.into())
+        Ok(state
+            .campaigns_to_response(campaigns, &mut accessor)
+            .unwrap_infallible()
+            .into())
     }
 
     async fn route_get_campaigns_by_eth_addr(
         state: ApiState<Self, S>,
         Path(eth_addr): Path<String>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
     ) -> ApiResult<Vec<CampaignResponse>> {
-        let addr = filament_hub_eth::addr_to_hub_address::<S>(&eth_addr)
-            .map_err(|e| errors::bad_request_400("malformed address", e))?;
+        let mut accessor = Self::height_accessor(&state, height)?;
+        let addr = Self::eth_hub_address(&state, &eth_addr, &mut accessor)?;
+        let campaigns = state
+            .get_campaigns_by_addr(addr, &mut accessor)
+            .unwrap_infallible();
 
-        let mut campaigns = vec![];
-        for campaign in state
-            .get_campaigns_by_addr(addr, &mut state.api_state_accessor())
+        Ok(state
+            .campaigns_to_response(campaigns, &mut accessor)
             .unwrap_infallible()
-            .into_iter()
-        {
-            campaigns.push(
-                state
-                    .campaign_to_response(campaign, &mut state.api_state_accessor())
-                    .map_err(|err| {
-                        errors::internal_server_error_response_500(format!(
-                            "failed to populate campaign response: {}",
-                            err
-                        ))
-                    })?,
-            );
-        }
+            .into())
+    }
 
-        Ok(campaigns.into())
+    async fn route_get_total_voting_power(
+        state: ApiState<Self, S>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<Power> {
+        let mut accessor = Self::height_accessor(&state, height)?;
+        Ok(state
+            .get_total_voting_power(&mut accessor)
+            .unwrap_infallible()
+            .into())
     }
 
     async fn route_get_criteria_votes(
         state: ApiState<Self, S>,
         Path(campaign_id): Path<u64>,
-    ) -> ApiResult<HashMap<String, CriteriaVote>> {
-        let hub_votes = state
-            .get_criteria_votes(campaign_id, &mut state.api_state_accessor())
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<Vec<VoteResponse<CriteriaVote>>> {
+        let mut accessor = Self::height_accessor(&state, height)?;
+        Ok(state
+            .get_criteria_votes_response(campaign_id, &mut accessor)
+            .unwrap_infallible()
+            .ok_or_else(|| errors::not_found_404("Campaign", campaign_id))?
+            .into())
+    }
+
+    async fn route_get_distribution_votes(
+        state: ApiState<Self, S>,
+        Path(campaign_id): Path<u64>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<Vec<VoteResponse<DistributionVote>>> {
+        let mut accessor = Self::height_accessor(&state, height)?;
+        Ok(state
+            .get_distribution_votes_response(campaign_id, &mut accessor)
+            .unwrap_infallible()
+            .ok_or_else(|| errors::not_found_404("Campaign", campaign_id))?
+            .into())
+    }
+
+    async fn route_get_criteria_tally(
+        state: ApiState<Self, S>,
+        Path(campaign_id): Path<u64>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<TallyResponse> {
+        let mut accessor = Self::height_accessor(&state, height)?;
+        Ok(state
+            .get_criteria_tally_response(campaign_id, &mut accessor)
+            .unwrap_infallible()
+            .ok_or_else(|| errors::not_found_404("Campaign", campaign_id))?
+            .into())
+    }
+
+    async fn route_get_distribution_tally(
+        state: ApiState<Self, S>,
+        Path(campaign_id): Path<u64>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<TallyResponse> {
+        let mut accessor = Self::height_accessor(&state, height)?;
+        Ok(state
+            .get_distribution_tally_response(campaign_id, &mut accessor)
+            .unwrap_infallible()
+            .ok_or_else(|| errors::not_found_404("Campaign", campaign_id))?
+            .into())
+    }
+
+    async fn route_get_criteria_proposals(
+        state: ApiState<Self, S>,
+        Path(campaign_id): Path<u64>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<Vec<ProposalResponse>> {
+        let mut accessor = Self::height_accessor(&state, height)?;
+        Ok(state
+            .get_criteria_proposals_response(campaign_id, &mut accessor)
+            .unwrap_infallible()
+            .ok_or_else(|| errors::not_found_404("Campaign", campaign_id))?
+            .into())
+    }
+
+    async fn route_get_criteria_proposal(
+        state: ApiState<Self, S>,
+        Path((campaign_id, proposal_id)): Path<(u64, u64)>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<ProposalResponse> {
+        let mut accessor = Self::height_accessor(&state, height)?;
+        Ok(state
+            .get_criteria_proposals_response(campaign_id, &mut accessor)
+            .unwrap_infallible()
+            .ok_or_else(|| errors::not_found_404("Campaign", campaign_id))?
+            .into_iter()
+            .find(|proposal| proposal.id == proposal_id)
+            .ok_or_else(|| errors::not_found_404("Criteria proposal", proposal_id))?
+            .into())
+    }
+
+    async fn route_get_segment(
+        state: ApiState<Self, S>,
+        Path(campaign_id): Path<u64>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<Segment> {
+        let mut accessor = Self::height_accessor(&state, height)?;
+        Ok(state
+            .get_segment(campaign_id, &mut accessor)
+            .unwrap_infallible()
+            .ok_or_else(|| errors::not_found_404("Segment", campaign_id))?
+            .into())
+    }
+
+    async fn route_get_indexers(
+        state: ApiState<Self, S>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<Vec<IndexerResponse>> {
+        let mut accessor = Self::height_accessor(&state, height)?;
+        Ok(state
+            .get_indexers_response(&mut accessor)
+            .unwrap_infallible()
+            .into())
+    }
+
+    async fn route_get_indexer(
+        state: ApiState<Self, S>,
+        Path(addr): Path<String>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<IndexerResponse> {
+        let addr = Self::hub_address(&addr)?;
+        let mut accessor = Self::height_accessor(&state, height)?;
+        let Indexer { addr, alias } = state
+            .get_indexer(addr.clone(), &mut accessor)
+            .unwrap_infallible()
+            .ok_or_else(|| errors::not_found_404("Indexer", addr))?;
+        let AddressResponse { addr, eth_addr } = state
+            .address_response(&addr, &mut accessor)
             .unwrap_infallible();
 
-        let mut votes = HashMap::new();
-        for (addr, vote) in hub_votes.into_iter() {
-            votes.insert(
-                state
-                    .get_eth_address(addr, &mut state.api_state_accessor())
-                    .unwrap()
-                    .unwrap(),
-                vote,
-            );
+        Ok(IndexerResponse {
+            addr,
+            eth_addr,
+            alias,
         }
+        .into())
+    }
 
-        Ok(votes.into())
+    async fn route_get_relayers(
+        state: ApiState<Self, S>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<Vec<AddressResponse>> {
+        let mut accessor = Self::height_accessor(&state, height)?;
+        Ok(state
+            .get_relayers_response(&mut accessor)
+            .unwrap_infallible()
+            .into())
     }
 
-    async fn route_get_distribution_votes(
+    async fn route_get_delegates(
         state: ApiState<Self, S>,
-        Path(campaign_id): Path<u64>,
-    ) -> ApiResult<HashMap<String, DistributionVote>> {
-        let hub_votes = state
-            .get_distribution_votes(campaign_id, &mut state.api_state_accessor())
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<Vec<DelegateResponse>> {
+        let mut accessor = Self::height_accessor(&state, height)?;
+        Ok(state
+            .get_delegates_response(&mut accessor)
+            .unwrap_infallible()
+            .into())
+    }
+
+    async fn route_get_delegate_campaigns(
+        state: ApiState<Self, S>,
+        Path(addr): Path<String>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<Vec<CampaignResponse>> {
+        let addr = Self::hub_address(&addr)?;
+        let mut accessor = Self::height_accessor(&state, height)?;
+        let campaigns = state
+            .get_campaigns_by_delegate(addr, &mut accessor)
             .unwrap_infallible();
 
-        let mut votes = HashMap::new();
-        for (addr, vote) in hub_votes.into_iter() {
-            votes.insert(
-                state
-                    .get_eth_address(addr, &mut state.api_state_accessor())
-                    .unwrap()
-                    .unwrap(),
-                vote,
-            );
+        Ok(state
+            .campaigns_to_response(campaigns, &mut accessor)
+            .unwrap_infallible()
+            .into())
+    }
+
+    async fn route_get_delegate_campaigns_by_eth_addr(
+        state: ApiState<Self, S>,
+        Path(eth_addr): Path<String>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<Vec<CampaignResponse>> {
+        let mut accessor = Self::height_accessor(&state, height)?;
+        let addr = Self::eth_hub_address(&state, &eth_addr, &mut accessor)?;
+        let campaigns = state
+            .get_campaigns_by_delegate(addr, &mut accessor)
+            .unwrap_infallible();
+
+        Ok(state
+            .campaigns_to_response(campaigns, &mut accessor)
+            .unwrap_infallible()
+            .into())
+    }
+
+    async fn route_get_pending_actions(
+        state: ApiState<Self, S>,
+        Path(addr): Path<String>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<Vec<PendingAction>> {
+        let addr = Self::hub_address(&addr)?;
+        let mut accessor = Self::height_accessor(&state, height)?;
+        Ok(state
+            .get_pending_actions(addr, &mut accessor)
+            .unwrap_infallible()
+            .into())
+    }
+
+    async fn route_get_pending_actions_by_eth_addr(
+        state: ApiState<Self, S>,
+        Path(eth_addr): Path<String>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<Vec<PendingAction>> {
+        let mut accessor = Self::height_accessor(&state, height)?;
+        let addr = Self::eth_hub_address(&state, &eth_addr, &mut accessor)?;
+
+        Ok(state
+            .get_pending_actions(addr, &mut accessor)
+            .unwrap_infallible()
+            .into())
+    }
+
+    async fn route_get_voting_powers(
+        state: ApiState<Self, S>,
+        query: Result<Query<VotingPowersQuery<S>>, QueryRejection>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<VotingPowersPage> {
+        let mut accessor = Self::height_accessor(&state, height)?;
+        let Query(query) = query.map_err(|e| errors::bad_request_400("malformed query", e))?;
+
+        Ok(state
+            .get_voting_powers_response(
+                query.after,
+                query.limit.unwrap_or(DEFAULT_POWERS_LIMIT),
+                &mut accessor,
+            )
+            .unwrap_infallible()
+            .into())
+    }
+
+    async fn route_get_voting_power(
+        state: ApiState<Self, S>,
+        Path(addr): Path<String>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<Power> {
+        let addr = Self::hub_address(&addr)?;
+        let mut accessor = Self::height_accessor(&state, height)?;
+        Ok(state
+            .get_voting_power(addr, &mut accessor)
+            .unwrap_infallible()
+            .into())
+    }
+
+    async fn route_get_stats(
+        state: ApiState<Self, S>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<StatsResponse> {
+        let mut accessor = Self::height_accessor(&state, height)?;
+        Ok(state.get_stats(&mut accessor).unwrap_infallible().into())
+    }
+
+    async fn route_get_admin(
+        state: ApiState<Self, S>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> ApiResult<AddressResponse> {
+        let mut accessor = Self::height_accessor(&state, height)?;
+        let admin = state
+            .get_admin(&mut accessor)
+            .unwrap_infallible()
+            .ok_or_else(|| errors::not_found_404("Admin", "core"))?;
+
+        Ok(state
+            .address_response(&admin, &mut accessor)
+            .unwrap_infallible()
+            .into())
+    }
+
+    /// Accessor of the state at the `?height=` of the request, the latest state if not set.
+    fn height_accessor(
+        state: &ApiState<Self, S>,
+        height: Result<Query<HeightQuery>, QueryRejection>,
+    ) -> Result<ApiStateAccessor<S>, ErrorObject> {
+        let Query(HeightQuery { height }) =
+            height.map_err(|e| errors::bad_request_400("malformed query", e))?;
+        let mut accessor = state.api_state_accessor();
+
+        match height {
+            None => Ok(accessor),
+            Some(height) => state
+                .archival_state(height, &mut accessor)
+                .map_err(|e| errors::bad_request_400("unavailable height", e)),
         }
+    }
+
+    /// Hub address in the path of a request.
+    fn hub_address(addr: &str) -> Result<S::Address, ErrorObject> {
+        addr.parse::<S::Address>()
+            .map_err(|_| errors::bad_request_400("malformed address", addr))
+    }
+
+    /// Hub address the eth address of a request acts as, preferring the hub account linked to
+    /// it over the derived one.
+    fn eth_hub_address(
+        state: &ApiState<Self, S>,
+        eth_addr: &str,
+        accessor: &mut ApiStateAccessor<S>,
+    ) -> Result<S::Address, ErrorObject> {
+        filament_hub_eth::parse_eth_address(eth_addr)
+            .map_err(|e| errors::bad_request_400("malformed address", e))?;
 
-        Ok(votes.into())
+        state
+            .get_hub_address_by_eth_addr(eth_addr, accessor)
+            .unwrap_infallible()
+            .ok_or_else(|| errors::bad_request_400("malformed address", eth_addr))
     }
 }
 
@@ -511,23 +2328,71 @@ impl<S: Spec> HasCustomRestApi for Core<S> {
                 get(Self::route_get_account_by_eth_addr),
             )
             .route(
-                "/campaigns/by_addr/:addr}",
+                "/campaigns/by_addr/:addr",
                 get(Self::route_get_campaigns_by_addr),
             )
             .route(
-                "/campaigns/by_eth_addr/:eth_addr}",
+                "/campaigns/by_eth_addr/:eth_addr",
                 get(Self::route_get_campaigns_by_eth_addr),
             )
             .route(
                 "/campaigns/:campaignId/criteria/votes",
                 get(Self::route_get_criteria_votes),
             )
+            .route(
+                "/campaigns/:campaignId/criteria/tally",
+                get(Self::route_get_criteria_tally),
+            )
             .route(
                 "/campaigns/:campaignId/distribution/votes",
                 get(Self::route_get_distribution_votes),
             )
+            .route(
+                "/campaigns/:campaignId/distribution/tally",
+                get(Self::route_get_distribution_tally),
+            )
+            .route(
+                "/campaigns/:campaignId/proposals/:proposalId",
+                get(Self::route_get_criteria_proposal),
+            )
+            .route(
+                "/campaigns/:campaignId/proposals",
+                get(Self::route_get_criteria_proposals),
+            )
+            .route(
+                "/campaigns/:campaignId/segment",
+                get(Self::route_get_segment),
+            )
             .route("/campaigns/:campaignId", get(Self::route_get_campaign))
             .route("/campaigns", get(Self::route_get_campaigns))
+            .route("/indexers/:addr", get(Self::route_get_indexer))
+            .route("/indexers", get(Self::route_get_indexers))
+            .route("/relayers", get(Self::route_get_relayers))
+            .route(
+                "/delegates/by_eth_addr/:eth_addr/campaigns",
+                get(Self::route_get_delegate_campaigns_by_eth_addr),
+            )
+            .route(
+                "/delegates/by_eth_addr/:eth_addr/pending_actions",
+                get(Self::route_get_pending_actions_by_eth_addr),
+            )
+            .route(
+                "/delegates/:addr/campaigns",
+                get(Self::route_get_delegate_campaigns),
+            )
+            .route(
+                "/delegates/:addr/pending_actions",
+                get(Self::route_get_pending_actions),
+            )
+            .route("/delegates", get(Self::route_get_delegates))
+            .route("/voting_powers/:addr", get(Self::route_get_voting_power))
+            .route("/voting_powers", get(Self::route_get_voting_powers))
+            .route(
+                "/total_voting_power",
+                get(Self::route_get_total_voting_power),
+            )
+            .route("/stats", get(Self::route_get_stats))
+            .route("/admin", get(Self::route_get_admin))
             .layer(cors)
             .with_state(state)
     }
@@ -0,0 +1,257 @@
+//! Delegate rewards based on the marginal contribution of their criteria weights to the campaign
+//! objective `V_G`, following the VCG mechanism outlined in `docs/spec/context/vcg_rewards.md`.
+//!
+//! All computations use integer arithmetic and iterate delegates in address order, so every node
+//! arrives at the same rewards for the same inputs.
+use std::collections::{BTreeMap, HashMap};
+
+use sov_mock_zkvm::MockZkVerifier;
+use sov_modules_api::{default_spec::DefaultSpec, execution_mode::Zk, Spec};
+
+use crate::{voting::CriteriaVote, Power, Relayer};
+
+/// Fixed-point precision used for normalised criteria weights.
+pub const WEIGHT_PRECISION: u128 = 1_000_000;
+
+/// Post-campaign measurement of the campaign objective, posted by the relayers. The objective
+/// `V_G` is estimated from the attributions and the aggregated criteria weights.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    derive(sov_modules_api::macros::UniversalWallet),
+    schemars(rename = "ObjectiveMeasurement")
+)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    ts_rs::TS,
+)]
+#[serde(rename_all = "snake_case")]
+#[ts(export_to = "../../../../bindings/ObjectiveMeasurement.ts")]
+pub struct ObjectiveMeasurement {
+    /// Share of the objective attributed to each criterion, in the order of the campaign
+    /// criteria.
+    pub attributions: Vec<u64>,
+}
+
+/// Measurement posted by relayers that has not reached the attestation threshold yet.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(bound = "S: ::sov_modules_api::Spec", rename = "ObjectiveSubmission")
+)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    ts_rs::TS,
+)]
+#[serde(bound = "S::Address: serde::Serialize + serde::de::DeserializeOwned")]
+#[ts(export, concrete(S = DefaultSpec<MockZkVerifier, MockZkVerifier, Zk>))]
+#[ts(export_to = "../../../../bindings/ObjectiveSubmission.ts")]
+pub struct ObjectiveSubmission<S: Spec> {
+    pub measurement: ObjectiveMeasurement,
+    #[ts(type = "Array<string>")]
+    pub relayers: Vec<Relayer<S>>,
+}
+
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(rename = "DelegateReward")
+)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    ts_rs::TS,
+)]
+#[serde(rename_all = "snake_case")]
+#[ts(export_to = "../../../../bindings/DelegateReward.ts")]
+pub struct DelegateReward {
+    pub delegate: String,
+    /// Positive part of `V_G - V_G^{-d}`, scaled by [`WEIGHT_PRECISION`].
+    pub contribution: u128,
+    pub reward: u64,
+}
+
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(bound = "S: ::sov_modules_api::Spec", rename = "RewardPool")
+)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    ts_rs::TS,
+)]
+#[serde(bound = "S::Address: serde::Serialize + serde::de::DeserializeOwned")]
+#[ts(export, concrete(S = DefaultSpec<MockZkVerifier, MockZkVerifier, Zk>))]
+#[ts(export_to = "../../../../bindings/RewardPool.ts")]
+pub struct RewardPool<S: Spec> {
+    #[ts(type = "string")]
+    pub funder: S::Address,
+    pub amount: u64,
+}
+
+/// Computes the rewards for every delegate of a campaign. Only approved votes with one weight per
+/// criterion take part, weighted by the voting power the delegate had in the campaign.
+pub fn delegate_rewards(
+    delegates: &HashMap<String, Power>,
+    votes: &HashMap<String, CriteriaVote>,
+    measurement: &ObjectiveMeasurement,
+    pool: u64,
+) -> Vec<DelegateReward> {
+    let criteria_len = measurement.attributions.len();
+    let ballots = ballots(
+        delegates,
+        votes.iter().filter_map(|(delegate, vote)| match vote {
+            CriteriaVote::Approved { weights } => Some((delegate, weights)),
+            CriteriaVote::Rejected => None,
+        }),
+        criteria_len,
+    );
+
+    let total = objective(
+        &aggregate_criteria(&ballots, criteria_len, None),
+        &measurement.attributions,
+    );
+    let contributions = ballots
+        .keys()
+        .map(|delegate| {
+            let without = objective(
+                &aggregate_criteria(&ballots, criteria_len, Some(delegate)),
+                &measurement.attributions,
+            );
+            (delegate.clone(), total.saturating_sub(without))
+        })
+        .collect::<BTreeMap<_, _>>();
+    let total_contribution = contributions
+        .values()
+        .fold(0u128, |acc, c| acc.saturating_add(*c));
+
+    let mut addresses = delegates.keys().cloned().collect::<Vec<_>>();
+    addresses.sort();
+
+    addresses
+        .into_iter()
+        .map(|delegate| {
+            let contribution = contributions.get(&delegate).copied().unwrap_or_default();
+            let reward = pro_rata(pool, contribution, total_contribution);
+
+            DelegateReward {
+                delegate,
+                contribution,
+                reward,
+            }
+        })
+        .collect()
+}
+
+/// Collects the approved weights of the delegates with the voting power each delegate had in
+/// the campaign. Only ballots with one weight per criterion and a non-zero weight count.
+pub fn ballots<'a>(
+    delegates: &HashMap<String, Power>,
+    approved: impl Iterator<Item = (&'a String, &'a Vec<u64>)>,
+    criteria_len: usize,
+) -> BTreeMap<String, (Power, Vec<u64>)> {
+    approved
+        .filter(|(_, weights)| weights.len() == criteria_len && weights.iter().any(|w| *w > 0))
+        .map(|(delegate, weights)| {
+            let power = delegates.get(delegate).copied().unwrap_or_default();
+            (delegate.clone(), (power, weights.clone()))
+        })
+        .collect()
+}
+
+/// Share `contribution / total` of the pool, rounded down. Both sides are scaled down until the
+/// product fits in a `u128`, dropping only low bits of the contributions.
+fn pro_rata(pool: u64, contribution: u128, total: u128) -> u64 {
+    if total == 0 {
+        return 0;
+    }
+
+    let shift = (u128::BITS - total.leading_zeros()).saturating_sub(u64::BITS);
+    let (contribution, total) = (contribution >> shift, total >> shift);
+    let reward = u128::from(pool)
+        .checked_mul(contribution)
+        .map_or(u128::from(pool), |scaled| scaled / total);
+
+    u64::try_from(reward).map_or(pool, |reward| reward.min(pool))
+}
+
+/// Aggregates the criteria weights of all ballots, weighted by voting power. Each ballot is
+/// normalised to [`WEIGHT_PRECISION`] first, so only the relative weights of a delegate matter.
+pub fn aggregate_criteria(
+    ballots: &BTreeMap<String, (Power, Vec<u64>)>,
+    criteria_len: usize,
+    exclude: Option<&String>,
+) -> Vec<u128> {
+    let mut aggregated = vec![0u128; criteria_len];
+    let mut total_power = 0u128;
+
+    for (delegate, (power, weights)) in ballots {
+        if exclude == Some(delegate) {
+            continue;
+        }
+
+        let power = u128::from(*power);
+        let sum = weights.iter().map(|w| u128::from(*w)).sum::<u128>();
+        if sum == 0 {
+            continue;
+        }
+
+        for (acc, weight) in aggregated.iter_mut().zip(weights) {
+            *acc += power * (u128::from(*weight) * WEIGHT_PRECISION / sum);
+        }
+        total_power += power;
+    }
+
+    if total_power == 0 {
+        return vec![0; criteria_len];
+    }
+
+    aggregated
+        .into_iter()
+        .map(|weight| weight / total_power)
+        .collect()
+}
+
+/// Estimates the objective for the given aggregated criteria as the attribution of each
+/// criterion weighted by its share, scaled by [`WEIGHT_PRECISION`].
+pub fn objective(criteria: &[u128], attributions: &[u64]) -> u128 {
+    let total_weight = criteria.iter().sum::<u128>();
+    if total_weight == 0 {
+        return 0;
+    }
+
+    criteria
+        .iter()
+        .zip(attributions)
+        .fold(0u128, |acc, (weight, attribution)| {
+            acc.saturating_add(weight * u128::from(*attribution) * WEIGHT_PRECISION)
+        })
+        / total_weight
+}
@@ -0,0 +1,143 @@
+use sov_mock_zkvm::MockZkVerifier;
+use sov_modules_api::{default_spec::DefaultSpec, execution_mode::Zk, Spec, StateReader};
+use sov_state::User;
+
+use crate::{CallMessage, Core};
+
+/// Calls a session key can be allowed to sign: the campaign and vote calls delegates and
+/// campaigners repeat during a campaign. Calls managing an account, its keys or the hub itself
+/// can only be signed by the account's own key.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    derive(sov_modules_api::macros::UniversalWallet),
+    schemars(rename = "SessionCall")
+)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    ts_rs::TS,
+)]
+#[serde(rename_all = "snake_case")]
+#[ts(export_to = "../../../../bindings/SessionCall.ts")]
+pub enum SessionCall {
+    Draft,
+    Init,
+    ProposeCriteria,
+    VoteCriteria,
+    ConfirmCriteria,
+    RejectCriteria,
+    VoteDistribution,
+    ConfirmDistribution,
+}
+
+impl SessionCall {
+    /// Session call matching `call`, if it may be signed by a session key.
+    pub fn of<S: Spec>(call: &CallMessage<S>) -> Option<Self> {
+        match call {
+            CallMessage::Draft { .. } => Some(Self::Draft),
+            CallMessage::Init { .. } => Some(Self::Init),
+            CallMessage::ProposeCriteria { .. } => Some(Self::ProposeCriteria),
+            CallMessage::VoteCriteria { .. } => Some(Self::VoteCriteria),
+            CallMessage::ConfirmCriteria { .. } => Some(Self::ConfirmCriteria),
+            CallMessage::RejectCriteria { .. } => Some(Self::RejectCriteria),
+            CallMessage::VoteDistribution { .. } => Some(Self::VoteDistribution),
+            CallMessage::ConfirmDistribution { .. } => Some(Self::ConfirmDistribution),
+            _ => None,
+        }
+    }
+
+    /// Name of the call as it is serialized, e.g. `vote_criteria`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Draft => "draft",
+            Self::Init => "init",
+            Self::ProposeCriteria => "propose_criteria",
+            Self::VoteCriteria => "vote_criteria",
+            Self::ConfirmCriteria => "confirm_criteria",
+            Self::RejectCriteria => "reject_criteria",
+            Self::VoteDistribution => "vote_distribution",
+            Self::ConfirmDistribution => "confirm_distribution",
+        }
+    }
+}
+
+/// Key allowed to sign a subset of calls on behalf of the hub address that granted it.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(bound = "S: ::sov_modules_api::Spec", rename = "SessionKey")
+)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    ts_rs::TS,
+)]
+#[serde(bound = "S::Address: serde::Serialize + serde::de::DeserializeOwned")]
+#[ts(export, concrete(S = DefaultSpec<MockZkVerifier, MockZkVerifier, Zk>))]
+#[ts(export_to = "../../../../bindings/SessionKey.ts")]
+pub struct SessionKey<S: Spec> {
+    #[ts(type = "string")]
+    pub owner: S::Address,
+    /// Calls the key may sign.
+    pub allowed_calls: Vec<SessionCall>,
+    /// First slot at which the key can no longer be used.
+    pub expires_at_slot: u64,
+}
+
+impl<S: Spec> Core<S> {
+    /// Owner of the session key with the eth address `key`, used to authenticate its
+    /// transactions. Calls the key isn't allowed to sign and keys expired by the last slot the
+    /// module executed in are rejected; the expiry is checked again against the exact slot when
+    /// the call is executed.
+    pub fn session_owner<Reader: StateReader<User>>(
+        &self,
+        key: &[u8; 20],
+        call: &CallMessage<S>,
+        state: &mut Reader,
+    ) -> anyhow::Result<Option<S::Address>> {
+        let key = format!("0x{}", hex::encode(key));
+        let Some(session_key) = self
+            .session_keys
+            .get(&key, state)
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?
+        else {
+            return Ok(None);
+        };
+
+        let slot = self
+            .last_slot
+            .get(state)
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?
+            .unwrap_or_default();
+        if slot >= session_key.expires_at_slot {
+            anyhow::bail!(
+                "session key '{}' expired at slot {}",
+                key,
+                session_key.expires_at_slot
+            );
+        }
+        if !SessionCall::of(call).is_some_and(|call| session_key.allowed_calls.contains(&call)) {
+            anyhow::bail!(
+                "call '{}' is not allowed for session key '{}'",
+                call.name(),
+                key
+            );
+        }
+
+        Ok(Some(session_key.owner))
+    }
+}
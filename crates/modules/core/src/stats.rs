@@ -0,0 +1,76 @@
+//! Aggregate protocol metrics, updated as campaigns progress so reading them doesn't scan
+//! campaigns. Counts of indexers, relayers and delegates are the lengths of their lists.
+use sov_modules_api::{Spec, StateAccessor, StateReader};
+use sov_state::User;
+
+use crate::{campaign::Phase, Core};
+
+/// Running totals of campaign budgets, rewards and turnout.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(rename = "Stats")
+)]
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    ts_rs::TS,
+)]
+#[ts(export_to = "../../../../bindings/Stats.ts")]
+pub struct Stats {
+    /// Funds held in reward pools whose rewards are not distributed yet.
+    pub escrowed_budget: u64,
+    /// Number of campaigns whose delegate rewards were distributed.
+    pub distributions: u64,
+    /// Sum of the delegate rewards distributed.
+    pub distributed_rewards: u64,
+    /// Number of criteria tallies recorded.
+    pub tallies: u64,
+    /// Sum of the participation of the recorded tallies, in basis points.
+    pub turnout_bps_sum: u64,
+}
+
+impl Stats {
+    /// Average participation of the recorded tallies, in basis points.
+    pub fn average_turnout_bps(&self) -> u64 {
+        self.turnout_bps_sum
+            .checked_div(self.tallies)
+            .unwrap_or_default()
+    }
+}
+
+impl<S: Spec> Core<S> {
+    /// Counts a campaign entering `phase`, leaving the phase `from` if it had one.
+    pub(crate) fn count_campaign<Accessor: StateAccessor>(
+        &self,
+        from: Option<&Phase>,
+        phase: &Phase,
+        state: &mut Accessor,
+    ) -> Result<(), <Accessor as StateReader<User>>::Error> {
+        if let Some(from) = from {
+            let count = self.campaign_counts.get(from, state)?.unwrap_or_default();
+            self.campaign_counts
+                .set(from, &count.saturating_sub(1), state)?;
+        }
+        let count = self.campaign_counts.get(phase, state)?.unwrap_or_default();
+        self.campaign_counts
+            .set(phase, &count.saturating_add(1), state)
+    }
+
+    pub(crate) fn update_stats<Accessor: StateAccessor>(
+        &self,
+        update: impl FnOnce(&mut Stats),
+        state: &mut Accessor,
+    ) -> Result<(), <Accessor as StateReader<User>>::Error> {
+        let mut stats = self.stats.get(state)?.unwrap_or_default();
+        update(&mut stats);
+        self.stats.set(&stats, state)
+    }
+}
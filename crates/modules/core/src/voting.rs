@@ -1,5 +1,11 @@
+use std::collections::HashMap;
+
 pub type Power = u64;
 
+/// Share of the total voting power, in basis points, that has to vote for a tally to reach
+/// quorum.
+pub const QUORUM_BPS: u64 = 5_000;
+
 #[cfg_attr(
     feature = "native",
     derive(schemars::JsonSchema),
@@ -41,3 +47,122 @@ pub enum DistributionVote {
     Approved { weights: Vec<u64> },
     Rejected,
 }
+
+/// Voting power of an address from `slot` onwards, until superseded by a later checkpoint.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(rename = "PowerCheckpoint")
+)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    ts_rs::TS,
+)]
+#[ts(export_to = "../../../../bindings/PowerCheckpoint.ts")]
+pub struct PowerCheckpoint {
+    pub slot: u64,
+    pub power: Power,
+}
+
+/// Voting power behind the votes of a campaign, measured against the total voting power at the
+/// campaign snapshot.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(rename = "Tally")
+)]
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    ts_rs::TS,
+)]
+#[ts(export_to = "../../../../bindings/Tally.ts")]
+pub struct Tally {
+    pub approved: Power,
+    pub rejected: Power,
+    /// Quorum denominator, the total voting power of all stakers.
+    pub total: Power,
+}
+
+impl Tally {
+    /// Share of the total voting power that voted, in basis points.
+    pub fn participation_bps(&self) -> u64 {
+        if self.total == 0 {
+            return 0;
+        }
+
+        ((u128::from(self.approved) + u128::from(self.rejected)) * 10_000 / u128::from(self.total))
+            as u64
+    }
+
+    /// Whether at least [`QUORUM_BPS`] of the total voting power voted.
+    pub fn has_quorum(&self) -> bool {
+        self.total > 0 && self.participation_bps() >= QUORUM_BPS
+    }
+}
+
+/// Tallies criteria votes by the powers of the campaign delegates.
+pub fn tally_criteria(
+    delegates: &HashMap<String, Power>,
+    votes: &HashMap<String, CriteriaVote>,
+    total: Power,
+) -> Tally {
+    tally(
+        delegates,
+        votes
+            .iter()
+            .map(|(voter, vote)| (voter, matches!(vote, CriteriaVote::Approved { .. }))),
+        total,
+    )
+}
+
+/// Tallies distribution votes by the powers of the campaign delegates.
+pub fn tally_distribution(
+    delegates: &HashMap<String, Power>,
+    votes: &HashMap<String, DistributionVote>,
+    total: Power,
+) -> Tally {
+    tally(
+        delegates,
+        votes
+            .iter()
+            .map(|(voter, vote)| (voter, matches!(vote, DistributionVote::Approved { .. }))),
+        total,
+    )
+}
+
+fn tally<'a>(
+    delegates: &HashMap<String, Power>,
+    votes: impl Iterator<Item = (&'a String, bool)>,
+    total: Power,
+) -> Tally {
+    let mut tally = Tally {
+        total,
+        ..Default::default()
+    };
+
+    for (voter, approved) in votes {
+        let power = delegates.get(voter).copied().unwrap_or_default();
+        if approved {
+            tally.approved = tally.approved.saturating_add(power);
+        } else {
+            tally.rejected = tally.rejected.saturating_add(power);
+        }
+    }
+
+    tally
+}
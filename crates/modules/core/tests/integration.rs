@@ -1,32 +1,55 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
     time::{SystemTime, UNIX_EPOCH},
 };
 
 use anyhow::anyhow;
 use filament_hub_core::{
+    attestation::{Attestation, AttestationConflict},
     campaign::{Campaign, Phase},
+    campaign_index::{CampaignFilter, CampaignSort, SortOrder},
     criteria::{Criteria, CriteriaProposal, Criterion, CriterionCategory},
     crypto::Ed25519Signature,
     delegate::Delegate,
+    light_client::{EthHeader, LightClientConfig},
+    multisig::Multisig,
+    rewards::{DelegateReward, ObjectiveMeasurement, RewardPool},
     segment::{SegmentData, SegmentProof},
-    voting::{CriteriaVote, DistributionVote},
+    session::{SessionCall, SessionKey},
+    voting::{CriteriaVote, DistributionVote, PowerCheckpoint, Tally},
+    AddressResponse,
     CallMessage,
     Core,
     CoreConfig,
+    DelegateAction,
+    DelegateResponse,
     Event,
+    EventFilter,
     Indexer,
+    PendingAction,
+    PhaseCount,
+    ProposalResponse,
     Segment,
+    StatsResponse,
+    TallyResponse,
+    VoteResponse,
+    VotingPowerResponse,
 };
+use filament_hub_eth::proof::{self, rlp};
+use k256::ecdsa::SigningKey;
 use lazy_static::lazy_static;
 use pretty_assertions::assert_eq;
 use sov_bank::{get_token_id, TokenId};
 use sov_modules_api::{
     prelude::UnwrapInfallible,
     test_utils::generate_address,
+    transaction::{PriorityFeeBips, TxDetails},
     Error,
     GasUnit,
+    ModuleInfo as _,
     Spec,
+    StateAccessor,
     TxEffect,
 };
 use sov_modules_stf_blueprint::RevertedTxContents;
@@ -51,6 +74,9 @@ lazy_static! {
 
 type S = TestSpec;
 
+const VAULT_ADDRESS: [u8; 20] = [0xaa; 20];
+const VAULT_BALANCES_SLOT: u64 = 1;
+
 generate_optimistic_runtime!(TestCoreRuntime <= core: Core<S>);
 
 struct TestRoles<S: Spec> {
@@ -120,6 +146,9 @@ fn draft_campaign() {
                 let mut campaign = generate_test_campaign(campaigner.address());
                 campaign.id = 2;
                 campaign.delegates = delegates;
+                // Genesis is slot 0 and every transaction runs in a slot of its own, so the
+                // draft after the rejected one is in slot 2.
+                campaign.snapshot_slot = 2;
                 campaign
             };
             assert_eq!(
@@ -128,11 +157,22 @@ fn draft_campaign() {
                     .unwrap_infallible(),
                 Some(expected.clone())
             );
+            // The campaigner also owns both campaigns created at genesis.
             assert_eq!(
                 Core::<S>::default()
                     .get_campaigns_by_addr(campaigner.address(), state)
-                    .unwrap_infallible(),
-                vec![expected]
+                    .unwrap_infallible()
+                    .last(),
+                Some(&expected)
+            );
+            assert_eq!(
+                Core::<S>::default()
+                    .get_campaigns_by_addr(campaigner.address(), state)
+                    .unwrap_infallible()
+                    .into_iter()
+                    .map(|campaign| campaign.id)
+                    .collect::<Vec<_>>(),
+                vec![0, 1, 2]
             );
         }),
     });
@@ -351,12 +391,31 @@ fn confirm_criteria() {
         TestRoles {
             campaign,
             campaigner,
+            delegate_users,
             staker,
             ..
         },
         mut runner,
     ) = setup();
 
+    for (delegate, vote) in [
+        (
+            &delegate_users[0],
+            CriteriaVote::Approved { weights: vec![1] },
+        ),
+        (&delegate_users[2], CriteriaVote::Rejected),
+    ] {
+        runner.execute_transaction(TransactionTestCase {
+            input: delegate.create_plain_message::<Core<S>>(CallMessage::VoteCriteria {
+                campaign_id: 0,
+                vote,
+            }),
+            assert: Box::new(move |result, _state| {
+                assert!(result.tx_receipt.is_successful());
+            }),
+        });
+    }
+
     // Confirm should fail if sender is not campaigner.
     {
         runner.execute_transaction(TransactionTestCase {
@@ -406,6 +465,21 @@ fn confirm_criteria() {
                     .unwrap_infallible(),
                 Some(campaign)
             );
+
+            // Votes are tallied against the total voting power at the campaign snapshot.
+            let tally = Core::<S>::default()
+                .get_criteria_tally(0, state)
+                .unwrap_infallible()
+                .unwrap();
+            assert_eq!(
+                tally,
+                Tally {
+                    approved: 3_000_000,
+                    rejected: 1_000_000,
+                    total: 6_000_000,
+                }
+            );
+            assert_eq!(tally.participation_bps(), 6666);
         }),
     });
 }
@@ -557,6 +631,122 @@ fn vote_distribution() {
     });
 }
 
+#[test]
+fn confirm_distribution() {
+    let (
+        TestRoles {
+            campaigner,
+            delegate_users,
+            staker,
+            ..
+        },
+        mut runner,
+    ) = setup();
+
+    // Confirm should fail if sender is not campaigner.
+    {
+        runner.execute_transaction(TransactionTestCase {
+            input: staker.create_plain_message::<Core<S>>(CallMessage::ConfirmDistribution {
+                campaign_id: 1,
+            }),
+            assert: Box::new(move |result, _state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "sender '{}' is not the campaigner",
+                            staker.address()
+                        ))
+                    })
+                );
+            }),
+        });
+    }
+
+    // Confirm should fail if the campaign is not in distribution phase.
+    runner.execute_transaction(TransactionTestCase {
+        input: campaigner
+            .create_plain_message::<Core<S>>(CallMessage::ConfirmDistribution { campaign_id: 0 }),
+        assert: Box::new(move |result, _state| {
+            assert_eq!(
+                result.tx_receipt,
+                TxEffect::Reverted(RevertedTxContents {
+                    gas_used: GasUnit::from([100, 100]),
+                    reason: Error::ModuleError(anyhow!(
+                        "invalid distribution confirmation, campaign '0' is not in distribution \
+                         phase"
+                    ))
+                })
+            );
+        }),
+    });
+
+    // The tally is recorded whatever the turnout.
+    runner.execute_transaction(TransactionTestCase {
+        input: delegate_users[2].create_plain_message::<Core<S>>(CallMessage::VoteDistribution {
+            campaign_id: 1,
+            vote: DistributionVote::Approved { weights: vec![1] },
+        }),
+        assert: Box::new(move |result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+    runner.execute_transaction(TransactionTestCase {
+        input: delegate_users[0].create_plain_message::<Core<S>>(CallMessage::VoteDistribution {
+            campaign_id: 1,
+            vote: DistributionVote::Rejected,
+        }),
+        assert: Box::new(move |result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+    runner.execute_transaction(TransactionTestCase {
+        input: campaigner
+            .create_plain_message::<Core<S>>(CallMessage::ConfirmDistribution { campaign_id: 1 }),
+        assert: Box::new(move |result, state| {
+            assert!(result.tx_receipt.is_successful());
+            assert_eq!(result.events.len(), 1);
+            assert_eq!(
+                result.events[0],
+                TestCoreRuntimeEvent::Core(Event::DistributionConfirmed { campaign_id: 1 })
+            );
+
+            let core = Core::<S>::default();
+            assert_eq!(
+                core.get_campaign(1, state)
+                    .unwrap_infallible()
+                    .unwrap()
+                    .phase,
+                Phase::Settle
+            );
+
+            // The tally is recorded at confirmation and returned from then on.
+            let tally = Tally {
+                approved: 1_000_000,
+                rejected: 3_000_000,
+                total: 6_000_000,
+            };
+            assert_eq!(
+                core.get_distribution_tally(1, state).unwrap_infallible(),
+                Some(tally.clone())
+            );
+            let response = core
+                .get_distribution_tally_response(1, state)
+                .unwrap_infallible()
+                .unwrap();
+            assert_eq!(
+                (response.approved, response.rejected, response.total),
+                (tally.approved, tally.rejected, tally.total)
+            );
+            assert_eq!(response.turnout_bps, 6666);
+            assert!(response.quorum);
+            assert!(response.closed);
+            assert_eq!(response.criteria_weights, vec![1_000_000]);
+        }),
+    });
+}
+
 #[test]
 fn indexer_registration() {
     let (TestRoles { admin, indexer, .. }, mut runner) = setup();
@@ -741,9 +931,10 @@ fn update_voting_power() {
     // Confirm that only a registered relayer can update voting powers.
     {
         runner.execute_transaction(TransactionTestCase {
-            input: staker.create_plain_message::<Core<S>>(CallMessage::UpdateVotingPower {
+            input: staker.create_plain_message::<Core<S>>(CallMessage::UpdateVotingPowerAt {
                 address: delegate_users[0].address(),
                 power: 1000,
+                eth_block: 10,
             }),
             assert: Box::new(move |result, _state| {
                 assert_eq!(
@@ -765,9 +956,10 @@ fn update_voting_power() {
         let relayer = relayer.clone();
 
         runner.execute_transaction(TransactionTestCase {
-            input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateVotingPower {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateVotingPowerAt {
                 address: delegate,
                 power: 1000,
+                eth_block: 10,
             }),
             assert: Box::new(move |result, state| {
                 assert!(result.tx_receipt.is_successful());
@@ -797,9 +989,10 @@ fn update_voting_power() {
         let relayer = relayer.clone();
 
         runner.execute_transaction(TransactionTestCase {
-            input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateVotingPower {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateVotingPowerAt {
                 address: delegate,
                 power: 10000,
+                eth_block: 11,
             }),
             assert: Box::new(move |result, _| {
                 assert!(result.tx_receipt.is_successful());
@@ -810,18 +1003,20 @@ fn update_voting_power() {
     // Ensure voting power is ordered from highest to lowest.
     {
         let delegate = delegate_users[2].address();
+        let delegate_users = delegate_users.clone();
 
         runner.execute_transaction(TransactionTestCase {
-            input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateVotingPower {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateVotingPowerAt {
                 address: delegate,
                 power: 8000,
+                eth_block: 12,
             }),
             assert: Box::new(move |result, state| {
                 assert!(result.tx_receipt.is_successful());
 
                 assert_eq!(
                     Core::<S>::default()
-                        .get_voting_powers(state)
+                        .get_voting_powers(None, 10, state)
                         .unwrap_infallible(),
                     vec![
                         (delegate_users[1].address(), 10000),
@@ -829,87 +1024,3787 @@ fn update_voting_power() {
                         (delegate_users[0].address(), 1000),
                     ],
                 );
+
+                let core = Core::<S>::default();
+                assert_eq!(
+                    core.get_total_voting_power(state).unwrap_infallible(),
+                    19000
+                );
+                assert_total_voting_power(
+                    core.get_total_voting_power(state).unwrap_infallible(),
+                    &core
+                        .get_voting_powers(None, usize::MAX, state)
+                        .unwrap_infallible(),
+                );
+            }),
+        });
+    }
+
+    // Confirm that single updates can't be older than the last applied eth block either.
+    {
+        let delegate = delegate_users[0].address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateVotingPowerAt {
+                address: delegate,
+                power: 1,
+                eth_block: 11,
+            }),
+            assert: Box::new(move |result, state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "eth block 11 is older than the last applied eth block 12"
+                        )),
+                    })
+                );
+
+                let core = Core::<S>::default();
+                assert_eq!(
+                    core.get_voting_power(delegate, state).unwrap_infallible(),
+                    1000
+                );
+                assert_eq!(core.get_last_eth_block(state).unwrap_infallible(), Some(12));
             }),
         });
     }
 }
 
-fn setup() -> (TestRoles<S>, TestRunner<TestCoreRuntime<S, MockDaSpec>, S>) {
-    let genesis_config =
-        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(8);
+#[test]
+fn untagged_voting_power_update() {
+    let (
+        TestRoles {
+            delegate_users,
+            relayer,
+            ..
+        },
+        mut runner,
+    ) = setup();
+    let delegate = delegate_users[0].address();
 
-    let admin = genesis_config.additional_accounts.first().unwrap().clone();
-    let staker = genesis_config.additional_accounts[1].clone();
-    let indexer = genesis_config.additional_accounts[2].clone();
-    let relayer = genesis_config.additional_accounts[3].clone();
-    let campaigner = genesis_config.additional_accounts[4].clone();
-    let delegate_users = vec![
-        genesis_config.additional_accounts[5].clone(),
-        genesis_config.additional_accounts[6].clone(),
-        genesis_config.additional_accounts[7].clone(),
-    ];
-    let delegates = {
-        let mut delegates = HashMap::new();
-        delegates.insert(delegate_users[0].address().to_string(), 3_000_000);
-        delegates.insert(delegate_users[1].address().to_string(), 2_000_000);
-        delegates.insert(delegate_users[2].address().to_string(), 1_000_000);
-        delegates
-    };
-    let powers = {
-        let mut powers = HashMap::new();
-        powers.insert(delegate_users[0].address(), 3_000_000);
-        powers.insert(delegate_users[1].address(), 2_000_000);
-        powers.insert(delegate_users[2].address(), 1_000_000);
-        powers
+    // Untagged updates keep the encoding relayers already sign.
+    let call = CallMessage::<S>::UpdateVotingPower {
+        address: delegate,
+        power: 1000,
     };
+    let mut encoded = vec![13];
+    encoded.extend(borsh::to_vec(&delegate).unwrap());
+    encoded.extend(1000u64.to_le_bytes());
+    assert_eq!(borsh::to_vec(&call).unwrap(), encoded);
 
-    let campaign = {
-        let mut campaign = generate_test_campaign(campaigner.address());
-        campaign.phase = Phase::Criteria;
-        campaign.delegates = delegates.clone();
-        campaign.indexer = Some(indexer.address());
-        campaign
-    };
-    let distribution_campaign = {
-        let mut campaign = generate_test_campaign(campaigner.address());
-        campaign.phase = Phase::Distribution;
-        campaign.delegates = delegates.clone();
-        campaign.indexer = Some(indexer.address());
-        campaign
-    };
+    runner.execute_transaction(TransactionTestCase {
+        input: relayer.create_plain_message::<Core<S>>(call),
+        assert: Box::new(move |result, state| {
+            assert!(result.tx_receipt.is_successful());
 
-    let genesis = GenesisConfig::from_minimal_config(
-        genesis_config.clone().into(),
-        CoreConfig {
-            admin: admin.address(),
-            campaigns: vec![campaign.clone(), distribution_campaign],
-            delegates: delegate_users
-                .iter()
-                .map(|u| Delegate {
-                    address: u.address(),
-                    alias: "".to_string(),
+            let core = Core::<S>::default();
+            assert_eq!(
+                core.get_voting_power(delegate, state).unwrap_infallible(),
+                1000
+            );
+            assert_eq!(core.get_last_eth_block(state).unwrap_infallible(), None);
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateVotingPowerAt {
+            address: delegate,
+            power: 2000,
+            eth_block: 10,
+        }),
+        assert: Box::new(move |result, _| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    // Once updates are tagged, untagged ones can't be ordered against them.
+    runner.execute_transaction(TransactionTestCase {
+        input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateVotingPower {
+            address: delegate,
+            power: 1,
+        }),
+        assert: Box::new(move |result, state| {
+            assert_eq!(
+                result.tx_receipt,
+                TxEffect::Reverted(RevertedTxContents {
+                    gas_used: GasUnit::from([100, 100]),
+                    reason: Error::ModuleError(anyhow!(
+                        "voting power updates have to be tagged with an eth block"
+                    )),
                 })
-                .collect::<Vec<_>>(),
-            eth_addresses: Default::default(),
-            indexers: vec![],
-            powers,
-            relayers: vec![relayer.address()],
-        },
-    );
+            );
+            assert_eq!(
+                Core::<S>::default()
+                    .get_voting_power(delegate, state)
+                    .unwrap_infallible(),
+                2000
+            );
+        }),
+    });
+}
 
-    (
+#[test]
+fn update_voting_powers() {
+    let (
         TestRoles {
-            admin,
-            campaign,
-            campaigner,
             delegate_users,
-            delegates,
-            indexer,
             relayer,
             staker,
+            ..
         },
-        TestRunner::new_with_genesis(genesis.into_genesis_params(), TestCoreRuntime::default()),
-    )
+        mut runner,
+    ) = setup();
+
+    // Confirm that only a registered relayer can update voting powers.
+    {
+        runner.execute_transaction(TransactionTestCase {
+            input: staker.create_plain_message::<Core<S>>(CallMessage::UpdateVotingPowers {
+                updates: vec![(delegate_users[0].address(), 1000)],
+                eth_block: 10,
+            }),
+            assert: Box::new(move |result, _state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "sender '{}' is not a registered relayer",
+                            staker.address(),
+                        )),
+                    })
+                );
+            }),
+        });
+    }
+
+    // Confirm that a batch is applied as a whole.
+    {
+        let delegate_users = delegate_users.clone();
+        let relayer_addr = relayer.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateVotingPowers {
+                updates: vec![
+                    (delegate_users[0].address(), 1000),
+                    (delegate_users[2].address(), 5000),
+                ],
+                eth_block: 10,
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(
+                    result.events,
+                    vec![
+                        TestCoreRuntimeEvent::Core(Event::VotingPowerUpdated {
+                            addr: delegate_users[0].address(),
+                            power: 1000,
+                            relayer: relayer_addr,
+                        }),
+                        TestCoreRuntimeEvent::Core(Event::VotingPowerUpdated {
+                            addr: delegate_users[2].address(),
+                            power: 5000,
+                            relayer: relayer_addr,
+                        }),
+                        TestCoreRuntimeEvent::Core(Event::VotingPowersUpdated {
+                            count: 2,
+                            eth_block: 10,
+                            relayer: relayer_addr,
+                        }),
+                    ]
+                );
+
+                let core = Core::<S>::default();
+                assert_eq!(core.get_last_eth_block(state).unwrap_infallible(), Some(10));
+                assert_eq!(
+                    core.get_voting_powers(None, 10, state).unwrap_infallible(),
+                    vec![
+                        (delegate_users[1].address(), 2_000_000),
+                        (delegate_users[2].address(), 5000),
+                        (delegate_users[0].address(), 1000),
+                    ],
+                );
+                assert_total_voting_power(
+                    core.get_total_voting_power(state).unwrap_infallible(),
+                    &core
+                        .get_voting_powers(None, usize::MAX, state)
+                        .unwrap_infallible(),
+                );
+            }),
+        });
+    }
+
+    // Confirm that duplicate addresses in a batch are rejected.
+    {
+        let delegate = delegate_users[1].address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateVotingPowers {
+                updates: vec![(delegate, 1), (delegate, 2)],
+                eth_block: 11,
+            }),
+            assert: Box::new(move |result, state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "duplicate voting power update for '{}'",
+                            delegate,
+                        )),
+                    })
+                );
+                assert_eq!(
+                    Core::<S>::default()
+                        .get_voting_power(delegate, state)
+                        .unwrap_infallible(),
+                    2_000_000
+                );
+            }),
+        });
+    }
+
+    // Confirm that stale updates are rejected.
+    {
+        let delegate = delegate_users[1].address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateVotingPowers {
+                updates: vec![(delegate, 1)],
+                eth_block: 9,
+            }),
+            assert: Box::new(move |result, state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "eth block 9 is older than the last applied eth block 10"
+                        )),
+                    })
+                );
+
+                let core = Core::<S>::default();
+                assert_eq!(
+                    core.get_voting_power(delegate, state).unwrap_infallible(),
+                    2_000_000
+                );
+                assert_eq!(core.get_last_eth_block(state).unwrap_infallible(), Some(10));
+            }),
+        });
+    }
+
+    // Confirm that updates for the same block are accepted.
+    {
+        let delegate = delegate_users[1].address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateVotingPowers {
+                updates: vec![(delegate, 1)],
+                eth_block: 10,
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(
+                    Core::<S>::default()
+                        .get_voting_power(delegate, state)
+                        .unwrap_infallible(),
+                    1
+                );
+            }),
+        });
+    }
+}
+
+#[test]
+fn attest_voting_power() {
+    let (
+        TestRoles {
+            admin,
+            delegate_users,
+            indexer,
+            relayer,
+            staker,
+            ..
+        },
+        mut runner,
+    ) = setup();
+
+    let delegate = delegate_users[0].address();
+
+    for address in [staker.address(), indexer.address()] {
+        runner.execute_transaction(TransactionTestCase {
+            input: admin.create_plain_message::<Core<S>>(CallMessage::RegisterRelayer { address }),
+            assert: Box::new(move |result, _state| {
+                assert!(result.tx_receipt.is_successful());
+            }),
+        });
+    }
+
+    // Confirm that the threshold can't exceed the number of relayers.
+    runner.execute_transaction(TransactionTestCase {
+        input: admin
+            .create_plain_message::<Core<S>>(CallMessage::SetAttestationThreshold { threshold: 4 }),
+        assert: Box::new(move |result, _state| {
+            assert_eq!(
+                result.tx_receipt,
+                TxEffect::Reverted(RevertedTxContents {
+                    gas_used: GasUnit::from([100, 100]),
+                    reason: Error::ModuleError(anyhow!(
+                        "attestation threshold 4 exceeds the number of registered relayers 3"
+                    )),
+                })
+            );
+        }),
+    });
+
+    {
+        let admin_addr = admin.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: admin.create_plain_message::<Core<S>>(CallMessage::SetAttestationThreshold {
+                threshold: 2,
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(
+                    result.events,
+                    vec![TestCoreRuntimeEvent::Core(
+                        Event::AttestationThresholdUpdated {
+                            threshold: 2,
+                            sender: admin_addr,
+                        }
+                    )]
+                );
+                assert_eq!(
+                    Core::<S>::default()
+                        .get_attestation_threshold(state)
+                        .unwrap_infallible(),
+                    Some(2)
+                );
+            }),
+        });
+    }
+
+    // Confirm that a single relayer can no longer set powers directly.
+    runner.execute_transaction(TransactionTestCase {
+        input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateVotingPowerAt {
+            address: delegate,
+            power: 1,
+            eth_block: 1,
+        }),
+        assert: Box::new(move |result, _state| {
+            assert_eq!(
+                result.tx_receipt,
+                TxEffect::Reverted(RevertedTxContents {
+                    gas_used: GasUnit::from([100, 100]),
+                    reason: Error::ModuleError(anyhow!(
+                        "direct voting power updates are disabled, attestation threshold is 2"
+                    )),
+                })
+            );
+        }),
+    });
+
+    // A single attestation is not enough.
+    {
+        let relayer_addr = relayer.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::AttestVotingPower {
+                address: delegate,
+                power: 5000,
+                eth_block: 7,
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(
+                    result.events,
+                    vec![TestCoreRuntimeEvent::Core(Event::VotingPowerAttested {
+                        addr: delegate,
+                        power: 5000,
+                        eth_block: 7,
+                        relayer: relayer_addr,
+                    })]
+                );
+
+                let core = Core::<S>::default();
+                assert_eq!(
+                    core.get_voting_power(delegate, state).unwrap_infallible(),
+                    3_000_000
+                );
+                assert_eq!(
+                    core.get_attestations(delegate, 7, state)
+                        .unwrap_infallible(),
+                    vec![Attestation {
+                        relayer: relayer_addr,
+                        power: 5000,
+                    }]
+                );
+            }),
+        });
+    }
+
+    // Confirm that a relayer can only attest once per block.
+    {
+        let relayer_addr = relayer.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::AttestVotingPower {
+                address: delegate,
+                power: 5000,
+                eth_block: 7,
+            }),
+            assert: Box::new(move |result, _state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "relayer '{}' already attested the voting power of '{}' at eth block 7",
+                            relayer_addr,
+                            delegate,
+                        )),
+                    })
+                );
+            }),
+        });
+    }
+
+    // A conflicting observation is recorded.
+    {
+        let relayer_addr = relayer.address();
+        let staker_addr = staker.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: staker.create_plain_message::<Core<S>>(CallMessage::AttestVotingPower {
+                address: delegate,
+                power: 6000,
+                eth_block: 7,
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+
+                let attestations = vec![
+                    Attestation {
+                        relayer: relayer_addr,
+                        power: 5000,
+                    },
+                    Attestation {
+                        relayer: staker_addr,
+                        power: 6000,
+                    },
+                ];
+                assert_eq!(
+                    result.events,
+                    vec![
+                        TestCoreRuntimeEvent::Core(Event::VotingPowerAttested {
+                            addr: delegate,
+                            power: 6000,
+                            eth_block: 7,
+                            relayer: staker_addr,
+                        }),
+                        TestCoreRuntimeEvent::Core(Event::AttestationConflictDetected {
+                            addr: delegate,
+                            eth_block: 7,
+                            attestations: attestations.clone(),
+                        }),
+                    ]
+                );
+
+                let core = Core::<S>::default();
+                assert_eq!(
+                    core.get_voting_power(delegate, state).unwrap_infallible(),
+                    3_000_000
+                );
+                assert_eq!(
+                    core.get_attestation_conflicts(delegate, state)
+                        .unwrap_infallible(),
+                    vec![AttestationConflict {
+                        eth_block: 7,
+                        attestations,
+                    }]
+                );
+            }),
+        });
+    }
+
+    // An attestation for an older block stays pending until a newer block is applied.
+    runner.execute_transaction(TransactionTestCase {
+        input: relayer.create_plain_message::<Core<S>>(CallMessage::AttestVotingPower {
+            address: delegate,
+            power: 4000,
+            eth_block: 6,
+        }),
+        assert: Box::new(move |result, state| {
+            assert!(result.tx_receipt.is_successful());
+            assert_eq!(
+                Core::<S>::default()
+                    .get_attestations(delegate, 6, state)
+                    .unwrap_infallible()
+                    .len(),
+                1
+            );
+        }),
+    });
+
+    // The power is applied once the threshold of relayers agree.
+    {
+        let indexer_addr = indexer.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: indexer.create_plain_message::<Core<S>>(CallMessage::AttestVotingPower {
+                address: delegate,
+                power: 5000,
+                eth_block: 7,
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(result.events.len(), 3);
+                assert_eq!(
+                    result.events[2],
+                    TestCoreRuntimeEvent::Core(Event::VotingPowerUpdated {
+                        addr: delegate,
+                        power: 5000,
+                        relayer: indexer_addr,
+                    })
+                );
+
+                let core = Core::<S>::default();
+                assert_eq!(
+                    core.get_voting_power(delegate, state).unwrap_infallible(),
+                    5000
+                );
+                assert_eq!(
+                    core.get_attestations(delegate, 7, state)
+                        .unwrap_infallible(),
+                    vec![]
+                );
+                // Attestations for superseded blocks are pruned.
+                assert_eq!(
+                    core.get_attestations(delegate, 6, state)
+                        .unwrap_infallible(),
+                    vec![]
+                );
+                assert_eq!(
+                    core.get_attestation_conflicts(delegate, state)
+                        .unwrap_infallible()[0]
+                        .attestations
+                        .len(),
+                    3
+                );
+                assert_total_voting_power(
+                    core.get_total_voting_power(state).unwrap_infallible(),
+                    &core
+                        .get_voting_powers(None, usize::MAX, state)
+                        .unwrap_infallible(),
+                );
+            }),
+        });
+    }
+
+    // Confirm that attestations for already applied blocks are rejected.
+    runner.execute_transaction(TransactionTestCase {
+        input: relayer.create_plain_message::<Core<S>>(CallMessage::AttestVotingPower {
+            address: delegate,
+            power: 5000,
+            eth_block: 7,
+        }),
+        assert: Box::new(move |result, _state| {
+            assert_eq!(
+                result.tx_receipt,
+                TxEffect::Reverted(RevertedTxContents {
+                    gas_used: GasUnit::from([100, 100]),
+                    reason: Error::ModuleError(anyhow!(
+                        "eth block 7 is not newer than the last attested eth block 7 for '{}'",
+                        delegate,
+                    )),
+                })
+            );
+        }),
+    });
+
+    // Relayers can be unregistered as long as the rest can still reach the threshold.
+    runner.execute_transaction(TransactionTestCase {
+        input: admin.create_plain_message::<Core<S>>(CallMessage::UnregisterRelayer {
+            address: staker.address(),
+        }),
+        assert: Box::new(move |result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+    runner.execute_transaction(TransactionTestCase {
+        input: admin.create_plain_message::<Core<S>>(CallMessage::UnregisterRelayer {
+            address: indexer.address(),
+        }),
+        assert: Box::new(move |result, state| {
+            assert_eq!(
+                result.tx_receipt,
+                TxEffect::Reverted(RevertedTxContents {
+                    gas_used: GasUnit::from([100, 100]),
+                    reason: Error::ModuleError(anyhow!(
+                        "unregistering relayer '{}' would leave 1 relayers, below the attestation threshold 2",
+                        indexer.address(),
+                    )),
+                })
+            );
+            assert!(Core::<S>::default()
+                .get_relayer(indexer.address(), state)
+                .unwrap_infallible()
+                .is_some());
+        }),
+    });
+}
+
+#[test]
+fn prove_voting_power() {
+    let (
+        TestRoles {
+            relayer, staker, ..
+        },
+        mut runner,
+    ) = setup();
+
+    let holder = [0x11; 20];
+    let holder_addr = filament_hub_eth::addr_to_hub_address::<S>(&hex::encode(holder)).unwrap();
+    let balance = 1_234_500_000_000_000_000_000u128;
+
+    let (storage_root, storage_proof) = generate_test_trie(
+        &proof::mapping_slot(holder, VAULT_BALANCES_SLOT),
+        rlp::encode_bytes(&balance.to_be_bytes()[7..]),
+    );
+    let (state_root, account_proof) = generate_test_trie(
+        &VAULT_ADDRESS,
+        rlp::encode_list(&[
+            rlp::encode_u64(1),
+            rlp::encode_u64(0),
+            rlp::encode_bytes(&storage_root),
+            rlp::encode_bytes(&proof::keccak(&[])),
+        ]),
+    );
+
+    let checkpoint = generate_test_eth_header(100, [0; 32], [0; 32]);
+    let header = generate_test_eth_header(101, proof::keccak(&checkpoint), state_root);
+
+    // Confirm that headers have to link to the stored chain.
+    runner.execute_transaction(TransactionTestCase {
+        input: relayer.create_plain_message::<Core<S>>(CallMessage::SubmitEthHeaders {
+            headers: vec![generate_test_eth_header(101, [1; 32], state_root)],
+        }),
+        assert: Box::new(move |result, _state| {
+            assert_eq!(
+                result.tx_receipt,
+                TxEffect::Reverted(RevertedTxContents {
+                    gas_used: GasUnit::from([100, 100]),
+                    reason: Error::ModuleError(anyhow!(
+                        "eth header 101 parent hash does not match header 100"
+                    )),
+                })
+            );
+        }),
+    });
+    runner.execute_transaction(TransactionTestCase {
+        input: relayer.create_plain_message::<Core<S>>(CallMessage::SubmitEthHeaders {
+            headers: vec![generate_test_eth_header(
+                102,
+                proof::keccak(&checkpoint),
+                state_root,
+            )],
+        }),
+        assert: Box::new(move |result, _state| {
+            assert_eq!(
+                result.tx_receipt,
+                TxEffect::Reverted(RevertedTxContents {
+                    gas_used: GasUnit::from([100, 100]),
+                    reason: Error::ModuleError(anyhow!(
+                        "eth header 102 does not extend the header chain at 100"
+                    )),
+                })
+            );
+        }),
+    });
+
+    {
+        let relayer_addr = relayer.address();
+        let header = header.clone();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::SubmitEthHeaders {
+                headers: vec![header.clone()],
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(
+                    result.events,
+                    vec![
+                        TestCoreRuntimeEvent::Core(Event::EthHeaderAccepted {
+                            number: 101,
+                            replaced: false,
+                        }),
+                        TestCoreRuntimeEvent::Core(Event::EthHeadersSubmitted {
+                            from: 101,
+                            to: 101,
+                            relayer: relayer_addr,
+                        }),
+                    ]
+                );
+                assert_eq!(
+                    Core::<S>::default().get_eth_head(state).unwrap_infallible(),
+                    Some(EthHeader {
+                        number: 101,
+                        hash: proof::keccak(&header),
+                        parent_hash: proof::keccak(&checkpoint),
+                        state_root,
+                    })
+                );
+            }),
+        });
+    }
+
+    // Confirm that tampered proofs are rejected.
+    {
+        let mut storage_proof = storage_proof.clone();
+        let last = storage_proof[0].len() - 1;
+        storage_proof[0][last] ^= 1;
+
+        runner.execute_transaction(TransactionTestCase {
+            input: staker.create_plain_message::<Core<S>>(CallMessage::ProveVotingPower {
+                eth_address: hex::encode(holder),
+                eth_block: 101,
+                account_proof: account_proof.clone(),
+                storage_proof,
+            }),
+            assert: Box::new(move |result, _state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "proof node does not match its reference"
+                        )),
+                    })
+                );
+            }),
+        });
+    }
+
+    // Confirm that proofs can't carry nodes off the path.
+    {
+        let mut storage_proof = storage_proof.clone();
+        storage_proof.push(rlp::encode_list(&[]));
+
+        runner.execute_transaction(TransactionTestCase {
+            input: staker.create_plain_message::<Core<S>>(CallMessage::ProveVotingPower {
+                eth_address: hex::encode(holder),
+                eth_block: 101,
+                account_proof: account_proof.clone(),
+                storage_proof,
+            }),
+            assert: Box::new(move |result, _state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!("proof has unused nodes")),
+                    })
+                );
+            }),
+        });
+    }
+
+    // Anyone can apply a valid proof.
+    {
+        let staker_addr = staker.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: staker.create_plain_message::<Core<S>>(CallMessage::ProveVotingPower {
+                eth_address: hex::encode(holder),
+                eth_block: 101,
+                account_proof: account_proof.clone(),
+                storage_proof: storage_proof.clone(),
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(
+                    result.events,
+                    vec![TestCoreRuntimeEvent::Core(Event::VotingPowerProven {
+                        addr: holder_addr,
+                        power: 1234,
+                        eth_block: 101,
+                        sender: staker_addr,
+                    })]
+                );
+                assert_eq!(
+                    Core::<S>::default()
+                        .get_voting_power(holder_addr, state)
+                        .unwrap_infallible(),
+                    1234
+                );
+            }),
+        });
+    }
+
+    // Confirm that proofs can't be replayed.
+    runner.execute_transaction(TransactionTestCase {
+        input: staker.create_plain_message::<Core<S>>(CallMessage::ProveVotingPower {
+            eth_address: hex::encode(holder),
+            eth_block: 101,
+            account_proof,
+            storage_proof,
+        }),
+        assert: Box::new(move |result, _state| {
+            assert_eq!(
+                result.tx_receipt,
+                TxEffect::Reverted(RevertedTxContents {
+                    gas_used: GasUnit::from([100, 100]),
+                    reason: Error::ModuleError(anyhow!(
+                        "eth block 101 is not newer than the last attested eth block 101 for '{}'",
+                        holder_addr,
+                    )),
+                })
+            );
+        }),
+    });
+}
+
+#[test]
+fn eth_header_quorum() {
+    let (
+        TestRoles {
+            admin,
+            indexer,
+            relayer,
+            staker,
+            ..
+        },
+        mut runner,
+    ) = setup();
+
+    let holder = [0x11; 20];
+    let balance = 1_234_500_000_000_000_000_000u128;
+    let (storage_root, storage_proof) = generate_test_trie(
+        &proof::mapping_slot(holder, VAULT_BALANCES_SLOT),
+        rlp::encode_bytes(&balance.to_be_bytes()[7..]),
+    );
+    let (state_root, account_proof) = generate_test_trie(
+        &VAULT_ADDRESS,
+        rlp::encode_list(&[
+            rlp::encode_u64(1),
+            rlp::encode_u64(0),
+            rlp::encode_bytes(&storage_root),
+            rlp::encode_bytes(&proof::keccak(&[])),
+        ]),
+    );
+
+    let checkpoint = generate_test_eth_header(100, [0; 32], [0; 32]);
+    let first = generate_test_eth_header(101, proof::keccak(&checkpoint), [0; 32]);
+    let second = generate_test_eth_header(102, proof::keccak(&first), [0; 32]);
+    let fork = generate_test_eth_header(102, proof::keccak(&first), state_root);
+
+    for address in [staker.address(), indexer.address()] {
+        runner.execute_transaction(TransactionTestCase {
+            input: admin.create_plain_message::<Core<S>>(CallMessage::RegisterRelayer { address }),
+            assert: Box::new(move |result, _state| {
+                assert!(result.tx_receipt.is_successful());
+            }),
+        });
+    }
+    runner.execute_transaction(TransactionTestCase {
+        input: admin
+            .create_plain_message::<Core<S>>(CallMessage::SetAttestationThreshold { threshold: 2 }),
+        assert: Box::new(move |result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    // A single relayer only leaves the headers pending.
+    {
+        let relayer_addr = relayer.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::SubmitEthHeaders {
+                headers: vec![first.clone(), second.clone()],
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(
+                    result.events,
+                    vec![TestCoreRuntimeEvent::Core(Event::EthHeadersSubmitted {
+                        from: 101,
+                        to: 102,
+                        relayer: relayer_addr,
+                    })]
+                );
+                assert_eq!(
+                    Core::<S>::default()
+                        .get_eth_head(state)
+                        .unwrap_infallible()
+                        .map(|head| head.number),
+                    Some(100)
+                );
+            }),
+        });
+    }
+
+    // The second relayer reaches the threshold and the headers are stored.
+    {
+        let staker_addr = staker.address();
+        let second = second.clone();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: staker.create_plain_message::<Core<S>>(CallMessage::SubmitEthHeaders {
+                headers: vec![first.clone(), second.clone()],
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(
+                    result.events,
+                    vec![
+                        TestCoreRuntimeEvent::Core(Event::EthHeaderAccepted {
+                            number: 101,
+                            replaced: false,
+                        }),
+                        TestCoreRuntimeEvent::Core(Event::EthHeaderAccepted {
+                            number: 102,
+                            replaced: false,
+                        }),
+                        TestCoreRuntimeEvent::Core(Event::EthHeadersSubmitted {
+                            from: 101,
+                            to: 102,
+                            relayer: staker_addr,
+                        }),
+                    ]
+                );
+                assert_eq!(
+                    Core::<S>::default()
+                        .get_eth_head(state)
+                        .unwrap_infallible()
+                        .map(|head| head.hash),
+                    Some(proof::keccak(&second))
+                );
+            }),
+        });
+    }
+
+    // A competing header attested by the threshold replaces the stored one.
+    runner.execute_transaction(TransactionTestCase {
+        input: relayer.create_plain_message::<Core<S>>(CallMessage::SubmitEthHeaders {
+            headers: vec![fork.clone()],
+        }),
+        assert: Box::new(move |result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+    {
+        let fork = fork.clone();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: indexer.create_plain_message::<Core<S>>(CallMessage::SubmitEthHeaders {
+                headers: vec![fork.clone()],
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(
+                    result.events[0],
+                    TestCoreRuntimeEvent::Core(Event::EthHeaderAccepted {
+                        number: 102,
+                        replaced: true,
+                    })
+                );
+                assert_eq!(
+                    Core::<S>::default()
+                        .get_eth_head(state)
+                        .unwrap_infallible()
+                        .map(|head| head.hash),
+                    Some(proof::keccak(&fork))
+                );
+            }),
+        });
+    }
+
+    // Confirm that proofs need a header backed by the current threshold.
+    runner.execute_transaction(TransactionTestCase {
+        input: admin
+            .create_plain_message::<Core<S>>(CallMessage::SetAttestationThreshold { threshold: 3 }),
+        assert: Box::new(move |result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+    runner.execute_transaction(TransactionTestCase {
+        input: staker.create_plain_message::<Core<S>>(CallMessage::ProveVotingPower {
+            eth_address: hex::encode(holder),
+            eth_block: 102,
+            account_proof,
+            storage_proof,
+        }),
+        assert: Box::new(move |result, _state| {
+            assert_eq!(
+                result.tx_receipt,
+                TxEffect::Reverted(RevertedTxContents {
+                    gas_used: GasUnit::from([100, 100]),
+                    reason: Error::ModuleError(anyhow!(
+                        "eth header 102 is attested by 2 relayers, below the attestation threshold 3"
+                    )),
+                })
+            );
+        }),
+    });
+}
+
+#[test]
+fn link_eth_address() {
+    let (
+        TestRoles {
+            campaigner, staker, ..
+        },
+        mut runner,
+    ) = setup();
+
+    let signing_key = SigningKey::from_bytes(&[7u8; 32].into()).unwrap();
+    let eth_address = format!(
+        "0x{}",
+        hex::encode(filament_hub_eth::vk_to_eth_address(
+            signing_key.verifying_key()
+        ))
+    );
+    let sign_link = |addr: <S as Spec>::Address| {
+        filament_hub_eth::sign(
+            &signing_key,
+            filament_hub_eth::link_eth_address_msg(&addr.to_string(), filament_hub_eth::CHAIN_ID),
+        )
+        .unwrap()
+        .to_vec()
+    };
+
+    // Confirm that the signature has to cover the sender.
+    {
+        let eth_address = eth_address.clone();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: campaigner.create_plain_message::<Core<S>>(CallMessage::LinkEthAddress {
+                eth_address: eth_address.clone(),
+                signature: sign_link(staker.address()),
+            }),
+            assert: Box::new(move |result, _state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "signature for eth address '{}' is invalid",
+                            eth_address,
+                        )),
+                    })
+                );
+            }),
+        });
+    }
+
+    {
+        let eth_address = eth_address.clone();
+        let campaigner_addr = campaigner.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: campaigner.create_plain_message::<Core<S>>(CallMessage::LinkEthAddress {
+                eth_address: eth_address.to_uppercase().replace("0X", "0x"),
+                signature: sign_link(campaigner.address()),
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(
+                    result.events,
+                    vec![TestCoreRuntimeEvent::Core(Event::EthAddressLinked {
+                        addr: campaigner_addr,
+                        eth_address: eth_address.clone(),
+                    })]
+                );
+
+                let core = Core::<S>::default();
+                assert_eq!(
+                    core.get_linked_eth_address(campaigner_addr, state)
+                        .unwrap_infallible(),
+                    Some(eth_address.clone())
+                );
+                assert_eq!(
+                    core.get_linked_hub_address(&eth_address.to_uppercase(), state)
+                        .unwrap_infallible(),
+                    Some(campaigner_addr)
+                );
+            }),
+        });
+    }
+
+    // Confirm that an eth address can only be linked once.
+    {
+        let eth_address = eth_address.clone();
+        let campaigner_addr = campaigner.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: staker.create_plain_message::<Core<S>>(CallMessage::LinkEthAddress {
+                eth_address: eth_address.clone(),
+                signature: sign_link(staker.address()),
+            }),
+            assert: Box::new(move |result, _state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "eth address '{}' is already linked to '{}'",
+                            eth_address,
+                            campaigner_addr,
+                        )),
+                    })
+                );
+            }),
+        });
+    }
+
+    {
+        let eth_address = eth_address.clone();
+        let campaigner_addr = campaigner.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: campaigner.create_plain_message::<Core<S>>(CallMessage::UnlinkEthAddress {}),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(
+                    result.events,
+                    vec![TestCoreRuntimeEvent::Core(Event::EthAddressUnlinked {
+                        addr: campaigner_addr,
+                        eth_address: eth_address.clone(),
+                    })]
+                );
+
+                let core = Core::<S>::default();
+                assert_eq!(
+                    core.get_linked_eth_address(campaigner_addr, state)
+                        .unwrap_infallible(),
+                    None
+                );
+                assert_eq!(
+                    core.get_linked_hub_address(&eth_address, state)
+                        .unwrap_infallible(),
+                    None
+                );
+            }),
+        });
+    }
+
+    // Confirm that only linked accounts can unlink.
+    {
+        let staker_addr = staker.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: staker.create_plain_message::<Core<S>>(CallMessage::UnlinkEthAddress {}),
+            assert: Box::new(move |result, _state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "'{}' is not linked to an eth address",
+                            staker_addr,
+                        )),
+                    })
+                );
+            }),
+        });
+    }
+}
+
+#[test]
+fn campaign_responses_without_eth_addresses() {
+    let (
+        TestRoles {
+            campaigner,
+            delegate_users,
+            indexer,
+            ..
+        },
+        mut runner,
+    ) = setup();
+
+    // Only the second delegate links an eth address, nobody else has one.
+    let signing_key = SigningKey::from_bytes(&[7u8; 32].into()).unwrap();
+    let eth_address = format!(
+        "0x{}",
+        hex::encode(filament_hub_eth::vk_to_eth_address(
+            signing_key.verifying_key()
+        ))
+    );
+    runner.execute_transaction(TransactionTestCase {
+        input: delegate_users[1].create_plain_message::<Core<S>>(CallMessage::LinkEthAddress {
+            eth_address: eth_address.clone(),
+            signature: filament_hub_eth::sign(
+                &signing_key,
+                filament_hub_eth::link_eth_address_msg(
+                    &delegate_users[1].address().to_string(),
+                    filament_hub_eth::CHAIN_ID,
+                ),
+            )
+            .unwrap()
+            .to_vec(),
+        }),
+        assert: Box::new(move |result, _| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    for delegate in &delegate_users[..2] {
+        runner.execute_transaction(TransactionTestCase {
+            input: delegate.create_plain_message::<Core<S>>(CallMessage::VoteCriteria {
+                campaign_id: 0,
+                vote: CriteriaVote::Approved { weights: vec![] },
+            }),
+            assert: Box::new(move |result, _| {
+                assert!(result.tx_receipt.is_successful());
+            }),
+        });
+    }
+
+    runner.execute_transaction(TransactionTestCase {
+        input: campaigner.create_plain_message::<Core<S>>(CallMessage::Draft {
+            title: "".to_string(),
+            description: "".to_string(),
+            criteria: generate_test_criteria(),
+            evictions: vec![delegate_users[2].address()],
+        }),
+        assert: Box::new(move |result, state| {
+            assert!(result.tx_receipt.is_successful());
+
+            let core = Core::<S>::default();
+            let address = |addr: <S as Spec>::Address, eth_addr: Option<String>| AddressResponse {
+                addr: addr.to_string(),
+                eth_addr,
+            };
+            let delegates = {
+                let mut delegates = delegate_users
+                    .iter()
+                    .zip([3_000_000, 2_000_000, 1_000_000])
+                    .enumerate()
+                    .map(|(i, (user, power))| DelegateResponse {
+                        addr: user.address().to_string(),
+                        eth_addr: (i == 1).then(|| eth_address.clone()),
+                        power,
+                    })
+                    .collect::<Vec<_>>();
+                delegates.sort_by(|a, b| a.addr.cmp(&b.addr));
+                delegates
+            };
+
+            let campaign = core.get_campaign(0, state).unwrap_infallible().unwrap();
+            let response = core
+                .campaign_to_response(campaign, state)
+                .unwrap_infallible();
+            assert_eq!(response.campaigner, address(campaigner.address(), None));
+            assert_eq!(response.delegates, delegates);
+            assert_eq!(response.indexer, Some(address(indexer.address(), None)));
+
+            let drafted = core
+                .get_campaigns_page(
+                    &CampaignFilter {
+                        campaigner: Some(campaigner.address()),
+                        ..Default::default()
+                    },
+                    CampaignSort::Id,
+                    SortOrder::Asc,
+                    None,
+                    10,
+                    state,
+                )
+                .unwrap_infallible()
+                .campaigns;
+            assert_eq!(drafted.len(), 1);
+            let response = core
+                .campaign_to_response(drafted[0].clone(), state)
+                .unwrap_infallible();
+            assert_eq!(
+                response.evictions,
+                vec![address(delegate_users[2].address(), None)]
+            );
+            assert_eq!(response.delegates.len(), 2);
+            assert_eq!(response.indexer, None);
+
+            let mut votes = delegate_users[..2]
+                .iter()
+                .enumerate()
+                .map(|(i, user)| VoteResponse {
+                    addr: user.address().to_string(),
+                    eth_addr: (i == 1).then(|| eth_address.clone()),
+                    vote: CriteriaVote::Approved { weights: vec![] },
+                })
+                .collect::<Vec<_>>();
+            votes.sort_by(|a, b| a.addr.cmp(&b.addr));
+            assert_eq!(
+                core.get_criteria_votes_response(0, state)
+                    .unwrap_infallible(),
+                Some(votes)
+            );
+            assert_eq!(
+                core.get_distribution_votes_response(1, state)
+                    .unwrap_infallible(),
+                Some(vec![])
+            );
+
+            // Votes of missing campaigns are not found rather than empty.
+            assert_eq!(
+                core.get_criteria_votes_response(99, state)
+                    .unwrap_infallible(),
+                None
+            );
+            assert_eq!(
+                core.get_distribution_votes_response(99, state)
+                    .unwrap_infallible(),
+                None
+            );
+        }),
+    });
+}
+
+#[test]
+fn delegate_campaigns() {
+    let (
+        TestRoles {
+            campaigner,
+            delegate_users,
+            ..
+        },
+        mut runner,
+    ) = setup();
+
+    for title in ["first", "second"] {
+        runner.execute_transaction(TransactionTestCase {
+            input: campaigner.create_plain_message::<Core<S>>(CallMessage::Draft {
+                title: title.to_string(),
+                description: "".to_string(),
+                criteria: generate_test_criteria(),
+                evictions: vec![],
+            }),
+            assert: Box::new(move |result, _| {
+                assert!(result.tx_receipt.is_successful());
+            }),
+        });
+    }
+    runner.execute_transaction(TransactionTestCase {
+        input: campaigner.create_plain_message::<Core<S>>(CallMessage::Init { campaign_id: 2 }),
+        assert: Box::new(move |result, _| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: delegate_users[0].create_plain_message::<Core<S>>(CallMessage::VoteCriteria {
+            campaign_id: 2,
+            vote: CriteriaVote::Rejected,
+        }),
+        assert: Box::new(move |result, state| {
+            assert!(result.tx_receipt.is_successful());
+
+            let core = Core::<S>::default();
+            // Campaigns created at genesis are indexed along with drafted ones.
+            assert_eq!(
+                core.get_campaigns_by_delegate(delegate_users[1].address(), state)
+                    .unwrap_infallible()
+                    .into_iter()
+                    .map(|campaign| (campaign.id, campaign.phase))
+                    .collect::<Vec<_>>(),
+                vec![
+                    (0, Phase::Criteria),
+                    (1, Phase::Distribution),
+                    (2, Phase::Criteria),
+                    (3, Phase::Draft)
+                ]
+            );
+            assert_eq!(
+                core.get_campaigns_by_delegate(campaigner.address(), state)
+                    .unwrap_infallible(),
+                vec![]
+            );
+
+            // Both delegates still owe votes on the campaigns from genesis.
+            let genesis_actions = vec![
+                PendingAction {
+                    campaign_id: 0,
+                    title: "".to_string(),
+                    action: DelegateAction::VoteCriteria,
+                },
+                PendingAction {
+                    campaign_id: 1,
+                    title: "".to_string(),
+                    action: DelegateAction::VoteDistribution,
+                },
+            ];
+            assert_eq!(
+                core.get_pending_actions(delegate_users[0].address(), state)
+                    .unwrap_infallible(),
+                genesis_actions.clone()
+            );
+            assert_eq!(
+                core.get_pending_actions(delegate_users[1].address(), state)
+                    .unwrap_infallible(),
+                [
+                    genesis_actions,
+                    vec![PendingAction {
+                        campaign_id: 2,
+                        title: "first".to_string(),
+                        action: DelegateAction::VoteCriteria,
+                    }]
+                ]
+                .concat()
+            );
+
+            let eth_addr = "0x0000000000000000000000000000000000000001";
+            assert_eq!(
+                core.get_hub_address_by_eth_addr(eth_addr, state)
+                    .unwrap_infallible(),
+                Some(filament_hub_eth::addr_to_hub_address::<S>(eth_addr).unwrap())
+            );
+            assert_eq!(
+                core.get_hub_address_by_eth_addr("0x01", state)
+                    .unwrap_infallible(),
+                None
+            );
+        }),
+    });
+}
+
+#[test]
+fn campaign_tallies() {
+    let (
+        TestRoles {
+            campaigner,
+            delegate_users,
+            ..
+        },
+        mut runner,
+    ) = setup();
+    let non_voters = |voters: &[usize]| {
+        let mut non_voters = [3_000_000, 2_000_000, 1_000_000]
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| !voters.contains(i))
+            .map(|(i, power)| DelegateResponse {
+                addr: delegate_users[i].address().to_string(),
+                eth_addr: None,
+                power,
+            })
+            .collect::<Vec<_>>();
+        non_voters.sort_by(|a, b| a.addr.cmp(&b.addr));
+        non_voters
+    };
+    let criteria_non_voters = non_voters(&[0]);
+    let distribution_non_voters = non_voters(&[2]);
+
+    runner.execute_transaction(TransactionTestCase {
+        input: delegate_users[0].create_plain_message::<Core<S>>(CallMessage::VoteCriteria {
+            campaign_id: 0,
+            vote: CriteriaVote::Approved { weights: vec![1] },
+        }),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+    let open_non_voters = criteria_non_voters.clone();
+    runner.execute_transaction(TransactionTestCase {
+        input: delegate_users[2].create_plain_message::<Core<S>>(CallMessage::VoteDistribution {
+            campaign_id: 1,
+            vote: DistributionVote::Rejected,
+        }),
+        assert: Box::new(move |result, state| {
+            assert!(result.tx_receipt.is_successful());
+
+            let core = Core::<S>::default();
+            assert_eq!(
+                core.get_criteria_tally_response(0, state)
+                    .unwrap_infallible(),
+                Some(TallyResponse {
+                    approved: 3_000_000,
+                    rejected: 0,
+                    total: 6_000_000,
+                    turnout_bps: 5000,
+                    quorum: true,
+                    closed: false,
+                    non_voters: open_non_voters,
+                    criteria_weights: vec![1_000_000],
+                })
+            );
+            assert_eq!(
+                core.get_distribution_tally_response(1, state)
+                    .unwrap_infallible(),
+                Some(TallyResponse {
+                    approved: 0,
+                    rejected: 1_000_000,
+                    total: 6_000_000,
+                    turnout_bps: 1666,
+                    quorum: false,
+                    closed: false,
+                    non_voters: distribution_non_voters,
+                    criteria_weights: vec![0],
+                })
+            );
+            assert_eq!(
+                core.get_criteria_tally_response(99, state)
+                    .unwrap_infallible(),
+                None
+            );
+            assert_eq!(
+                core.get_distribution_tally_response(99, state)
+                    .unwrap_infallible(),
+                None
+            );
+        }),
+    });
+
+    // Once confirmed, the tally recorded by the state machine is returned.
+    runner.execute_transaction(TransactionTestCase {
+        input: campaigner.create_plain_message::<Core<S>>(CallMessage::ConfirmCriteria {
+            campaign_id: 0,
+            proposal_id: None,
+        }),
+        assert: Box::new(move |result, state| {
+            assert!(result.tx_receipt.is_successful());
+
+            let core = Core::<S>::default();
+            let tally = core
+                .get_criteria_tally(0, state)
+                .unwrap_infallible()
+                .unwrap();
+            let response = core
+                .get_criteria_tally_response(0, state)
+                .unwrap_infallible()
+                .unwrap();
+            assert_eq!(
+                (response.approved, response.rejected, response.total),
+                (tally.approved, tally.rejected, tally.total)
+            );
+            assert!(response.closed);
+            assert_eq!(response.non_voters, criteria_non_voters);
+        }),
+    });
+}
+
+#[test]
+fn protocol_stats() {
+    let (
+        TestRoles {
+            admin,
+            campaigner,
+            delegate_users,
+            relayer,
+            ..
+        },
+        mut runner,
+    ) = setup();
+    let campaigns = |counts: &[(Phase, u64)]| {
+        Phase::ALL
+            .into_iter()
+            .map(|phase| PhaseCount {
+                campaigns: counts
+                    .iter()
+                    .find(|(each, _)| *each == phase)
+                    .map(|(_, count)| *count)
+                    .unwrap_or_default(),
+                phase,
+            })
+            .collect::<Vec<_>>()
+    };
+    let stats = StatsResponse {
+        campaigns: campaigns(&[(Phase::Criteria, 1), (Phase::Distribution, 1)]),
+        escrowed_budget: 0,
+        distributions: 0,
+        distributed_rewards: 0,
+        indexers: 0,
+        relayers: 1,
+        delegates: 3,
+        total_voting_power: 6_000_000,
+        average_turnout_bps: 0,
+    };
+
+    // Campaigns created at genesis are counted along with drafted ones.
+    {
+        let stats = StatsResponse {
+            campaigns: campaigns(&[
+                (Phase::Draft, 1),
+                (Phase::Criteria, 1),
+                (Phase::Distribution, 1),
+            ]),
+            ..stats.clone()
+        };
+        runner.execute_transaction(TransactionTestCase {
+            input: campaigner.create_plain_message::<Core<S>>(CallMessage::Draft {
+                title: "".to_string(),
+                description: "".to_string(),
+                criteria: generate_test_criteria(),
+                evictions: vec![],
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(
+                    Core::<S>::default().get_stats(state).unwrap_infallible(),
+                    stats
+                );
+            }),
+        });
+    }
+    runner.execute_transaction(TransactionTestCase {
+        input: delegate_users[0].create_plain_message::<Core<S>>(CallMessage::VoteCriteria {
+            campaign_id: 0,
+            vote: CriteriaVote::Approved { weights: vec![1] },
+        }),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+    {
+        let stats = StatsResponse {
+            campaigns: campaigns(&[
+                (Phase::Draft, 1),
+                (Phase::Publish, 1),
+                (Phase::Distribution, 1),
+            ]),
+            average_turnout_bps: 5000,
+            ..stats.clone()
+        };
+        runner.execute_transaction(TransactionTestCase {
+            input: campaigner.create_plain_message::<Core<S>>(CallMessage::ConfirmCriteria {
+                campaign_id: 0,
+                proposal_id: None,
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(
+                    Core::<S>::default().get_stats(state).unwrap_infallible(),
+                    stats
+                );
+            }),
+        });
+    }
+
+    runner.execute_transaction(TransactionTestCase {
+        input: campaigner.create_plain_message::<Core<S>>(CallMessage::FundRewardPool {
+            campaign_id: 1,
+            amount: 1000,
+        }),
+        assert: Box::new(|result, state| {
+            assert!(result.tx_receipt.is_successful());
+            assert_eq!(
+                Core::<S>::default()
+                    .get_stats(state)
+                    .unwrap_infallible()
+                    .escrowed_budget,
+                1000
+            );
+        }),
+    });
+
+    // Distributing the rewards releases the pool from escrow.
+    runner.execute_transaction(TransactionTestCase {
+        input: admin
+            .create_plain_message::<Core<S>>(CallMessage::SetAttestationThreshold { threshold: 1 }),
+        assert: Box::new(move |result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+    runner.execute_transaction(TransactionTestCase {
+        input: relayer.create_plain_message::<Core<S>>(CallMessage::PostObjective {
+            campaign_id: 1,
+            measurement: ObjectiveMeasurement {
+                attributions: vec![100],
+            },
+        }),
+        assert: Box::new(|result, state| {
+            assert!(result.tx_receipt.is_successful());
+
+            let core = Core::<S>::default();
+            let distributed = core
+                .get_delegate_rewards(1, state)
+                .unwrap_infallible()
+                .iter()
+                .map(|reward| reward.reward)
+                .sum::<u64>();
+            let stats = core.get_stats(state).unwrap_infallible();
+            assert_eq!(
+                (
+                    stats.escrowed_budget,
+                    stats.distributions,
+                    stats.distributed_rewards
+                ),
+                (0, 1, distributed)
+            );
+        }),
+    });
+}
+
+#[test]
+fn state_responses() {
+    let (
+        TestRoles {
+            admin,
+            delegate_users,
+            relayer,
+            ..
+        },
+        mut runner,
+    ) = setup();
+
+    runner.execute_transaction(TransactionTestCase {
+        input: delegate_users[0].create_plain_message::<Core<S>>(CallMessage::ProposeCriteria {
+            campaign_id: 0,
+            criteria: generate_test_criteria(),
+        }),
+        assert: Box::new(move |result, state| {
+            assert!(result.tx_receipt.is_successful());
+
+            let core = Core::<S>::default();
+            let address = |addr: <S as Spec>::Address| AddressResponse {
+                addr: addr.to_string(),
+                eth_addr: None,
+            };
+
+            assert_eq!(
+                core.get_criteria_proposals_response(0, state)
+                    .unwrap_infallible(),
+                Some(vec![ProposalResponse {
+                    id: 0,
+                    proposer: address(delegate_users[0].address()),
+                    criteria: generate_test_criteria(),
+                }])
+            );
+            assert_eq!(
+                core.get_criteria_proposals_response(1, state)
+                    .unwrap_infallible(),
+                Some(vec![])
+            );
+            assert_eq!(
+                core.get_criteria_proposals_response(99, state)
+                    .unwrap_infallible(),
+                None
+            );
+
+            assert_eq!(
+                core.get_admin(state).unwrap_infallible(),
+                Some(admin.address())
+            );
+            assert_eq!(
+                core.get_relayers_response(state).unwrap_infallible(),
+                vec![address(relayer.address())]
+            );
+            assert_eq!(
+                core.get_indexers_response(state).unwrap_infallible(),
+                vec![]
+            );
+            assert_eq!(
+                core.get_delegates_response(state).unwrap_infallible(),
+                delegate_users
+                    .iter()
+                    .zip([3_000_000, 2_000_000, 1_000_000])
+                    .map(|(user, power)| DelegateResponse {
+                        addr: user.address().to_string(),
+                        eth_addr: None,
+                        power,
+                    })
+                    .collect::<Vec<_>>()
+            );
+
+            let page = core
+                .get_voting_powers_response(None, 2, state)
+                .unwrap_infallible();
+            assert_eq!(page.total, 3);
+            assert_eq!(
+                page.powers
+                    .iter()
+                    .map(|power| power.power)
+                    .collect::<Vec<_>>(),
+                vec![3_000_000, 2_000_000]
+            );
+            assert_eq!(page.next, Some(delegate_users[1].address().to_string()));
+
+            let page = core
+                .get_voting_powers_response(Some(delegate_users[1].address()), 2, state)
+                .unwrap_infallible();
+            assert_eq!(
+                page.powers,
+                vec![VotingPowerResponse {
+                    addr: delegate_users[2].address().to_string(),
+                    eth_addr: None,
+                    power: 1_000_000,
+                }]
+            );
+            assert_eq!(page.next, None);
+        }),
+    });
+}
+
+#[test]
+fn update_multisig() {
+    let (
+        TestRoles {
+            admin,
+            relayer,
+            staker,
+            ..
+        },
+        mut runner,
+    ) = setup();
+
+    let owner_keys = (1u8..=3)
+        .map(|i| SigningKey::from_bytes(&[i; 32].into()).unwrap())
+        .collect::<Vec<_>>();
+    let owners = owner_keys
+        .iter()
+        .map(|key| filament_hub_eth::vk_to_eth_address(key.verifying_key()))
+        .collect::<Vec<_>>();
+    let owner_addresses = owners
+        .iter()
+        .map(|owner| format!("0x{}", hex::encode(owner)))
+        .collect::<Vec<_>>();
+    let wallet = [0x5a; 20];
+    let wallet_address = format!("0x{}", hex::encode(wallet));
+
+    // Block 101 holds the wallet contract, block 102 the account of the first owner.
+    let account = |code_hash: [u8; 32]| {
+        rlp::encode_list(&[
+            rlp::encode_u64(1),
+            rlp::encode_u64(0),
+            rlp::encode_bytes(&proof::keccak(&[])),
+            rlp::encode_bytes(&code_hash),
+        ])
+    };
+    let (wallet_root, wallet_proof) =
+        generate_test_trie(&wallet, account(proof::keccak(b"wallet code")));
+    let (owner_root, owner_proof) = generate_test_trie(&owners[0], account(proof::keccak(&[])));
+    let checkpoint = generate_test_eth_header(100, [0; 32], [0; 32]);
+    let wallet_header = generate_test_eth_header(101, proof::keccak(&checkpoint), wallet_root);
+    let owner_header = generate_test_eth_header(102, proof::keccak(&wallet_header), owner_root);
+
+    runner.execute_transaction(TransactionTestCase {
+        input: relayer.create_plain_message::<Core<S>>(CallMessage::SubmitEthHeaders {
+            headers: vec![wallet_header, owner_header],
+        }),
+        assert: Box::new(move |result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    // Confirm that only a registered relayer can sync multisigs.
+    {
+        let owner_addresses = owner_addresses.clone();
+        let staker_addr = staker.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: staker.create_plain_message::<Core<S>>(CallMessage::UpdateMultisig {
+                address: wallet_address.clone(),
+                owners: owner_addresses,
+                threshold: 2,
+                eth_block: 101,
+                account_proof: wallet_proof.clone(),
+            }),
+            assert: Box::new(move |result, _state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "sender '{}' is not a registered relayer",
+                            staker_addr,
+                        )),
+                    })
+                );
+            }),
+        });
+    }
+
+    // Confirm that the threshold has to be reachable.
+    {
+        let owner_addresses = owner_addresses.clone();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateMultisig {
+                address: wallet_address.clone(),
+                owners: owner_addresses,
+                threshold: 4,
+                eth_block: 101,
+                account_proof: wallet_proof.clone(),
+            }),
+            assert: Box::new(move |result, _state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "threshold 4 must be between 1 and the number of owners 3"
+                        )),
+                    })
+                );
+            }),
+        });
+    }
+
+    // Confirm that a single relayer can't sync owner sets of accounts without code.
+    {
+        let owner_addresses = owner_addresses.clone();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateMultisig {
+                address: owner_addresses[0].clone(),
+                owners: owner_addresses[1..].to_vec(),
+                threshold: 1,
+                eth_block: 102,
+                account_proof: owner_proof.clone(),
+            }),
+            assert: Box::new(move |result, _state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "account '{}' has no code at eth block 102",
+                            owner_addresses[0],
+                        )),
+                    })
+                );
+            }),
+        });
+    }
+    {
+        let owner_addresses = owner_addresses.clone();
+        let wallet_address = wallet_address.clone();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateMultisig {
+                address: wallet_address.clone(),
+                owners: owner_addresses,
+                threshold: 2,
+                eth_block: 102,
+                account_proof: owner_proof,
+            }),
+            assert: Box::new(move |result, _state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "account '{}' does not exist at eth block 102",
+                            wallet_address,
+                        )),
+                    })
+                );
+            }),
+        });
+    }
+
+    {
+        let owner_addresses = owner_addresses.clone();
+        let owners = owners.clone();
+        let wallet_address = wallet_address.clone();
+        let relayer_addr = relayer.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateMultisig {
+                address: wallet_address.to_uppercase().replace("0X", "0x"),
+                owners: owner_addresses
+                    .iter()
+                    .map(|owner| owner.to_uppercase().replace("0X", "0x"))
+                    .collect(),
+                threshold: 2,
+                eth_block: 101,
+                account_proof: wallet_proof.clone(),
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(
+                    result.events,
+                    vec![TestCoreRuntimeEvent::Core(Event::MultisigUpdated {
+                        address: wallet_address.clone(),
+                        owners: owner_addresses.clone(),
+                        threshold: 2,
+                        eth_block: 101,
+                        relayer: relayer_addr,
+                    })]
+                );
+
+                let core = Core::<S>::default();
+                assert_eq!(
+                    core.get_multisig(&wallet_address, state)
+                        .unwrap_infallible(),
+                    Some(Multisig {
+                        owners: owner_addresses,
+                        threshold: 2,
+                        eth_block: 101,
+                    })
+                );
+                assert_eq!(
+                    core.multisig_owners(&wallet, state).unwrap(),
+                    Some(filament_hub_eth::multisig::Owners {
+                        owners,
+                        threshold: 2,
+                    })
+                );
+            }),
+        });
+    }
+
+    // Confirm that stale owner sets are rejected.
+    {
+        let wallet_address = wallet_address.clone();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateMultisig {
+                address: wallet_address.clone(),
+                owners: owner_addresses[..1].to_vec(),
+                threshold: 1,
+                eth_block: 101,
+                account_proof: wallet_proof,
+            }),
+            assert: Box::new(move |result, _state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "eth block 101 is not newer than the last synced eth block 101 for \
+                             multisig '{}'",
+                            wallet_address,
+                        )),
+                    })
+                );
+            }),
+        });
+    }
+
+    // In attestation mode owner sets need the threshold of relayers instead of a proof.
+    runner.execute_transaction(TransactionTestCase {
+        input: admin.create_plain_message::<Core<S>>(CallMessage::RegisterRelayer {
+            address: staker.address(),
+        }),
+        assert: Box::new(move |result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+    runner.execute_transaction(TransactionTestCase {
+        input: admin
+            .create_plain_message::<Core<S>>(CallMessage::SetAttestationThreshold { threshold: 2 }),
+        assert: Box::new(move |result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+    {
+        let wallet_address = wallet_address.clone();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateMultisig {
+                address: wallet_address.clone(),
+                owners: owner_addresses[..2].to_vec(),
+                threshold: 1,
+                eth_block: 103,
+                account_proof: vec![],
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(result.events, vec![]);
+                assert_eq!(
+                    Core::<S>::default()
+                        .get_multisig(&wallet_address, state)
+                        .unwrap_infallible()
+                        .map(|multisig| multisig.eth_block),
+                    Some(101)
+                );
+            }),
+        });
+    }
+    {
+        let owner_addresses = owner_addresses.clone();
+        let wallet_address = wallet_address.clone();
+        let staker_addr = staker.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: staker.create_plain_message::<Core<S>>(CallMessage::UpdateMultisig {
+                address: wallet_address.clone(),
+                owners: owner_addresses[..2].to_vec(),
+                threshold: 1,
+                eth_block: 103,
+                account_proof: vec![],
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(
+                    result.events,
+                    vec![TestCoreRuntimeEvent::Core(Event::MultisigUpdated {
+                        address: wallet_address.clone(),
+                        owners: owner_addresses[..2].to_vec(),
+                        threshold: 1,
+                        eth_block: 103,
+                        relayer: staker_addr,
+                    })]
+                );
+                assert_eq!(
+                    Core::<S>::default()
+                        .get_multisig(&wallet_address, state)
+                        .unwrap_infallible()
+                        .map(|multisig| multisig.eth_block),
+                    Some(103)
+                );
+            }),
+        });
+    }
+
+    // Confirm that transactions of the wallet need signatures of the threshold of owners.
+    let owners = filament_hub_eth::multisig::Owners {
+        owners,
+        threshold: 2,
+    };
+    let mut tx = filament_hub_eth::multisig::MultisigTx::<S> {
+        wallet,
+        signatures: vec![],
+        runtime_msg: vec![1, 2, 3],
+        nonce: 0,
+        details: TxDetails {
+            chain_id: filament_hub_eth::CHAIN_ID,
+            max_priority_fee_bips: PriorityFeeBips::ZERO,
+            max_fee: 100,
+            gas_limit: None,
+        },
+    };
+    let sign = |key: &SigningKey, tx: &filament_hub_eth::multisig::MultisigTx<S>| {
+        filament_hub_eth::sign(key, tx.signing_msg().unwrap())
+            .unwrap()
+            .to_vec()
+    };
+    let outsider = SigningKey::from_bytes(&[9u8; 32].into()).unwrap();
+
+    tx.signatures = vec![sign(&owner_keys[0], &tx), sign(&owner_keys[0], &tx)];
+    assert!(tx.verify(&owners).is_err());
+    tx.signatures = vec![sign(&owner_keys[0], &tx), sign(&outsider, &tx)];
+    assert!(tx.verify(&owners).is_err());
+    tx.signatures = vec![sign(&owner_keys[0], &tx), sign(&owner_keys[2], &tx)];
+    assert!(tx.verify(&owners).is_ok());
+
+    // Confirm that signatures beyond the number of owners aren't recovered.
+    tx.signatures = vec![sign(&owner_keys[0], &tx); 4];
+    assert!(tx
+        .verify(&owners)
+        .is_err_and(|e| e.to_string() == "4 signatures exceed the 3 owners of the wallet"));
+
+    // The wallet acts from an address apart from the Ethereum key with the same address.
+    assert_ne!(
+        filament_hub_eth::multisig::wallet_to_hub_address::<S>(&wallet).unwrap(),
+        filament_hub_eth::bytes_to_address::<S>(wallet).unwrap()
+    );
+}
+
+#[test]
+fn session_keys() {
+    let (
+        TestRoles {
+            campaigner, staker, ..
+        },
+        mut runner,
+    ) = setup();
+
+    let session_key = SigningKey::from_bytes(&[11u8; 32].into()).unwrap();
+    let key = format!(
+        "0x{}",
+        hex::encode(filament_hub_eth::vk_to_eth_address(
+            session_key.verifying_key()
+        ))
+    );
+    let draft = CallMessage::<S>::Draft {
+        title: "".to_string(),
+        description: "".to_string(),
+        criteria: generate_test_criteria(),
+        evictions: vec![],
+    };
+
+    // Grants are signed by the eth key linked to the granting account.
+    let campaigner_eth_key = SigningKey::from_bytes(&[7u8; 32].into()).unwrap();
+    let staker_eth_key = SigningKey::from_bytes(&[12u8; 32].into()).unwrap();
+    let eth_address = |signing_key: &SigningKey| {
+        format!(
+            "0x{}",
+            hex::encode(filament_hub_eth::vk_to_eth_address(
+                signing_key.verifying_key()
+            ))
+        )
+    };
+    let sign_grant = |signing_key: &SigningKey,
+                      addr: <S as Spec>::Address,
+                      key: &str,
+                      allowed_calls: &[SessionCall],
+                      expires_at_slot: u64| {
+        filament_hub_eth::sign(
+            signing_key,
+            filament_hub_eth::session::grant_session_key_msg(
+                &addr.to_string(),
+                key,
+                &allowed_calls
+                    .iter()
+                    .map(SessionCall::name)
+                    .collect::<Vec<_>>(),
+                expires_at_slot,
+                filament_hub_eth::CHAIN_ID,
+            ),
+        )
+        .unwrap()
+        .to_vec()
+    };
+
+    for (user, signing_key) in [
+        (&campaigner, &campaigner_eth_key),
+        (&staker, &staker_eth_key),
+    ] {
+        runner.execute_transaction(TransactionTestCase {
+            input: user.create_plain_message::<Core<S>>(CallMessage::LinkEthAddress {
+                eth_address: eth_address(signing_key),
+                signature: filament_hub_eth::sign(
+                    signing_key,
+                    filament_hub_eth::link_eth_address_msg(
+                        &user.address().to_string(),
+                        filament_hub_eth::CHAIN_ID,
+                    ),
+                )
+                .unwrap()
+                .to_vec(),
+            }),
+            assert: Box::new(move |result, _state| {
+                assert!(result.tx_receipt.is_successful());
+            }),
+        });
+    }
+
+    // Confirm that the grant has to be signed by the eth key of the sender.
+    {
+        let campaigner_addr = campaigner.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: campaigner.create_plain_message::<Core<S>>(CallMessage::GrantSessionKey {
+                key: key.clone(),
+                allowed_calls: vec![SessionCall::Draft],
+                expires_at_slot: u64::MAX,
+                signature: sign_grant(
+                    &staker_eth_key,
+                    campaigner.address(),
+                    &key,
+                    &[SessionCall::Draft],
+                    u64::MAX,
+                ),
+            }),
+            assert: Box::new(move |result, _state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "session key grant is not signed by the eth key of '{}'",
+                            campaigner_addr,
+                        )),
+                    })
+                );
+            }),
+        });
+    }
+
+    // Confirm that the signature covers the allowed calls.
+    {
+        let campaigner_addr = campaigner.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: campaigner.create_plain_message::<Core<S>>(CallMessage::GrantSessionKey {
+                key: key.clone(),
+                allowed_calls: vec![SessionCall::Draft, SessionCall::Init],
+                expires_at_slot: u64::MAX,
+                signature: sign_grant(
+                    &campaigner_eth_key,
+                    campaigner.address(),
+                    &key,
+                    &[SessionCall::Draft],
+                    u64::MAX,
+                ),
+            }),
+            assert: Box::new(move |result, _state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "session key grant is not signed by the eth key of '{}'",
+                            campaigner_addr,
+                        )),
+                    })
+                );
+            }),
+        });
+    }
+
+    // Confirm that the expiry has to be in the future.
+    runner.execute_transaction(TransactionTestCase {
+        input: campaigner.create_plain_message::<Core<S>>(CallMessage::GrantSessionKey {
+            key: key.clone(),
+            allowed_calls: vec![SessionCall::Draft],
+            expires_at_slot: 0,
+            signature: sign_grant(
+                &campaigner_eth_key,
+                campaigner.address(),
+                &key,
+                &[SessionCall::Draft],
+                0,
+            ),
+        }),
+        assert: Box::new(move |result, _state| match &result.tx_receipt {
+            TxEffect::Reverted(contents) => assert!(contents
+                .reason
+                .to_string()
+                .contains("session key expiry slot 0 is not after the current slot")),
+            receipt => panic!("unexpected receipt {receipt:?}"),
+        }),
+    });
+
+    {
+        let key = key.clone();
+        let campaigner_addr = campaigner.address();
+        let draft = draft.clone();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: campaigner.create_plain_message::<Core<S>>(CallMessage::GrantSessionKey {
+                key: key.to_uppercase().replace("0X", "0x"),
+                allowed_calls: vec![SessionCall::Draft],
+                expires_at_slot: u64::MAX,
+                signature: sign_grant(
+                    &campaigner_eth_key,
+                    campaigner.address(),
+                    &key,
+                    &[SessionCall::Draft],
+                    u64::MAX,
+                ),
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(
+                    result.events,
+                    vec![TestCoreRuntimeEvent::Core(Event::SessionKeyGranted {
+                        addr: campaigner_addr,
+                        key: key.clone(),
+                        allowed_calls: vec![SessionCall::Draft],
+                        expires_at_slot: u64::MAX,
+                    })]
+                );
+
+                let core = Core::<S>::default();
+                assert_eq!(
+                    core.get_session_key(&key, state).unwrap_infallible(),
+                    Some(SessionKey {
+                        owner: campaigner_addr,
+                        allowed_calls: vec![SessionCall::Draft],
+                        expires_at_slot: u64::MAX,
+                    })
+                );
+
+                // The authenticator resolves the owner only for calls the key may sign.
+                let key_bytes = filament_hub_eth::parse_eth_address(&key).unwrap();
+                assert_eq!(
+                    core.session_owner(&key_bytes, &draft, state).unwrap(),
+                    Some(campaigner_addr)
+                );
+                assert!(core
+                    .session_owner(&key_bytes, &CallMessage::Init { campaign_id: 1 }, state)
+                    .is_err_and(|e| e.to_string()
+                        == format!("call 'init' is not allowed for session key '{key}'")));
+                assert!(core
+                    .session_owner(&key_bytes, &CallMessage::UnlinkEthAddress {}, state)
+                    .is_err_and(|e| e.to_string()
+                        == format!(
+                            "call 'unlink_eth_address' is not allowed for session key '{key}'"
+                        )));
+            }),
+        });
+    }
+
+    // Confirm that a session key belongs to a single account.
+    {
+        let key = key.clone();
+        let campaigner_addr = campaigner.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: staker.create_plain_message::<Core<S>>(CallMessage::GrantSessionKey {
+                key: key.clone(),
+                allowed_calls: vec![SessionCall::Draft],
+                expires_at_slot: u64::MAX,
+                signature: sign_grant(
+                    &staker_eth_key,
+                    staker.address(),
+                    &key,
+                    &[SessionCall::Draft],
+                    u64::MAX,
+                ),
+            }),
+            assert: Box::new(move |result, _state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "session key '{}' is already granted to '{}'",
+                            key,
+                            campaigner_addr,
+                        )),
+                    })
+                );
+            }),
+        });
+    }
+
+    // Confirm that allowed calls are executed for the owner.
+    {
+        let campaigner_addr = campaigner.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: campaigner.create_plain_message::<Core<S>>(CallMessage::ExecuteSession {
+                key: key.clone(),
+                call: Box::new(draft.clone()),
+            }),
+            assert: Box::new(move |result, _state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(
+                    result.events,
+                    vec![TestCoreRuntimeEvent::Core(Event::CampaignDrafted {
+                        campaign_id: 2,
+                        campaigner: campaigner_addr,
+                        evictions: vec![],
+                    })]
+                );
+            }),
+        });
+    }
+
+    // Confirm that other calls are rejected.
+    {
+        let key = key.clone();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: campaigner.create_plain_message::<Core<S>>(CallMessage::ExecuteSession {
+                key: key.clone(),
+                call: Box::new(CallMessage::Init { campaign_id: 2 }),
+            }),
+            assert: Box::new(move |result, _state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "call 'init' is not allowed for session key '{}'",
+                            key,
+                        )),
+                    })
+                );
+            }),
+        });
+    }
+
+    // Confirm that the session key can't act for other accounts.
+    {
+        let key = key.clone();
+        let staker_addr = staker.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: staker.create_plain_message::<Core<S>>(CallMessage::ExecuteSession {
+                key: key.clone(),
+                call: Box::new(draft.clone()),
+            }),
+            assert: Box::new(move |result, _state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "session key '{}' is not granted to '{}'",
+                            key,
+                            staker_addr,
+                        )),
+                    })
+                );
+            }),
+        });
+    }
+
+    // Confirm that expired keys are rejected by the authenticator and at execution. Every
+    // transaction runs in a slot of its own, so the grant below is in slot 11.
+    let expiring_key = eth_address(&SigningKey::from_bytes(&[13u8; 32].into()).unwrap());
+    {
+        let expiring_key = expiring_key.clone();
+        let draft = draft.clone();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: campaigner.create_plain_message::<Core<S>>(CallMessage::GrantSessionKey {
+                key: expiring_key.clone(),
+                allowed_calls: vec![SessionCall::Draft],
+                expires_at_slot: 12,
+                signature: sign_grant(
+                    &campaigner_eth_key,
+                    campaigner.address(),
+                    &expiring_key,
+                    &[SessionCall::Draft],
+                    12,
+                ),
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert!(Core::<S>::default()
+                    .session_owner(
+                        &filament_hub_eth::parse_eth_address(&expiring_key).unwrap(),
+                        &draft,
+                        state
+                    )
+                    .unwrap()
+                    .is_some());
+            }),
+        });
+    }
+    {
+        let expiring_key = expiring_key.clone();
+        let draft = draft.clone();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: campaigner.create_plain_message::<Core<S>>(CallMessage::ExecuteSession {
+                key: key.clone(),
+                call: Box::new(draft.clone()),
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert!(Core::<S>::default()
+                    .session_owner(
+                        &filament_hub_eth::parse_eth_address(&expiring_key).unwrap(),
+                        &draft,
+                        state
+                    )
+                    .is_err_and(|e| e.to_string()
+                        == format!("session key '{expiring_key}' expired at slot 12")));
+            }),
+        });
+    }
+    {
+        let expiring_key = expiring_key.clone();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: campaigner.create_plain_message::<Core<S>>(CallMessage::ExecuteSession {
+                key: expiring_key.clone(),
+                call: Box::new(draft.clone()),
+            }),
+            assert: Box::new(move |result, _state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "session key '{}' expired at slot 12",
+                            expiring_key,
+                        )),
+                    })
+                );
+            }),
+        });
+    }
+
+    {
+        let key = key.clone();
+        let campaigner_addr = campaigner.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: campaigner.create_plain_message::<Core<S>>(CallMessage::RevokeSessionKey {
+                key: key.clone(),
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(
+                    result.events,
+                    vec![TestCoreRuntimeEvent::Core(Event::SessionKeyRevoked {
+                        addr: campaigner_addr,
+                        key: key.clone(),
+                    })]
+                );
+                assert_eq!(
+                    Core::<S>::default()
+                        .get_session_key(&key, state)
+                        .unwrap_infallible(),
+                    None
+                );
+            }),
+        });
+    }
+
+    // Confirm that a revoked session key can't be used.
+    {
+        let campaigner_addr = campaigner.address();
+
+        runner.execute_transaction(TransactionTestCase {
+            input: campaigner.create_plain_message::<Core<S>>(CallMessage::ExecuteSession {
+                key: key.clone(),
+                call: Box::new(draft),
+            }),
+            assert: Box::new(move |result, _state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "session key '{}' is not granted to '{}'",
+                            key,
+                            campaigner_addr,
+                        )),
+                    })
+                );
+            }),
+        });
+    }
+}
+
+#[test]
+fn delegate_rewards() {
+    let (
+        TestRoles {
+            admin,
+            campaigner,
+            delegate_users,
+            indexer,
+            relayer,
+            staker,
+            ..
+        },
+        mut runner,
+    ) = setup();
+
+    let criteria = {
+        let mut criteria = generate_test_criteria();
+        criteria.push(Criterion {
+            name: "Another Test Criterion".to_string(),
+            category: CriterionCategory::Defi,
+            parameters: Default::default(),
+            weight: 1,
+        });
+        criteria
+    };
+
+    // Drive a fresh campaign into the distribution phase.
+    runner.execute_transaction(TransactionTestCase {
+        input: campaigner.create_plain_message::<Core<S>>(CallMessage::Draft {
+            title: "".to_string(),
+            description: "".to_string(),
+            criteria,
+            evictions: vec![],
+        }),
+        assert: Box::new(move |result, _| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+    runner.execute_transaction(TransactionTestCase {
+        input: campaigner.create_plain_message::<Core<S>>(CallMessage::Init { campaign_id: 2 }),
+        assert: Box::new(move |result, _| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+    for (delegate, weights) in delegate_users
+        .iter()
+        .zip([vec![1, 1], vec![0, 1], vec![1, 0]])
+    {
+        runner.execute_transaction(TransactionTestCase {
+            input: delegate.create_plain_message::<Core<S>>(CallMessage::VoteCriteria {
+                campaign_id: 2,
+                vote: CriteriaVote::Approved { weights },
+            }),
+            assert: Box::new(move |result, _| {
+                assert!(result.tx_receipt.is_successful());
+            }),
+        });
+    }
+    for msg in [
+        CallMessage::ConfirmCriteria {
+            campaign_id: 2,
+            proposal_id: None,
+        },
+        CallMessage::IndexCampaign { campaign_id: 2 },
+        CallMessage::PostSegment {
+            campaign_id: 2,
+            segment: generate_test_segment(),
+        },
+    ] {
+        runner.execute_transaction(TransactionTestCase {
+            input: campaigner.create_plain_message::<Core<S>>(msg),
+            assert: Box::new(move |result, _| {
+                assert!(result.tx_receipt.is_successful());
+            }),
+        });
+    }
+
+    // Balances of the delegates before the rewards are paid out.
+    let balances = Arc::new(Mutex::new(Vec::new()));
+    {
+        let campaigner = campaigner.clone();
+        let delegate_users = delegate_users.clone();
+        let balances = balances.clone();
+        runner.execute_transaction(TransactionTestCase {
+            input: campaigner.create_plain_message::<Core<S>>(CallMessage::FundRewardPool {
+                campaign_id: 2,
+                amount: 1000,
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(
+                    result.events,
+                    vec![TestCoreRuntimeEvent::Core(Event::RewardPoolFunded {
+                        campaign_id: 2,
+                        amount: 1000,
+                        total: 1000,
+                    })]
+                );
+                assert_eq!(
+                    Core::<S>::default()
+                        .get_reward_pool(2, state)
+                        .unwrap_infallible(),
+                    Some(RewardPool {
+                        funder: campaigner.address(),
+                        amount: 1000,
+                    })
+                );
+
+                // The pool is escrowed by the module.
+                assert_eq!(escrow_balance(state), 1000);
+                *balances.lock().unwrap() = delegate_users
+                    .iter()
+                    .map(|user| gas_balance(&user.address(), state))
+                    .collect();
+            }),
+        });
+    }
+
+    // Funding fails if the campaigner can't pay for the pool.
+    {
+        let campaigner = campaigner.clone();
+        runner.execute_transaction(TransactionTestCase {
+            input: campaigner.create_plain_message::<Core<S>>(CallMessage::FundRewardPool {
+                campaign_id: 2,
+                amount: u64::MAX - 1000,
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(matches!(result.tx_receipt, TxEffect::Reverted(_)));
+                assert_eq!(escrow_balance(state), 1000);
+            }),
+        });
+    }
+
+    let measurement = ObjectiveMeasurement {
+        attributions: vec![100, 20],
+    };
+
+    // Only registered relayers can post objective measurements.
+    {
+        runner.execute_transaction(TransactionTestCase {
+            input: staker.create_plain_message::<Core<S>>(CallMessage::PostObjective {
+                campaign_id: 2,
+                measurement: measurement.clone(),
+            }),
+            assert: Box::new(move |result, _state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "sender '{}' is not a registered relayer",
+                            staker.address(),
+                        )),
+                    })
+                );
+            }),
+        });
+    }
+
+    // Objectives need a quorum of relayers, so they can't be posted without attestation mode.
+    {
+        runner.execute_transaction(TransactionTestCase {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::PostObjective {
+                campaign_id: 2,
+                measurement: measurement.clone(),
+            }),
+            assert: Box::new(move |result, state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!("attestation mode is disabled")),
+                    })
+                );
+                assert_eq!(escrow_balance(state), 1000);
+            }),
+        });
+    }
+
+    runner.execute_transaction(TransactionTestCase {
+        input: admin.create_plain_message::<Core<S>>(CallMessage::RegisterRelayer {
+            address: indexer.address(),
+        }),
+        assert: Box::new(move |result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+    runner.execute_transaction(TransactionTestCase {
+        input: admin
+            .create_plain_message::<Core<S>>(CallMessage::SetAttestationThreshold { threshold: 2 }),
+        assert: Box::new(move |result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    // A single relayer can't release the escrow, its measurement stays pending.
+    {
+        let relayer = relayer.clone();
+        let delegate_users = delegate_users.clone();
+        let balances = balances.clone();
+        runner.execute_transaction(TransactionTestCase {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::PostObjective {
+                campaign_id: 2,
+                measurement: measurement.clone(),
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(
+                    result.events,
+                    vec![TestCoreRuntimeEvent::Core(Event::ObjectiveAttested {
+                        campaign_id: 2,
+                        relayer: relayer.address(),
+                        attestations: 1,
+                    })]
+                );
+
+                assert_eq!(escrow_balance(state), 1000);
+                let balances = balances.lock().unwrap();
+                for (user, before) in delegate_users.iter().zip(balances.iter()) {
+                    assert_eq!(gas_balance(&user.address(), state), *before);
+                }
+                assert_eq!(
+                    Core::<S>::default()
+                        .get_objective(2, state)
+                        .unwrap_infallible(),
+                    None
+                );
+            }),
+        });
+    }
+
+    // Posting again doesn't count twice.
+    {
+        let relayer = relayer.clone();
+        runner.execute_transaction(TransactionTestCase {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::PostObjective {
+                campaign_id: 2,
+                measurement: measurement.clone(),
+            }),
+            assert: Box::new(move |result, state| {
+                assert_eq!(
+                    result.tx_receipt,
+                    TxEffect::Reverted(RevertedTxContents {
+                        gas_used: GasUnit::from([100, 100]),
+                        reason: Error::ModuleError(anyhow!(
+                            "relayer '{}' already posted an objective for campaign '2'",
+                            relayer.address(),
+                        )),
+                    })
+                );
+                assert_eq!(escrow_balance(state), 1000);
+            }),
+        });
+    }
+
+    // Delegate 0 and 2 moved the aggregated criteria towards the more valuable criterion, while
+    // delegate 1 pulled it away and therefore receives nothing.
+    let expected = {
+        let mut expected = vec![
+            DelegateReward {
+                delegate: delegate_users[0].address().to_string(),
+                contribution: 6_666_647,
+                reward: 416,
+            },
+            DelegateReward {
+                delegate: delegate_users[1].address().to_string(),
+                contribution: 0,
+                reward: 0,
+            },
+            DelegateReward {
+                delegate: delegate_users[2].address().to_string(),
+                contribution: 9_333_313,
+                reward: 583,
+            },
+        ];
+        expected.sort_by(|a, b| a.delegate.cmp(&b.delegate));
+        expected
+    };
+
+    // The second relayer posting the same measurement reaches the threshold and pays out.
+    {
+        let measurement = measurement.clone();
+        let delegate_users = delegate_users.clone();
+        runner.execute_transaction(TransactionTestCase {
+            input: indexer.create_plain_message::<Core<S>>(CallMessage::PostObjective {
+                campaign_id: 2,
+                measurement: measurement.clone(),
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(
+                    result.events,
+                    vec![
+                        TestCoreRuntimeEvent::Core(Event::ObjectiveAttested {
+                            campaign_id: 2,
+                            relayer: indexer.address(),
+                            attestations: 2,
+                        }),
+                        TestCoreRuntimeEvent::Core(Event::ObjectivePosted {
+                            campaign_id: 2,
+                            relayer: indexer.address(),
+                        }),
+                        TestCoreRuntimeEvent::Core(Event::DelegateRewardsDistributed {
+                            campaign_id: 2,
+                            rewards: expected.clone(),
+                            refund: 1,
+                        }),
+                    ]
+                );
+
+                // Every delegate is paid its reward and the rounding dust goes back to the
+                // funder, leaving nothing in escrow.
+                assert_eq!(escrow_balance(state), 0);
+                let balances = balances.lock().unwrap();
+                for (user, before) in delegate_users.iter().zip(balances.iter()) {
+                    let reward = expected
+                        .iter()
+                        .find(|reward| reward.delegate == user.address().to_string())
+                        .unwrap()
+                        .reward;
+                    assert_eq!(gas_balance(&user.address(), state), before + reward);
+                }
+
+                assert_eq!(
+                    Core::<S>::default()
+                        .get_objective(2, state)
+                        .unwrap_infallible(),
+                    Some(measurement)
+                );
+                assert_eq!(
+                    Core::<S>::default()
+                        .get_delegate_rewards(2, state)
+                        .unwrap_infallible(),
+                    expected
+                );
+            }),
+        });
+    }
+
+    // Objectives can only be posted once.
+    runner.execute_transaction(TransactionTestCase {
+        input: relayer.create_plain_message::<Core<S>>(CallMessage::PostObjective {
+            campaign_id: 2,
+            measurement,
+        }),
+        assert: Box::new(move |result, _state| {
+            assert_eq!(
+                result.tx_receipt,
+                TxEffect::Reverted(RevertedTxContents {
+                    gas_used: GasUnit::from([100, 100]),
+                    reason: Error::ModuleError(anyhow!(
+                        "objective for campaign '2' already posted"
+                    )),
+                })
+            );
+        }),
+    });
+}
+
+#[test]
+fn voting_power_snapshots() {
+    let (
+        TestRoles {
+            campaigner,
+            delegate_users,
+            relayer,
+            ..
+        },
+        mut runner,
+    ) = setup();
+
+    let delegate = delegate_users[0].address();
+
+    // Draft a campaign, which snapshots the current powers.
+    runner.execute_transaction(TransactionTestCase {
+        input: campaigner.create_plain_message::<Core<S>>(CallMessage::Draft {
+            title: "".to_string(),
+            description: "".to_string(),
+            criteria: generate_test_criteria(),
+            evictions: vec![],
+        }),
+        assert: Box::new(move |result, state| {
+            assert!(result.tx_receipt.is_successful());
+
+            // Genesis powers are checkpointed at slot 0.
+            assert_eq!(
+                Core::<S>::default()
+                    .get_power_checkpoints(delegate, state)
+                    .unwrap_infallible(),
+                vec![PowerCheckpoint {
+                    slot: 0,
+                    power: 3_000_000,
+                }]
+            );
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateVotingPowerAt {
+            address: delegate,
+            power: 1000,
+            eth_block: 1,
+        }),
+        assert: Box::new(move |result, _| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateVotingPowerAt {
+            address: delegate,
+            power: 2000,
+            eth_block: 2,
+        }),
+        assert: Box::new(move |result, state| {
+            assert!(result.tx_receipt.is_successful());
+
+            let core = Core::<S>::default();
+            let checkpoints = core
+                .get_power_checkpoints(delegate, state)
+                .unwrap_infallible();
+            assert_eq!(
+                checkpoints.iter().map(|c| c.power).collect::<Vec<_>>(),
+                vec![3_000_000, 1000, 2000]
+            );
+            assert!(checkpoints.windows(2).all(|w| w[0].slot < w[1].slot));
+
+            // Powers are looked up by the latest checkpoint at or before the slot.
+            for checkpoint in &checkpoints {
+                assert_eq!(
+                    core.get_voting_power_at(delegate, checkpoint.slot, state)
+                        .unwrap_infallible(),
+                    checkpoint.power
+                );
+            }
+            assert_eq!(
+                core.get_voting_power_at(delegate, checkpoints[1].slot - 1, state)
+                    .unwrap_infallible(),
+                3_000_000
+            );
+            assert_eq!(
+                core.get_voting_power_at(delegate, u64::MAX, state)
+                    .unwrap_infallible(),
+                2000
+            );
+
+            // The campaign powers can be recomputed from the history at its snapshot slot.
+            let campaign = core.get_campaign(2, state).unwrap_infallible().unwrap();
+            assert!(campaign.snapshot_slot < checkpoints[1].slot);
+            assert_eq!(
+                core.get_total_voting_power_at(campaign.snapshot_slot, state)
+                    .unwrap_infallible(),
+                6_000_000
+            );
+            assert_eq!(
+                core.get_total_voting_power_at(checkpoints[1].slot, state)
+                    .unwrap_infallible(),
+                3_001_000
+            );
+            assert_eq!(
+                core.get_total_voting_power(state).unwrap_infallible(),
+                3_002_000
+            );
+            assert_eq!(
+                core.get_campaign_powers(2, state).unwrap_infallible(),
+                Some(campaign.delegates)
+            );
+        }),
+    });
+}
+
+#[test]
+fn voting_powers_pagination() {
+    let stakers = (0..20)
+        .map(|i| generate_address::<S>(&format!("staker-{i}")))
+        .collect::<Vec<_>>();
+    let (
+        TestRoles {
+            delegate_users,
+            relayer,
+            ..
+        },
+        mut runner,
+    ) = setup_with_powers(
+        stakers
+            .iter()
+            .enumerate()
+            .map(|(i, addr)| (*addr, 100 * (i as u64 % 5)))
+            .collect(),
+    );
+
+    runner.execute_transaction(TransactionTestCase {
+        input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateVotingPowerAt {
+            address: stakers[0],
+            power: 2_500_000,
+            eth_block: 1,
+        }),
+        assert: Box::new(move |result, state| {
+            assert!(result.tx_receipt.is_successful());
+
+            let core = Core::<S>::default();
+            assert_eq!(core.get_voting_powers_count(state).unwrap_infallible(), 23);
+
+            let top = core.get_voting_powers(None, 3, state).unwrap_infallible();
+            assert_eq!(
+                top,
+                vec![
+                    (delegate_users[0].address(), 3_000_000),
+                    (stakers[0], 2_500_000),
+                    (delegate_users[1].address(), 2_000_000),
+                ]
+            );
+
+            // Walking all pages yields every power exactly once, ordered from highest to lowest.
+            let mut all = top;
+            loop {
+                let after = all.last().map(|(addr, _)| *addr);
+                let page = core.get_voting_powers(after, 4, state).unwrap_infallible();
+                if page.is_empty() {
+                    break;
+                }
+                assert!(page.len() <= 4);
+                all.extend(page);
+            }
+            assert_eq!(all.len(), 23);
+            assert!(all.windows(2).all(|w| w[0].1 >= w[1].1));
+            assert_eq!(
+                all.iter()
+                    .map(|(addr, _)| *addr)
+                    .collect::<HashSet<_>>()
+                    .len(),
+                23
+            );
+            for (addr, power) in &all {
+                assert_eq!(
+                    core.get_voting_power(*addr, state).unwrap_infallible(),
+                    *power
+                );
+            }
+            assert_total_voting_power(core.get_total_voting_power(state).unwrap_infallible(), &all);
+        }),
+    });
+}
+
+#[test]
+fn campaigns_pagination() {
+    let (
+        TestRoles {
+            campaigner,
+            delegate_users,
+            indexer,
+            staker,
+            ..
+        },
+        mut runner,
+    ) = setup();
+
+    for (sender, evictions) in [
+        (campaigner.clone(), vec![]),
+        (campaigner.clone(), vec![delegate_users[0].address()]),
+        (staker.clone(), vec![]),
+    ] {
+        runner.execute_transaction(TransactionTestCase {
+            input: sender.create_plain_message::<Core<S>>(CallMessage::Draft {
+                title: "".to_string(),
+                description: "".to_string(),
+                criteria: generate_test_criteria(),
+                evictions,
+            }),
+            assert: Box::new(move |result, _| {
+                assert!(result.tx_receipt.is_successful());
+            }),
+        });
+    }
+
+    // Take the first draft to indexing, which makes its campaigner its indexer.
+    runner.execute_transaction(TransactionTestCase {
+        input: campaigner.create_plain_message::<Core<S>>(CallMessage::Init { campaign_id: 2 }),
+        assert: Box::new(move |result, _| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+    for delegate in &delegate_users {
+        runner.execute_transaction(TransactionTestCase {
+            input: delegate.create_plain_message::<Core<S>>(CallMessage::VoteCriteria {
+                campaign_id: 2,
+                vote: CriteriaVote::Approved { weights: vec![1] },
+            }),
+            assert: Box::new(move |result, _| {
+                assert!(result.tx_receipt.is_successful());
+            }),
+        });
+    }
+    runner.execute_transaction(TransactionTestCase {
+        input: campaigner.create_plain_message::<Core<S>>(CallMessage::ConfirmCriteria {
+            campaign_id: 2,
+            proposal_id: None,
+        }),
+        assert: Box::new(move |result, _| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: campaigner
+            .create_plain_message::<Core<S>>(CallMessage::IndexCampaign { campaign_id: 2 }),
+        assert: Box::new(move |result, state| {
+            assert!(result.tx_receipt.is_successful());
+
+            let core = Core::<S>::default();
+            let mut page = |filter: &CampaignFilter<S>,
+                            sort: CampaignSort,
+                            order: SortOrder,
+                            after: Option<u64>,
+                            limit: usize| {
+                let page = core
+                    .get_campaigns_page(filter, sort, order, after, limit, state)
+                    .unwrap_infallible();
+                (
+                    page.campaigns
+                        .iter()
+                        .map(|campaign| campaign.id)
+                        .collect::<Vec<_>>(),
+                    page.next,
+                )
+            };
+            let all = CampaignFilter::default();
+
+            // Campaigns from genesis are indexed ahead of the drafted ones.
+            assert_eq!(
+                page(&all, CampaignSort::Id, SortOrder::Asc, None, 2),
+                (vec![0, 1], Some(1))
+            );
+            assert_eq!(
+                page(&all, CampaignSort::Id, SortOrder::Asc, Some(1), 2),
+                (vec![2, 3], Some(3))
+            );
+            assert_eq!(
+                page(&all, CampaignSort::Id, SortOrder::Asc, Some(3), 2),
+                (vec![4], None)
+            );
+            assert_eq!(
+                page(&all, CampaignSort::Id, SortOrder::Desc, None, 10),
+                (vec![4, 3, 2, 1, 0], None)
+            );
+            assert_eq!(
+                page(&all, CampaignSort::Id, SortOrder::Desc, Some(4), 1),
+                (vec![3], Some(3))
+            );
+            assert_eq!(
+                page(&all, CampaignSort::Id, SortOrder::Desc, Some(1), 1),
+                (vec![0], None)
+            );
+            assert_eq!(
+                page(&all, CampaignSort::Slot, SortOrder::Asc, None, 10),
+                (vec![0, 1, 2, 3, 4], None)
+            );
+            assert_eq!(
+                page(&all, CampaignSort::Slot, SortOrder::Desc, Some(3), 10),
+                (vec![2, 1, 0], None)
+            );
+            assert_eq!(
+                page(&all, CampaignSort::Id, SortOrder::Asc, Some(99), 10),
+                (vec![], None)
+            );
+
+            let by_phase = |phase| CampaignFilter {
+                phase: Some(phase),
+                ..Default::default()
+            };
+            for (phase, ids) in [
+                (Phase::Criteria, vec![0]),
+                (Phase::Distribution, vec![1]),
+                (Phase::Indexing, vec![2]),
+                (Phase::Draft, vec![3, 4]),
+                (Phase::Publish, vec![]),
+            ] {
+                assert_eq!(
+                    page(&by_phase(phase), CampaignSort::Id, SortOrder::Asc, None, 10),
+                    (ids, None)
+                );
+            }
+            assert_eq!(
+                page(
+                    &by_phase(Phase::Draft),
+                    CampaignSort::Id,
+                    SortOrder::Desc,
+                    None,
+                    1
+                ),
+                (vec![4], Some(4))
+            );
+
+            let by_campaigner = CampaignFilter {
+                campaigner: Some(campaigner.address()),
+                ..Default::default()
+            };
+            assert_eq!(
+                page(&by_campaigner, CampaignSort::Id, SortOrder::Asc, None, 10),
+                (vec![0, 1, 2, 3], None)
+            );
+
+            let by_delegate = CampaignFilter {
+                delegate: Some(delegate_users[0].address()),
+                ..Default::default()
+            };
+            assert_eq!(
+                page(&by_delegate, CampaignSort::Id, SortOrder::Asc, None, 10),
+                (vec![0, 1, 2, 4], None)
+            );
+            assert_eq!(
+                page(
+                    &CampaignFilter {
+                        campaigner: Some(campaigner.address()),
+                        ..by_delegate
+                    },
+                    CampaignSort::Id,
+                    SortOrder::Asc,
+                    Some(0),
+                    10
+                ),
+                (vec![1, 2], None)
+            );
+
+            let by_indexer = |indexer| CampaignFilter {
+                indexer: Some(indexer),
+                ..Default::default()
+            };
+            assert_eq!(
+                page(
+                    &by_indexer(indexer.address()),
+                    CampaignSort::Id,
+                    SortOrder::Asc,
+                    None,
+                    10
+                ),
+                (vec![0, 1], None)
+            );
+            assert_eq!(
+                page(
+                    &by_indexer(campaigner.address()),
+                    CampaignSort::Id,
+                    SortOrder::Asc,
+                    None,
+                    10
+                ),
+                (vec![2], None)
+            );
+            assert_eq!(
+                core.get_campaign(2, state)
+                    .unwrap_infallible()
+                    .unwrap()
+                    .indexer,
+                Some(campaigner.address())
+            );
+        }),
+    });
+}
+
+#[test]
+fn campaigns_sorted_by_slot() {
+    // The first genesis campaign is snapshotted after the slots the drafts are drafted at.
+    let (TestRoles { campaigner, .. }, mut runner) = setup_with_config(|config| {
+        config.campaigns[0].snapshot_slot = 1_000;
+    });
+
+    let draft = CallMessage::<S>::Draft {
+        title: "Slot".to_string(),
+        description: "".to_string(),
+        criteria: vec![Criterion {
+            name: "Test Criterion".to_string(),
+            category: CriterionCategory::Balance,
+            parameters: Default::default(),
+            weight: 1,
+        }],
+        evictions: vec![],
+    };
+    runner.execute_transaction(TransactionTestCase {
+        input: campaigner.create_plain_message::<Core<S>>(draft.clone()),
+        assert: Box::new(move |result, _| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    let address = campaigner.address();
+    runner.execute_transaction(TransactionTestCase {
+        input: campaigner.create_plain_message::<Core<S>>(draft),
+        assert: Box::new(move |result, state| {
+            assert!(result.tx_receipt.is_successful());
+
+            let core = Core::<S>::default();
+            let mut page = |filter: &CampaignFilter<S>, order, after, limit| {
+                let page = core
+                    .get_campaigns_page(filter, CampaignSort::Slot, order, after, limit, state)
+                    .unwrap_infallible();
+                (
+                    page.campaigns
+                        .iter()
+                        .map(|campaign| campaign.id)
+                        .collect::<Vec<_>>(),
+                    page.next,
+                )
+            };
+            let all = CampaignFilter::default();
+
+            assert_eq!(
+                page(&all, SortOrder::Asc, None, 10),
+                (vec![1, 2, 3, 0], None)
+            );
+            assert_eq!(page(&all, SortOrder::Asc, Some(2), 1), (vec![3], Some(3)));
+            assert_eq!(page(&all, SortOrder::Asc, Some(3), 10), (vec![0], None));
+            assert_eq!(page(&all, SortOrder::Desc, None, 2), (vec![0, 3], Some(3)));
+            assert_eq!(page(&all, SortOrder::Desc, Some(3), 10), (vec![2, 1], None));
+            assert_eq!(page(&all, SortOrder::Asc, Some(99), 10), (vec![], None));
+
+            let by_campaigner = CampaignFilter {
+                campaigner: Some(address),
+                ..Default::default()
+            };
+            assert_eq!(
+                page(&by_campaigner, SortOrder::Asc, None, 10),
+                (vec![1, 2, 3, 0], None)
+            );
+            assert_eq!(
+                page(&by_campaigner, SortOrder::Desc, Some(0), 2),
+                (vec![3, 2], Some(2))
+            );
+        }),
+    });
+}
+#[test]
+fn event_filter() {
+    let campaigner = generate_address::<S>("campaigner");
+    let delegate = generate_address::<S>("delegate");
+    let drafted = Event::<S>::CampaignDrafted {
+        campaign_id: 2,
+        campaigner,
+        evictions: vec![delegate],
+    };
+    let voted = Event::<S>::CriteriaVoted {
+        campaign_id: 3,
+        delegate,
+        old_vote: None,
+        vote: CriteriaVote::Rejected,
+    };
+    let linked = Event::<S>::EthAddressLinked {
+        addr: campaigner,
+        eth_address: "0x0000000000000000000000000000000000000001".to_string(),
+    };
+
+    assert_eq!(drafted.kind(), "campaign_drafted");
+    assert_eq!(voted.campaign_id(), Some(3));
+    assert_eq!(linked.campaign_id(), None);
+
+    let matching = |filter: EventFilter<S>| {
+        [&drafted, &voted, &linked]
+            .into_iter()
+            .filter(|event| filter.matches(event))
+            .map(|event| event.kind())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        matching(EventFilter::default()),
+        vec!["campaign_drafted", "criteria_voted", "eth_address_linked"]
+    );
+    assert_eq!(
+        matching(EventFilter {
+            campaign_id: Some(2),
+            ..Default::default()
+        }),
+        vec!["campaign_drafted"]
+    );
+    assert_eq!(
+        matching(EventFilter {
+            kinds: vec![
+                "criteria_voted".to_string(),
+                "eth_address_linked".to_string()
+            ],
+            ..Default::default()
+        }),
+        vec!["criteria_voted", "eth_address_linked"]
+    );
+    assert_eq!(
+        matching(EventFilter {
+            addr: Some(delegate),
+            ..Default::default()
+        }),
+        vec!["campaign_drafted", "criteria_voted"]
+    );
+    assert_eq!(
+        matching(EventFilter {
+            campaign_id: Some(3),
+            addr: Some(campaigner),
+            ..Default::default()
+        }),
+        Vec::<&str>::new()
+    );
+}
+
+/// Compares the cost of a voting power update through the ordered index with the sorted list it
+/// replaced, which was read, sorted and rewritten with `set_all` on every update, as the number
+/// of indexed stakers grows.
+///
+/// Run with `cargo test --release -p filament-hub-core -- --ignored voting_power_index_benchmark`,
+/// the timings are traced at info level.
+#[test]
+#[ignore]
+fn voting_power_index_benchmark() {
+    const UPDATES: u64 = 20;
+
+    for stakers in [100u64, 1_000, 10_000] {
+        let addresses = (0..stakers)
+            .map(|i| generate_address::<S>(&format!("staker-{i}")))
+            .collect::<Vec<_>>();
+        let (TestRoles { relayer, .. }, mut runner) = setup_with_powers(
+            addresses
+                .iter()
+                .enumerate()
+                .map(|(i, addr)| (*addr, i as u64))
+                .collect(),
+        );
+
+        runner.execute_transaction(TransactionTestCase {
+            input: relayer.create_plain_message::<Core<S>>(CallMessage::UpdateVotingPowerAt {
+                address: addresses[0],
+                power: 0,
+                eth_block: 0,
+            }),
+            assert: Box::new(move |result, state| {
+                assert!(result.tx_receipt.is_successful());
+
+                // Sorted list holding the same powers as the index.
+                let core = Core::<S>::default();
+                let list = sov_modules_api::StateVec::<(<S as Spec>::Address, u64)>::new(
+                    sov_state::Prefix::new(b"voting_power_index_benchmark".to_vec()),
+                );
+                let powers = core
+                    .get_voting_powers(None, usize::MAX, state)
+                    .unwrap_infallible();
+                list.set_all(powers, state).unwrap_infallible();
+
+                // Move stakers from the bottom to the top of both.
+                let started = std::time::Instant::now();
+                for i in 0..UPDATES {
+                    core.index_power(&addresses[i as usize], 10_000_000 + i, state)
+                        .unwrap_infallible();
+                }
+                let indexed = started.elapsed() / UPDATES as u32;
+
+                let started = std::time::Instant::now();
+                for i in 0..UPDATES {
+                    let addr = addresses[i as usize];
+                    let mut powers = list
+                        .iter(state)
+                        .unwrap_infallible()
+                        .collect::<Result<Vec<_>, _>>()
+                        .unwrap_infallible();
+                    if let Some((_, stored)) = powers.iter_mut().find(|(stored, _)| *stored == addr)
+                    {
+                        *stored = 10_000_000 + i;
+                    } else {
+                        powers.push((addr, 10_000_000 + i));
+                    }
+                    powers.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+                    list.set_all(powers, state).unwrap_infallible();
+                }
+                let sorted = started.elapsed() / UPDATES as u32;
+
+                tracing::info!(
+                    %stakers,
+                    ?indexed,
+                    ?sorted,
+                    "voting power update through the index and the sorted list"
+                );
+
+                // Both order the moved stakers the same.
+                let top = list
+                    .iter(state)
+                    .unwrap_infallible()
+                    .take(UPDATES as usize)
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap_infallible();
+                assert_eq!(
+                    core.get_voting_powers(None, UPDATES as usize, state)
+                        .unwrap_infallible(),
+                    top
+                );
+                // The sorted list rewrites every entry, so it falls behind with many stakers.
+                if stakers >= 10_000 {
+                    assert!(
+                        indexed < sorted,
+                        "index update took {indexed:?}, sorted list update {sorted:?}"
+                    );
+                }
+            }),
+        });
+    }
+}
+
+fn setup() -> (TestRoles<S>, TestRunner<TestCoreRuntime<S, MockDaSpec>, S>) {
+    setup_with_powers(HashMap::new())
+}
+
+fn setup_with_powers(
+    extra_powers: HashMap<<S as Spec>::Address, u64>,
+) -> (TestRoles<S>, TestRunner<TestCoreRuntime<S, MockDaSpec>, S>) {
+    setup_with_config(|config| config.powers.extend(extra_powers))
+}
+
+/// Sets up the test roles and runner, letting `configure` amend the core genesis config.
+fn setup_with_config(
+    configure: impl FnOnce(&mut CoreConfig<S>),
+) -> (TestRoles<S>, TestRunner<TestCoreRuntime<S, MockDaSpec>, S>) {
+    let genesis_config =
+        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(8);
+
+    let admin = genesis_config.additional_accounts.first().unwrap().clone();
+    let staker = genesis_config.additional_accounts[1].clone();
+    let indexer = genesis_config.additional_accounts[2].clone();
+    let relayer = genesis_config.additional_accounts[3].clone();
+    let campaigner = genesis_config.additional_accounts[4].clone();
+    let delegate_users = vec![
+        genesis_config.additional_accounts[5].clone(),
+        genesis_config.additional_accounts[6].clone(),
+        genesis_config.additional_accounts[7].clone(),
+    ];
+    let delegates = {
+        let mut delegates = HashMap::new();
+        delegates.insert(delegate_users[0].address().to_string(), 3_000_000);
+        delegates.insert(delegate_users[1].address().to_string(), 2_000_000);
+        delegates.insert(delegate_users[2].address().to_string(), 1_000_000);
+        delegates
+    };
+    let powers = {
+        let mut powers = HashMap::new();
+        powers.insert(delegate_users[0].address(), 3_000_000);
+        powers.insert(delegate_users[1].address(), 2_000_000);
+        powers.insert(delegate_users[2].address(), 1_000_000);
+        powers
+    };
+
+    let campaign = {
+        let mut campaign = generate_test_campaign(campaigner.address());
+        campaign.phase = Phase::Criteria;
+        campaign.delegates = delegates.clone();
+        campaign.indexer = Some(indexer.address());
+        campaign
+    };
+    let distribution_campaign = {
+        let mut campaign = generate_test_campaign(campaigner.address());
+        campaign.phase = Phase::Distribution;
+        campaign.delegates = delegates.clone();
+        campaign.indexer = Some(indexer.address());
+        campaign
+    };
+
+    let mut core_config = CoreConfig {
+        admin: admin.address(),
+        campaigns: vec![campaign.clone(), distribution_campaign],
+        delegates: delegate_users
+            .iter()
+            .map(|u| Delegate {
+                address: u.address(),
+                alias: "".to_string(),
+            })
+            .collect::<Vec<_>>(),
+        eth_addresses: Default::default(),
+        indexers: vec![],
+        powers,
+        relayers: vec![relayer.address()],
+        light_client: Some(LightClientConfig {
+            vault_address: format!("0x{}", hex::encode(VAULT_ADDRESS)),
+            balances_slot: VAULT_BALANCES_SLOT,
+            decimals: 18,
+            checkpoint: hex::encode(generate_test_eth_header(100, [0; 32], [0; 32])),
+        }),
+    };
+    configure(&mut core_config);
+    let genesis = GenesisConfig::from_minimal_config(genesis_config.clone().into(), core_config);
+
+    (
+        TestRoles {
+            admin,
+            campaign,
+            campaigner,
+            delegate_users,
+            delegates,
+            indexer,
+            relayer,
+            staker,
+        },
+        TestRunner::new_with_genesis(genesis.into_genesis_params(), TestCoreRuntime::default()),
+    )
+}
+
+/// Checks the invariant that the total voting power always equals the sum of all powers.
+fn assert_total_voting_power(total: u64, powers: &[(<S as Spec>::Address, u64)]) {
+    assert_eq!(total, powers.iter().map(|(_, power)| power).sum::<u64>());
+}
+
+/// Gas token balance of `addr`, the token reward pools are held in.
+fn gas_balance(addr: &<S as Spec>::Address, state: &mut impl StateAccessor) -> u64 {
+    sov_bank::Bank::<S>::default()
+        .get_balance_of(addr, sov_bank::config_gas_token_id(), state)
+        .unwrap_infallible()
+        .unwrap_or_default()
+}
+
+/// Balance of the reward pools escrowed by the core module.
+fn escrow_balance(state: &mut impl StateAccessor) -> u64 {
+    sov_bank::Bank::<S>::default()
+        .get_balance_of(
+            Core::<S>::default().id().to_payable(),
+            sov_bank::config_gas_token_id(),
+            state,
+        )
+        .unwrap_infallible()
+        .unwrap_or_default()
+}
+
+/// RLP encoded Ethereum header with the fields the light client reads.
+fn generate_test_eth_header(number: u64, parent_hash: [u8; 32], state_root: [u8; 32]) -> Vec<u8> {
+    rlp::encode_list(&[
+        rlp::encode_bytes(&parent_hash),
+        rlp::encode_bytes(&[0; 32]),
+        rlp::encode_bytes(&[0; 20]),
+        rlp::encode_bytes(&state_root),
+        rlp::encode_bytes(&[0; 32]),
+        rlp::encode_bytes(&[0; 32]),
+        rlp::encode_bytes(&[0; 256]),
+        rlp::encode_u64(0),
+        rlp::encode_u64(number),
+        rlp::encode_u64(30_000_000),
+        rlp::encode_u64(0),
+        rlp::encode_u64(1_700_000_000),
+        rlp::encode_bytes(&[]),
+        rlp::encode_bytes(&[0; 32]),
+        rlp::encode_bytes(&[0; 8]),
+    ])
+}
+
+/// Trie with a single leaf holding `value` at `key`, returning its root and the proof for `key`.
+fn generate_test_trie(key: &[u8], value: Vec<u8>) -> ([u8; 32], Vec<Vec<u8>>) {
+    let path = [&[0x20][..], &proof::keccak(key)].concat();
+    let leaf = rlp::encode_list(&[rlp::encode_bytes(&path), rlp::encode_bytes(&value)]);
+
+    (proof::keccak(&leaf), vec![leaf])
 }
 
 fn generate_test_campaign(campaigner: <S as Spec>::Address) -> Campaign<S> {
@@ -925,6 +4820,7 @@ fn generate_test_campaign(campaigner: <S as Spec>::Address) -> Campaign<S> {
 
         evictions: vec![],
         delegates: HashMap::new(),
+        snapshot_slot: 0,
 
         indexer: None,
     }
@@ -27,11 +27,11 @@ use filament_hub_core::{
     Playbook,
 };
 use futures::FutureExt as _;
-use jsonrpsee::{core::client::ClientT, rpc_params};
 use neutron::state::Campaign;
+use sov_celestia_adapter::verifier::CelestiaSpec;
 use sov_cli::wallet_state::PrivateKeyAndAddress;
 use sov_ledger_apis::rpc::client::RpcClient;
-use sov_modules_api::{utils::generate_address, CryptoSpec, PrivateKey, PublicKey, Spec};
+use sov_modules_api::utils::generate_address;
 use sov_rollup_interface::rpc::QueryMode;
 use tendermint_rpc::{Client, WebSocketClient};
 use tokio::sync::{watch, Mutex};
@@ -42,6 +42,9 @@ mod config;
 mod hub;
 mod outpost;
 
+/// DA spec of the hub rollup, which calls are encoded for.
+type HubDa = CelestiaSpec;
+
 // XXX: to initialize pull all campaigns from outpost that are Created, Funded,
 //      Indexing, Attesting?
 
@@ -115,30 +118,14 @@ async fn main() -> Result<()> {
             .expect("Unable to read file to string"),
     )?;
     debug!("hub address: {:?}", key_and_address.address);
-    let hub_pkh = key_and_address
-        .private_key
-        .pub_key()
-        .secure_hash::<<<hub::FilaSpec as Spec>::CryptoSpec as CryptoSpec>::Hasher>()
-        .0;
-    let hub_account = match hws
-        .request("accounts_getAccount", rpc_params!(hub_pkh.to_vec()))
-        .await?
-    {
-        hub::AccResp::AccountExists { addr: _, nonce } => Arc::new(Mutex::new(hub::Account {
-            pkh: hub_pkh,
-            nonce,
-        })),
-        _ => Arc::new(Mutex::new(hub::Account {
-            pkh: hub_pkh,
-            nonce: 0,
-        })),
-    };
-
-    let hb = Arc::new(Mutex::new(hub::Hub::new(
-        "ws://localhost:12345".to_string(),
-        hub_account.clone(),
-        key_and_address.private_key.clone(),
-    )));
+    let hb = Arc::new(Mutex::new(
+        hub::Hub::<HubDa>::new(
+            "ws://localhost:12345",
+            key_and_address.private_key,
+            key_and_address.address,
+        )
+        .await?,
+    ));
 
     let (last_block_sender, last_block_recv) = match outpost_client.latest_block().await {
         Ok(res) => {
@@ -148,7 +135,7 @@ async fn main() -> Result<()> {
         Err(e) => Err(eyre!("failed to poll latest block: {}", e)),
     }?;
 
-    let (last_slot_sender, last_slot_recv) = match hws.get_head(QueryMode::Compact).await {
+    let (last_slot_sender, _last_slot_recv) = match hws.get_head(QueryMode::Compact).await {
         Ok(Some(res)) => {
             println!("slot: {:?}", res.number);
             Ok(watch::channel(res.number))
@@ -178,14 +165,6 @@ async fn main() -> Result<()> {
     ))
     .fuse();
 
-    let mut hub_account_handle = tokio::spawn(hub::account_watcher(
-        hws.clone(),
-        hub_pkh,
-        hub_account.clone(),
-        last_slot_recv.clone(),
-    ))
-    .fuse();
-
     let mut outpost_contract_handle = tokio::spawn(outpost::contract_watcher(
         outpost_client.clone(),
         args.outpost_addr,
@@ -208,7 +187,6 @@ async fn main() -> Result<()> {
             join = &mut outpost_account_handle => join??,
             join = &mut outpost_contract_handle => join??,
             join = &mut outpost_campaigns_handle => join??,
-            join = &mut hub_account_handle => join??,
             else => break
         }
     }
@@ -219,7 +197,7 @@ async fn main() -> Result<()> {
 //
 pub async fn outpost_campaigns(
     op: Arc<Mutex<outpost::Outpost>>,
-    hb: Arc<Mutex<hub::Hub>>,
+    hb: Arc<Mutex<hub::Hub<HubDa>>>,
     mut lcr: watch::Receiver<u64>,
 ) -> Result<()> {
     debug!("outpost_campaign");
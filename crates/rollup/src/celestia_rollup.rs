@@ -40,6 +40,8 @@ use sov_stf_runner::{
 };
 use tokio::sync::watch;
 
+use crate::{events, stats};
+
 /// The rollup stores its data in the namespace "sov-test-b" on Celestia.
 /// You can change this constant to point your rollup at a different namespace.
 const ROLLUP_BATCH_NAMESPACE: Namespace = Namespace::const_v0(*b"sov-test-b");
@@ -117,13 +119,18 @@ impl FullNodeBlueprint<Native> for CelestiaRollup<Native> {
             FairBatchBuilderConfig<Self::DaSpec>,
         >,
     ) -> anyhow::Result<RuntimeEndpoints> {
-        sov_modules_rollup_blueprint::register_endpoints::<Self, _>(
+        let mut endpoints = sov_modules_rollup_blueprint::register_endpoints::<Self, _>(
             storage.clone(),
             ledger_db,
             sequencer_db,
             da_service,
             &rollup_config.sequencer,
-        )
+        )?;
+
+        events::register_endpoints::<Self::Spec, Self::DaSpec>(&mut endpoints, ledger_db)?;
+        stats::register_endpoints(&mut endpoints);
+
+        Ok(endpoints)
     }
 
     async fn create_da_service(
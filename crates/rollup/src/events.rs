@@ -0,0 +1,289 @@
+//! Subscriptions to the events emitted by the core module.
+//!
+//! Events are read from the ledger, so subscribers can resume from any slot still stored by the
+//! node. They are streamed over the `core_subscribeEvents` JSON-RPC subscription and as
+//! server-sent events from `/core/events`, and the events of a single slot are returned by
+//! `core_getSlotEvents`.
+use std::{convert::Infallible, ops::Range};
+
+use axum::{
+    extract::{Query, State},
+    response::sse::{Event as SseEvent, KeepAlive, Sse},
+    routing::get,
+    Router,
+};
+use filament_hub_core::{Event, EventFilter};
+use filament_hub_stf::RuntimeEvent;
+use futures::{stream, Stream};
+use jsonrpsee::{
+    types::{
+        error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+        ErrorObjectOwned,
+    },
+    RpcModule,
+    SubscriptionMessage,
+};
+use sov_db::ledger_db::LedgerDb;
+use sov_modules_api::Spec;
+use sov_modules_stf_blueprint::RuntimeEndpoints;
+use sov_rollup_interface::{
+    da::DaSpec,
+    rpc::{LedgerRpcProvider, QueryMode},
+};
+use tokio::sync::{broadcast::error::RecvError, mpsc};
+use tracing::{debug, warn};
+
+/// Number of events buffered per subscriber before the stream waits for it to catch up.
+const SUBSCRIBER_BUFFER: usize = 256;
+
+/// Filters of a subscription and the position to start streaming from.
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+#[serde(
+    bound = "S::Address: serde::Serialize + serde::de::DeserializeOwned",
+    default
+)]
+pub struct EventsQuery<S: Spec> {
+    pub campaign_id: Option<u64>,
+    /// Comma separated event kinds, e.g. `campaign_drafted,criteria_voted`.
+    pub kind: Option<String>,
+    pub addr: Option<S::Address>,
+    /// First slot to stream events of. Defaults to the next slot processed by the node.
+    pub from_slot: Option<u64>,
+    /// First event number to stream. Clients resume with the slot and event number following
+    /// the last event they received.
+    pub from_event: Option<u64>,
+}
+
+impl<S: Spec> EventsQuery<S> {
+    fn filter(&self) -> EventFilter<S> {
+        EventFilter {
+            campaign_id: self.campaign_id,
+            kinds: self
+                .kind
+                .iter()
+                .flat_map(|kinds| kinds.split(','))
+                .map(|kind| kind.trim().to_string())
+                .filter(|kind| !kind.is_empty())
+                .collect(),
+            addr: self.addr.clone(),
+        }
+    }
+}
+
+/// Core event and its position in the ledger.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(bound = "S::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct CoreEvent<S: Spec> {
+    pub slot_number: u64,
+    pub event_number: u64,
+    pub event: Event<S>,
+}
+
+/// Adds the event subscription and stream to the endpoints of a rollup.
+pub fn register_endpoints<S: Spec, Da: DaSpec>(
+    endpoints: &mut RuntimeEndpoints,
+    ledger_db: &LedgerDb,
+) -> anyhow::Result<()> {
+    endpoints
+        .jsonrpsee_module
+        .merge(rpc_module::<S, Da>(ledger_db.clone())?)?;
+    endpoints.axum_router =
+        std::mem::take(&mut endpoints.axum_router).merge(router::<S, Da>(ledger_db.clone()));
+
+    Ok(())
+}
+
+/// JSON-RPC module with the `core_subscribeEvents` subscription and the `core_getSlotEvents`
+/// method.
+pub fn rpc_module<S: Spec, Da: DaSpec>(ledger_db: LedgerDb) -> anyhow::Result<RpcModule<LedgerDb>> {
+    let mut module = RpcModule::new(ledger_db);
+    module.register_method("core_getSlotEvents", |params, ledger_db, _| {
+        let mut params = params.sequence();
+        let slot_number: u64 = params.next()?;
+        let query = params
+            .optional_next::<EventsQuery<S>>()?
+            .unwrap_or_default();
+
+        let filter = query.filter();
+        let events =
+            slot_events::<S, Da>(ledger_db, slot_number, query.from_event.unwrap_or_default())
+                .map_err(|e| {
+                    ErrorObjectOwned::owned(INTERNAL_ERROR_CODE, e.to_string(), None::<()>)
+                })?
+                .into_iter()
+                .filter(|event| filter.matches(&event.event))
+                .collect::<Vec<_>>();
+
+        Ok::<_, ErrorObjectOwned>(events)
+    })?;
+    module.register_subscription(
+        "core_subscribeEvents",
+        "core_subscribeEvents",
+        "core_unsubscribeEvents",
+        |params, pending, ledger_db, _| async move {
+            let query = match params.sequence().optional_next::<EventsQuery<S>>() {
+                Ok(query) => query.unwrap_or_default(),
+                Err(e) => {
+                    pending
+                        .reject(ErrorObjectOwned::owned(
+                            INVALID_PARAMS_CODE,
+                            e.to_string(),
+                            None::<()>,
+                        ))
+                        .await;
+                    return Ok(());
+                },
+            };
+
+            let sink = pending.accept().await?;
+            let mut events = subscribe::<S, Da>((*ledger_db).clone(), query);
+            while let Some(event) = events.recv().await {
+                sink.send(SubscriptionMessage::from_json(&event)?).await?;
+            }
+
+            Ok(())
+        },
+    )?;
+
+    Ok(module)
+}
+
+/// Router serving the `/core/events` stream of server-sent events.
+pub fn router<S: Spec, Da: DaSpec>(ledger_db: LedgerDb) -> Router {
+    Router::new()
+        .route("/core/events", get(route_events::<S, Da>))
+        .with_state(ledger_db)
+}
+
+async fn route_events<S: Spec, Da: DaSpec>(
+    State(ledger_db): State<LedgerDb>,
+    Query(query): Query<EventsQuery<S>>,
+) -> Sse<impl Stream<Item = Result<SseEvent, Infallible>>> {
+    let events = subscribe::<S, Da>(ledger_db, query);
+    let stream = stream::unfold(events, |mut events| async move {
+        let event = events.recv().await?;
+        let sse = SseEvent::default()
+            .id(event.event_number.to_string())
+            .event(event.event.kind())
+            .json_data(&event)
+            .unwrap_or_else(|e| SseEvent::default().event("error").data(e.to_string()));
+
+        Some((Ok(sse), events))
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Streams the core events matching `query` until the receiver is dropped.
+fn subscribe<S: Spec, Da: DaSpec>(
+    ledger_db: LedgerDb,
+    query: EventsQuery<S>,
+) -> mpsc::Receiver<CoreEvent<S>> {
+    let (sender, receiver) = mpsc::channel(SUBSCRIBER_BUFFER);
+    tokio::spawn(async move {
+        if let Err(e) = stream_events::<S, Da>(&ledger_db, &query, &sender).await {
+            warn!("core event subscription failed: {e:?}");
+        }
+    });
+
+    receiver
+}
+
+async fn stream_events<S: Spec, Da: DaSpec>(
+    ledger_db: &LedgerDb,
+    query: &EventsQuery<S>,
+    sender: &mpsc::Sender<CoreEvent<S>>,
+) -> anyhow::Result<()> {
+    let filter = query.filter();
+    // Subscribe before catching up, so no slot is missed in between.
+    let mut slots = ledger_db.subscribe_slots()?;
+    let head = head_slot(ledger_db)?;
+    let mut next_slot = query.from_slot.unwrap_or(head + 1);
+    let from_event = query.from_event.unwrap_or_default();
+
+    let mut head = head;
+    loop {
+        while next_slot <= head {
+            for event in slot_events::<S, Da>(ledger_db, next_slot, from_event)? {
+                if !filter.matches(&event.event) {
+                    continue;
+                }
+                if sender.send(event).await.is_err() {
+                    // The subscriber is gone.
+                    return Ok(());
+                }
+            }
+            next_slot += 1;
+        }
+
+        head = match slots.recv().await {
+            Ok(slot) => slot,
+            Err(RecvError::Lagged(skipped)) => {
+                debug!("core event subscription lagged {skipped} slots behind");
+                head_slot(ledger_db)?
+            },
+            Err(RecvError::Closed) => return Ok(()),
+        };
+    }
+}
+
+fn head_slot(ledger_db: &LedgerDb) -> anyhow::Result<u64> {
+    Ok(ledger_db
+        .get_head::<u32, u32>(QueryMode::Compact)?
+        .map(|slot| slot.number)
+        .unwrap_or_default())
+}
+
+/// Core events of the slot `slot_number`, starting at event number `from_event`.
+fn slot_events<S: Spec, Da: DaSpec>(
+    ledger_db: &LedgerDb,
+    slot_number: u64,
+    from_event: u64,
+) -> anyhow::Result<Vec<CoreEvent<S>>> {
+    let range = slot_event_range(ledger_db, slot_number)?;
+
+    let mut events = Vec::new();
+    for event_number in range.start.max(from_event)..range.end {
+        let Some(event) = ledger_db.get_event_by_number::<RuntimeEvent<S, Da>>(event_number)?
+        else {
+            continue;
+        };
+        if let RuntimeEvent::core(event) = event {
+            events.push(CoreEvent {
+                slot_number,
+                event_number,
+                event,
+            });
+        }
+    }
+
+    Ok(events)
+}
+
+/// Numbers of the events emitted by the transactions of the slot `slot_number`.
+fn slot_event_range(ledger_db: &LedgerDb, slot_number: u64) -> anyhow::Result<Range<u64>> {
+    let Some(slot) = ledger_db.get_slot_by_number::<u32, u32>(slot_number, QueryMode::Compact)?
+    else {
+        return Ok(0..0);
+    };
+
+    let mut range: Option<Range<u64>> = None;
+    for batch_number in slot.batch_range {
+        let Some(batch) =
+            ledger_db.get_batch_by_number::<u32, u32>(batch_number, QueryMode::Compact)?
+        else {
+            continue;
+        };
+        for tx_number in batch.tx_range {
+            let Some(tx) = ledger_db.get_tx_by_number::<u32>(tx_number, QueryMode::Compact)? else {
+                continue;
+            };
+            range = Some(match range {
+                None => tx.event_range,
+                Some(range) => range.start..tx.event_range.end,
+            });
+        }
+    }
+
+    Ok(range.unwrap_or(0..0))
+}
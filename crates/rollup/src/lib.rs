@@ -1,3 +1,6 @@
+pub mod events;
+pub mod stats;
+
 #[cfg(feature = "mock_da")]
 pub mod mock_rollup;
 
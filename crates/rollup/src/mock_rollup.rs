@@ -38,6 +38,8 @@ use sov_stf_runner::{
 };
 use tokio::sync::watch::{self};
 
+use crate::{events, stats};
+
 /// Rollup with [`MockDaService`].
 #[derive(Default)]
 pub struct MockRollup<M> {
@@ -110,13 +112,18 @@ impl FullNodeBlueprint<Native> for MockRollup<Native> {
             FairBatchBuilderConfig<Self::DaSpec>,
         >,
     ) -> Result<RuntimeEndpoints, Error> {
-        sov_modules_rollup_blueprint::register_endpoints::<Self, Native>(
+        let mut endpoints = sov_modules_rollup_blueprint::register_endpoints::<Self, Native>(
             storage.clone(),
             ledger_db,
             sequencer_db,
             da_service,
             &rollup_config.sequencer,
-        )
+        )?;
+
+        events::register_endpoints::<Self::Spec, Self::DaSpec>(&mut endpoints, ledger_db)?;
+        stats::register_endpoints(&mut endpoints);
+
+        Ok(endpoints)
     }
 
     async fn create_da_service(
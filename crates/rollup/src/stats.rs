@@ -0,0 +1,26 @@
+//! Protocol statistics of the core module at `/core/stats`.
+//!
+//! The core module serves its statistics at `/modules/core/stats` like all its REST endpoints,
+//! `/core/stats` redirects there so dashboards can use the documented path.
+use axum::{extract::RawQuery, response::Redirect, routing::get, Router};
+use sov_modules_stf_blueprint::RuntimeEndpoints;
+
+/// Path the core module serves its statistics at.
+const CORE_STATS_PATH: &str = "/modules/core/stats";
+
+/// Adds the `/core/stats` route to the endpoints of the rollup.
+pub fn register_endpoints(endpoints: &mut RuntimeEndpoints) {
+    endpoints.axum_router = std::mem::take(&mut endpoints.axum_router).merge(router());
+}
+
+/// Router redirecting `/core/stats` to the statistics of the core module.
+pub fn router() -> Router {
+    Router::new().route("/core/stats", get(route_stats))
+}
+
+async fn route_stats(RawQuery(query): RawQuery) -> Redirect {
+    match query {
+        Some(query) => Redirect::temporary(&format!("{CORE_STATS_PATH}?{query}")),
+        None => Redirect::temporary(CORE_STATS_PATH),
+    }
+}
@@ -1,4 +1,8 @@
+mod archival;
 mod authenticate;
 mod bank;
+mod client;
+mod core_rest;
+mod events;
 
 mod test_helpers;
@@ -0,0 +1,167 @@
+//! Tests of reading the core state at a past height over RPC and REST.
+use std::net::SocketAddr;
+
+use anyhow::{bail, Context as _};
+use filament_hub_client::{Client, Signer};
+use filament_hub_core::{
+    criteria::{Criterion, CriterionCategory},
+    CoreRpcClient,
+};
+use filament_hub_stf::genesis_config::GenesisPaths;
+use sov_kernels::basic::BasicKernelGenesisPaths;
+use sov_mock_da::{BlockProducingConfig, MockAddress, MockDaConfig, MockDaSpec};
+use sov_modules_api::execution_mode::Native;
+use sov_stf_runner::processes::RollupProverConfig;
+
+use super::test_helpers::{read_private_keys, start_rollup};
+
+/// Path the core module REST API is nested under.
+const CORE_PATH: &str = "/modules/core";
+/// Height far above anything the test rollup produces.
+const FUTURE_HEIGHT: u64 = 1_000_000;
+
+type TestSpec = sov_modules_api::default_spec::DefaultSpec<
+    sov_mock_zkvm::MockZkVerifier,
+    sov_mock_zkvm::MockZkVerifier,
+    Native,
+>;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn archival_tests() -> Result<(), anyhow::Error> {
+    let (rpc_port_tx, rpc_port_rx) = tokio::sync::oneshot::channel();
+    let (rest_port_tx, rest_port_rx) = tokio::sync::oneshot::channel();
+
+    let rollup_task = tokio::spawn(async {
+        start_rollup(
+            rpc_port_tx,
+            rest_port_tx,
+            GenesisPaths::from_dir("../../test-data/genesis/mock/"),
+            BasicKernelGenesisPaths {
+                chain_state: "../../test-data/genesis/mock/chain_state.json".into(),
+            },
+            RollupProverConfig::Skip,
+            MockDaConfig {
+                connection_string: "sqlite::memory:".to_string(),
+                sender_address: MockAddress::new([0; 32]),
+                finalization_blocks: 3,
+                block_producing: BlockProducingConfig::OnSubmit,
+                block_time_ms: 100_000,
+            },
+        )
+        .await;
+    });
+    let rpc_port = rpc_port_rx.await.unwrap();
+    let rest_port = rest_port_rx.await.unwrap();
+
+    // If the rollup throws an error, return it and stop querying the state
+    tokio::select! {
+        err = rollup_task => err?,
+        res = query_heights(rpc_port, rest_port) => res?,
+    }
+    Ok(())
+}
+
+async fn query_heights(rpc_address: SocketAddr, rest_address: SocketAddr) -> anyhow::Result<()> {
+    let key_and_address = read_private_keys::<TestSpec>("tx_signer_private_key.json");
+    let client = Client::<TestSpec, MockDaSpec>::new(
+        &format!("ws://{rpc_address}"),
+        Signer::standard(key_and_address.private_key, key_and_address.address),
+    )
+    .await?;
+    let criteria = vec![Criterion {
+        name: "Test Criterion".to_string(),
+        category: CriterionCategory::Balance,
+        parameters: Default::default(),
+        weight: 1,
+    }];
+
+    let first = client
+        .draft(
+            "Archival".to_string(),
+            "".to_string(),
+            criteria.clone(),
+            vec![],
+        )
+        .await?;
+    let second = client
+        .draft("Archival".to_string(), "".to_string(), criteria, vec![])
+        .await?;
+
+    // Over RPC, campaign 1 only exists from the slot it was drafted in on.
+    let campaign_at = |height| CoreRpcClient::<TestSpec>::rpc_get_campaign(client.rpc(), 1, height);
+    assert_eq!(campaign_at(Some(first.slot_number)).await?, None);
+    assert!(campaign_at(Some(second.slot_number)).await?.is_some());
+    assert!(campaign_at(None).await?.is_some());
+    let err = campaign_at(Some(FUTURE_HEIGHT))
+        .await
+        .expect_err("read the state at a future height over RPC");
+    if !err.to_string().contains("has not been produced yet") {
+        bail!("unexpected RPC error for a future height: {err}");
+    }
+
+    // Over REST, the same reads answer with a 404, 200 and 400.
+    let http = reqwest::Client::new();
+    let url = format!("http://{rest_address}{CORE_PATH}/campaigns/1");
+    for (height, expected) in [
+        (Some(first.slot_number), reqwest::StatusCode::NOT_FOUND),
+        (Some(second.slot_number), reqwest::StatusCode::OK),
+        (None, reqwest::StatusCode::OK),
+        (Some(FUTURE_HEIGHT), reqwest::StatusCode::BAD_REQUEST),
+    ] {
+        let url = match height {
+            Some(height) => format!("{url}?height={height}"),
+            None => url.clone(),
+        };
+        let response = http.get(&url).send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+        if status != expected {
+            bail!("GET {url}: expected {expected}, got {status} with body {body:?}");
+        }
+        if height == Some(FUTURE_HEIGHT) && !body.contains("has not been produced yet") {
+            bail!("GET {url}: unexpected error {body:?}");
+        }
+    }
+
+    // Listing campaigns at the first slot returns the list as it was then.
+    let body = http
+        .get(format!(
+            "http://{rest_address}{CORE_PATH}/campaigns?height={}",
+            first.slot_number
+        ))
+        .send()
+        .await?
+        .text()
+        .await?;
+    let campaigns: serde_json::Value = serde_json::from_str(&body)
+        .with_context(|| format!("malformed campaigns page {body:?}"))?;
+    let ids = campaigns["campaigns"]
+        .as_array()
+        .context("campaigns page without campaigns")?
+        .iter()
+        .map(|campaign| campaign["id"].as_u64())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec![Some(0)]);
+
+    // The statistics are served at `/core/stats` as well, keeping the height.
+    let body = http
+        .get(format!(
+            "http://{rest_address}/core/stats?height={}",
+            first.slot_number
+        ))
+        .send()
+        .await?
+        .text()
+        .await?;
+    let stats: serde_json::Value =
+        serde_json::from_str(&body).with_context(|| format!("malformed stats {body:?}"))?;
+    let drafts = stats["campaigns"]
+        .as_array()
+        .context("stats without campaign counts")?
+        .iter()
+        .find(|count| count["phase"] == "Draft")
+        .and_then(|count| count["campaigns"].as_u64());
+    assert_eq!(drafts, Some(1));
+
+    Ok(())
+}
@@ -6,17 +6,27 @@ use filament_hub_core::{
     criteria::{Criterion, CriterionCategory},
     CoreRpcClient,
 };
-use filament_hub_eth::Tx;
-use filament_hub_stf::{genesis_config::GenesisPaths, RuntimeCall};
+use filament_hub_eth::{eip712::Eip712Tx, Tx};
+use filament_hub_stf::{authentication::Auth, genesis_config::GenesisPaths, RuntimeCall};
 use futures::StreamExt;
+use sov_bank::BankRpcClient;
 use sov_kernels::basic::BasicKernelGenesisPaths;
-use sov_mock_da::{BlockProducingConfig, MockAddress, MockDaConfig, MockDaSpec};
+use sov_mock_da::{
+    storable::service::StorableMockDaService,
+    BlockProducingConfig,
+    MockAddress,
+    MockDaConfig,
+    MockDaSpec,
+};
 use sov_modules_api::{
     execution_mode::Native,
     macros::config_value,
     transaction::{PriorityFeeBips, TxDetails, UnsignedTransaction},
+    Batch,
+    RawTx,
     Spec,
 };
+use sov_rollup_interface::node::da::DaService as _;
 use sov_stf_runner::processes::RollupProverConfig;
 use sov_test_utils::ApiClient;
 use tracing_subscriber::{fmt, layer::SubscriberExt as _, util::SubscriberInitExt as _, EnvFilter};
@@ -24,6 +34,10 @@ use tracing_subscriber::{fmt, layer::SubscriberExt as _, util::SubscriberInitExt
 use super::test_helpers::start_rollup;
 use crate::test_helpers::read_eth_key;
 
+/// DA address of a sequencer that is not in the genesis sequencer registry.
+const UNREGISTERED_SEQUENCER: [u8; 32] = [7; 32];
+const MINIMUM_BOND: u64 = 100_000_000;
+
 type TestSpec = sov_modules_api::default_spec::DefaultSpec<
     sov_mock_zkvm::MockZkVerifier,
     sov_mock_zkvm::MockZkVerifier,
@@ -139,15 +153,17 @@ async fn send_eth_tx(
 
     client
         .sequencer
-        .publish_batch_with_serialized_txs(&[tx])
+        .publish_batch_with_serialized_txs(&[Auth::Eth(borsh::to_vec(&tx)?)])
         .await?;
     // Wait until the rollup has processed the next slot
-    let _slot_number = slot_subscription
+    let slot_number = slot_subscription
         .next()
         .await
         .transpose()?
         .map(|slot| slot.number)
         .unwrap_or_default();
+    // The draft is the first transaction after genesis.
+    assert_eq!(slot_number, 1);
 
     let delegates = {
         let mut delegates = HashMap::new();
@@ -165,7 +181,8 @@ async fn send_eth_tx(
         );
         delegates
     };
-    let campaign_response = CoreRpcClient::<TestSpec>::rpc_get_campaign(&client.rpc, 0).await?;
+    let campaign_response =
+        CoreRpcClient::<TestSpec>::rpc_get_campaign(&client.rpc, 0, None).await?;
     assert_eq!(
         campaign_response,
         Some(Campaign {
@@ -177,11 +194,209 @@ async fn send_eth_tx(
             criteria,
             evictions: vec![],
             delegates,
+            snapshot_slot: 1,
 
             indexer: None,
         }),
         "initialized campaign is incorrect"
     );
 
+    // The same account can sign the call as EIP-712 typed data instead.
+    let call = serde_json::to_string(&RuntimeCall::<TestSpec, MockDaSpec>::Core(
+        filament_hub_core::CallMessage::<TestSpec>::Draft {
+            title: "typed".to_string(),
+            description: "".to_string(),
+            criteria: criteria.clone(),
+            evictions: vec![],
+        },
+    ))?;
+    let tx = {
+        let tx = Eip712Tx::<TestSpec> {
+            signature: vec![],
+            call,
+            nonce: 1,
+            details: TxDetails {
+                max_priority_fee_bips,
+                max_fee: 100,
+                gas_limit: None,
+                chain_id,
+            },
+        };
+        let signature = filament_hub_eth::eip712::sign(&signing_key, &tx.signing_hash()?)?;
+
+        Eip712Tx {
+            signature: signature.to_vec(),
+            ..tx
+        }
+    };
+
+    client
+        .sequencer
+        .publish_batch_with_serialized_txs(&[Auth::Eip712(borsh::to_vec(&tx)?)])
+        .await?;
+    slot_subscription.next().await.transpose()?;
+
+    let campaign_response =
+        CoreRpcClient::<TestSpec>::rpc_get_campaign(&client.rpc, 1, None).await?;
+    assert_eq!(
+        campaign_response.map(|campaign| (campaign.campaigner, campaign.title, campaign.phase)),
+        Some((user_address, "typed".to_string(), Phase::Draft)),
+        "EIP-712 signed draft is incorrect"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn authenticate_unregistered_eth_tx_tests() -> Result<(), anyhow::Error> {
+    let temp_dir = tempfile::tempdir()?;
+    let da_connection_string = format!(
+        "sqlite://{}?mode=rwc",
+        temp_dir.path().join("da.sqlite").display()
+    );
+    let (rpc_port_tx, rpc_port_rx) = tokio::sync::oneshot::channel();
+    let (rest_port_tx, rest_port_rx) = tokio::sync::oneshot::channel();
+
+    let rollup_da_connection_string = da_connection_string.clone();
+    let rollup_task = tokio::spawn(async {
+        start_rollup(
+            rpc_port_tx,
+            rest_port_tx,
+            GenesisPaths::from_dir("../../test-data/genesis/mock/"),
+            BasicKernelGenesisPaths {
+                chain_state: "../../test-data/genesis/mock/chain_state.json".into(),
+            },
+            RollupProverConfig::Skip,
+            MockDaConfig {
+                connection_string: rollup_da_connection_string,
+                sender_address: MockAddress::new([0; 32]),
+                finalization_blocks: 3,
+                block_producing: BlockProducingConfig::OnSubmit,
+                block_time_ms: 100_000,
+            },
+        )
+        .await;
+    });
+    let rpc_port = rpc_port_rx.await.unwrap();
+    let rest_port = rest_port_rx.await.unwrap();
+
+    // If the rollup throws an error, return it and stop trying to send the transactions
+    tokio::select! {
+        err = rollup_task => err?,
+        res = send_unregistered_eth_txs(rpc_port, rest_port, da_connection_string) => res?,
+    }
+    Ok(())
+}
+
+async fn send_unregistered_eth_txs(
+    rpc_address: SocketAddr,
+    rest_address: SocketAddr,
+    da_connection_string: String,
+) -> Result<(), anyhow::Error> {
+    let (signing_key, address) = read_eth_key::<TestSpec>("signer.json")?;
+    let user_address: <TestSpec as Spec>::Address = address;
+
+    let client = ApiClient::new(rpc_address.port(), rest_address.port()).await?;
+    let da_service = StorableMockDaService::from_config(MockDaConfig {
+        connection_string: da_connection_string,
+        sender_address: MockAddress::new(UNREGISTERED_SEQUENCER),
+        finalization_blocks: 3,
+        block_producing: BlockProducingConfig::OnSubmit,
+        block_time_ms: 100_000,
+    })
+    .await;
+
+    let mut slot_subscription = client
+        .ledger
+        .subscribe_slots()
+        .await
+        .context("Failed to subscribe to slots!")?;
+
+    let gas_token_id = sov_bank::config_gas_token_id();
+    let balance_before =
+        BankRpcClient::<TestSpec>::balance_of(&client.rpc, None, user_address, gas_token_id)
+            .await?
+            .amount
+            .unwrap_or_default();
+
+    // Only sequencer registrations are accepted from unregistered sequencers.
+    let draft = RuntimeCall::<TestSpec, MockDaSpec>::Core(filament_hub_core::CallMessage::<
+        TestSpec,
+    >::Draft {
+        title: "".to_string(),
+        description: "".to_string(),
+        criteria: vec![],
+        evictions: vec![],
+    });
+    let blob = generate_unregistered_blob(&signing_key, draft, 0)?;
+    da_service.send_transaction(&blob).await?;
+    slot_subscription.next().await.transpose()?;
+
+    let campaign_response =
+        CoreRpcClient::<TestSpec>::rpc_get_campaign(&client.rpc, 0, None).await?;
+    assert_eq!(
+        campaign_response, None,
+        "unregistered sequencer executed a non registration call"
+    );
+
+    let register = RuntimeCall::<TestSpec, MockDaSpec>::SequencerRegistry(
+        sov_sequencer_registry::CallMessage::Register {
+            da_address: UNREGISTERED_SEQUENCER.to_vec(),
+            amount: MINIMUM_BOND,
+        },
+    );
+    let blob = generate_unregistered_blob(&signing_key, register, 0)?;
+    da_service.send_transaction(&blob).await?;
+    slot_subscription.next().await.transpose()?;
+
+    let balance_after =
+        BankRpcClient::<TestSpec>::balance_of(&client.rpc, None, user_address, gas_token_id)
+            .await?
+            .amount
+            .unwrap_or_default();
+    assert!(
+        balance_after <= balance_before - MINIMUM_BOND,
+        "eth signed sequencer registration did not bond {MINIMUM_BOND}, balance went from \
+         {balance_before} to {balance_after}"
+    );
+
     Ok(())
 }
+
+/// Blob with a single eth signed transaction, as submitted by a sequencer to the DA layer.
+fn generate_unregistered_blob(
+    signing_key: &k256::ecdsa::SigningKey,
+    msg: RuntimeCall<TestSpec, MockDaSpec>,
+    nonce: u64,
+) -> Result<Vec<u8>, anyhow::Error> {
+    let chain_id = config_value!("CHAIN_ID");
+    let runtime_msg = borsh::to_vec(&msg)?;
+    let unsigned_tx_bytes = borsh::to_vec(&UnsignedTransaction::<TestSpec>::new(
+        runtime_msg.clone(),
+        chain_id,
+        PriorityFeeBips::ZERO,
+        MINIMUM_BOND,
+        nonce,
+        None,
+    ))?;
+    let signature = filament_hub_eth::sign(signing_key, unsigned_tx_bytes)?;
+
+    let tx: Tx<TestSpec> = Tx {
+        signature: signature.to_vec(),
+        runtime_msg,
+        nonce,
+        details: TxDetails {
+            max_priority_fee_bips: PriorityFeeBips::ZERO,
+            max_fee: MINIMUM_BOND,
+            gas_limit: None,
+            chain_id,
+        },
+    };
+    let batch = Batch {
+        txs: vec![RawTx {
+            data: borsh::to_vec(&Auth::Eth(borsh::to_vec(&tx)?))?,
+        }],
+    };
+
+    Ok(borsh::to_vec(&batch)?)
+}
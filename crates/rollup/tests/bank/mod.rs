@@ -1,7 +1,7 @@
 use std::{env, net::SocketAddr, str::FromStr as _};
 
 use anyhow::Context as _;
-use filament_hub_stf::{genesis_config::GenesisPaths, RuntimeCall};
+use filament_hub_stf::{authentication::Auth, genesis_config::GenesisPaths, RuntimeCall};
 use futures::StreamExt;
 use sov_bank::BankRpcClient;
 use sov_kernels::basic::BasicKernelGenesisPaths;
@@ -120,7 +120,7 @@ async fn send_test_create_token_tx(
 
     client
         .sequencer
-        .publish_batch_with_serialized_txs(&[tx])
+        .publish_batch_with_serialized_txs(&[Auth::Standard(borsh::to_vec(&tx)?)])
         .await?;
     // Wait until the rollup has processed the next slot
     let _slot_number = slot_subscription
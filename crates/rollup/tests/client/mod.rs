@@ -0,0 +1,192 @@
+use std::{env, net::SocketAddr, str::FromStr as _};
+
+use anyhow::Context as _;
+use filament_hub_client::{Client, Inclusion, Signer};
+use filament_hub_core::{
+    campaign::Phase,
+    criteria::{Criterion, CriterionCategory},
+    CallMessage,
+    Event,
+};
+use filament_hub_stf::genesis_config::GenesisPaths;
+use jsonrpsee::{core::client::ClientT as _, rpc_params};
+use sov_kernels::basic::BasicKernelGenesisPaths;
+use sov_mock_da::{BlockProducingConfig, MockAddress, MockDaConfig, MockDaSpec};
+use sov_modules_api::{execution_mode::Native, Spec};
+use sov_rollup_interface::{da::DaSpec, rpc::QueryMode};
+use sov_stf_runner::processes::RollupProverConfig;
+use tracing_subscriber::{fmt, layer::SubscriberExt as _, util::SubscriberInitExt as _, EnvFilter};
+
+use super::test_helpers::{read_eth_key, read_private_keys, start_rollup};
+
+type TestSpec = sov_modules_api::default_spec::DefaultSpec<
+    sov_mock_zkvm::MockZkVerifier,
+    sov_mock_zkvm::MockZkVerifier,
+    Native,
+>;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn client_tests() -> Result<(), anyhow::Error> {
+    tracing_subscriber::registry()
+        .with(fmt::layer())
+        .with(
+            EnvFilter::from_str(
+                &env::var("RUST_LOG")
+                    .unwrap_or_else(|_| "debug,hyper=info,jmt=info,risc0_zkvm=info,reqwest=info,tower_http=info,jsonrpsee-client=info,jsonrpsee-server=info,sqlx=warn".to_string()),
+            )
+            .unwrap(),
+        )
+        .init();
+    let (rpc_port_tx, rpc_port_rx) = tokio::sync::oneshot::channel();
+    let (rest_port_tx, rest_port_rx) = tokio::sync::oneshot::channel();
+
+    let rollup_task = tokio::spawn(async {
+        start_rollup(
+            rpc_port_tx,
+            rest_port_tx,
+            GenesisPaths::from_dir("../../test-data/genesis/mock/"),
+            BasicKernelGenesisPaths {
+                chain_state: "../../test-data/genesis/mock/chain_state.json".into(),
+            },
+            RollupProverConfig::Skip,
+            MockDaConfig {
+                connection_string: "sqlite::memory:".to_string(),
+                sender_address: MockAddress::new([0; 32]),
+                finalization_blocks: 3,
+                block_producing: BlockProducingConfig::OnSubmit,
+                block_time_ms: 100_000,
+            },
+        )
+        .await;
+    });
+    let rpc_port = rpc_port_rx.await.unwrap();
+    let _rest_port = rest_port_rx.await.unwrap();
+
+    // If the rollup throws an error, return it and stop sending transactions
+    tokio::select! {
+        err = rollup_task => err?,
+        res = send_client_txs(rpc_port) => res?,
+    }
+    Ok(())
+}
+
+async fn send_client_txs(rpc_address: SocketAddr) -> Result<(), anyhow::Error> {
+    let url = format!("ws://{rpc_address}");
+    let criteria = vec![Criterion {
+        name: "Test Criterion".to_string(),
+        category: CriterionCategory::Balance,
+        parameters: Default::default(),
+        weight: 1,
+    }];
+    let draft = CallMessage::<TestSpec>::Draft {
+        title: "Client".to_string(),
+        description: "".to_string(),
+        criteria: criteria.clone(),
+        evictions: vec![],
+    };
+
+    // Standard signer.
+    let key_and_address = read_private_keys::<TestSpec>("tx_signer_private_key.json");
+    let address = key_and_address.address;
+    let client = Client::<TestSpec, MockDaSpec>::new(
+        &url,
+        Signer::standard(key_and_address.private_key, address),
+    )
+    .await?;
+
+    let inclusion = client.submit_and_wait(draft.clone()).await?;
+    assert_eq!(inclusion.nonce, 0);
+    assert!(
+        matches!(
+            inclusion.events.as_slice(),
+            [Event::CampaignDrafted { campaign_id: 0, campaigner, .. }] if *campaigner == address
+        ),
+        "unexpected events {:?}",
+        inclusion.events
+    );
+    assert_receipt(&client, &inclusion).await?;
+    assert!(inclusion.fee > 0);
+    assert!(client.estimate_fee(&draft).await >= inclusion.fee);
+
+    // The nonce is tracked locally between transactions.
+    let inclusion = client
+        .draft("Client".to_string(), "".to_string(), criteria, vec![])
+        .await?;
+    assert_eq!(inclusion.nonce, 1);
+    assert_eq!(client.fetch_nonce().await?, 2);
+
+    let campaign = client
+        .get_campaign(1)
+        .await?
+        .expect("campaign 1 not drafted");
+    assert_eq!(campaign.campaigner, address);
+    assert_eq!(campaign.phase, Phase::Draft);
+    let stats = client.get_stats().await?;
+    assert_eq!(
+        stats
+            .campaigns
+            .iter()
+            .find(|count| count.phase == Phase::Draft)
+            .map(|count| count.campaigns),
+        Some(2)
+    );
+
+    // Eth signer.
+    let (signing_key, eth_address) = read_eth_key::<TestSpec>("signer.json")?;
+    let eth_client = Client::<TestSpec, MockDaSpec>::new(&url, Signer::eth(signing_key)).await?;
+    assert_eq!(*eth_client.address(), eth_address);
+
+    let inclusion = eth_client.submit_and_wait(draft).await?;
+    assert_eq!(inclusion.nonce, 0);
+    assert_receipt(&eth_client, &inclusion).await?;
+    assert_eq!(
+        eth_client
+            .get_campaigns_by_addr(eth_address)
+            .await?
+            .into_iter()
+            .map(|campaign| campaign.id)
+            .collect::<Vec<_>>(),
+        vec![2]
+    );
+
+    Ok(())
+}
+
+/// Asserts the ledger indexes the included transaction under its hash, and that the fee is the
+/// gas of its receipt at the gas price of its slot.
+async fn assert_receipt<S: Spec, Da: DaSpec>(
+    client: &Client<S, Da>,
+    inclusion: &Inclusion<S>,
+) -> anyhow::Result<()> {
+    let tx: serde_json::Value = client
+        .rpc()
+        .request(
+            "ledger_getTxByHash",
+            rpc_params![inclusion.hash.to_string(), QueryMode::Compact],
+        )
+        .await?;
+    assert_eq!(tx["hash"], serde_json::json!(inclusion.hash.to_string()));
+    assert_eq!(tx["receipt"]["result"], "successful", "unexpected tx {tx}");
+
+    let slot: serde_json::Value = client
+        .rpc()
+        .request(
+            "ledger_getSlotByNumber",
+            rpc_params![inclusion.slot_number, QueryMode::Compact],
+        )
+        .await?;
+    let gas_used = tx["receipt"]["data"]["gas_used"]
+        .as_array()
+        .context("receipt without gas used")?;
+    let gas_price = slot["gas_price"]
+        .as_array()
+        .context("slot without gas price")?;
+    let fee = gas_used
+        .iter()
+        .zip(gas_price)
+        .map(|(gas, price)| gas.as_u64().unwrap_or_default() * price.as_u64().unwrap_or_default())
+        .sum::<u64>();
+    assert_eq!(inclusion.fee, fee);
+
+    Ok(())
+}
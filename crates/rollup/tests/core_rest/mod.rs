@@ -0,0 +1,295 @@
+//! Contract tests of the core REST API: every route declared in the OpenAPI spec is requested
+//! from a running rollup and its responses are checked against the spec.
+use std::{env, net::SocketAddr, str::FromStr as _};
+
+use anyhow::{anyhow, bail, Context as _};
+use filament_hub_core::criteria::{Criterion, CriterionCategory};
+use filament_hub_stf::{authentication::Auth, genesis_config::GenesisPaths, RuntimeCall};
+use futures::StreamExt;
+use serde_json::Value;
+use sov_kernels::basic::BasicKernelGenesisPaths;
+use sov_mock_da::{BlockProducingConfig, MockAddress, MockDaConfig, MockDaSpec};
+use sov_modules_api::{
+    execution_mode::Native,
+    macros::config_value,
+    prelude::serde_yaml,
+    transaction::{PriorityFeeBips, Transaction, UnsignedTransaction},
+};
+use sov_stf_runner::processes::RollupProverConfig;
+use sov_test_utils::ApiClient;
+use tracing_subscriber::{fmt, layer::SubscriberExt as _, util::SubscriberInitExt as _, EnvFilter};
+
+use super::test_helpers::{read_private_keys, start_rollup};
+
+const SPEC: &str = include_str!("../../../modules/core/openapi-v3.yaml");
+/// Path the core module REST API is nested under.
+const CORE_PATH: &str = "/modules/core";
+const MAX_TX_FEE: u64 = 100_000_000;
+
+/// Delegate of the mock genesis, with a linked eth address.
+const DELEGATE: &str = "sov1el0m5n0m4tjr5mawdglwae3swn7jw69cqqqqqqqqqqqqqqqqqqqq359qtu";
+const DELEGATE_ETH: &str = "0xcfdfBa4dfbaaE43a6Fae6a3eEeE63074FD2768B8";
+
+type TestSpec = sov_modules_api::default_spec::DefaultSpec<
+    sov_mock_zkvm::MockZkVerifier,
+    sov_mock_zkvm::MockZkVerifier,
+    Native,
+>;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn core_rest_tests() -> Result<(), anyhow::Error> {
+    tracing_subscriber::registry()
+        .with(fmt::layer())
+        .with(
+            EnvFilter::from_str(
+                &env::var("RUST_LOG")
+                    .unwrap_or_else(|_| "debug,hyper=info,jmt=info,risc0_zkvm=info,reqwest=info,tower_http=info,jsonrpsee-client=info,jsonrpsee-server=info,sqlx=warn".to_string()),
+            )
+            .unwrap(),
+        )
+        .init();
+    let (rpc_port_tx, rpc_port_rx) = tokio::sync::oneshot::channel();
+    let (rest_port_tx, rest_port_rx) = tokio::sync::oneshot::channel();
+
+    let rollup_task = tokio::spawn(async {
+        start_rollup(
+            rpc_port_tx,
+            rest_port_tx,
+            GenesisPaths::from_dir("../../test-data/genesis/mock/"),
+            BasicKernelGenesisPaths {
+                chain_state: "../../test-data/genesis/mock/chain_state.json".into(),
+            },
+            RollupProverConfig::Skip,
+            MockDaConfig {
+                connection_string: "sqlite::memory:".to_string(),
+                sender_address: MockAddress::new([0; 32]),
+                finalization_blocks: 3,
+                block_producing: BlockProducingConfig::OnSubmit,
+                block_time_ms: 100_000,
+            },
+        )
+        .await;
+    });
+    let rpc_port = rpc_port_rx.await.unwrap();
+    let rest_port = rest_port_rx.await.unwrap();
+
+    // If the rollup throws an error, return it and stop checking the routes
+    tokio::select! {
+        err = rollup_task => err?,
+        res = check_routes(rpc_port, rest_port) => res?,
+    }
+    Ok(())
+}
+
+async fn check_routes(rpc_address: SocketAddr, rest_address: SocketAddr) -> anyhow::Result<()> {
+    draft_campaign(rpc_address, rest_address).await?;
+
+    let spec: Value = serde_yaml::from_str(SPEC).context("malformed OpenAPI spec")?;
+    let paths = spec["paths"]
+        .as_object()
+        .ok_or(anyhow!("OpenAPI spec without paths"))?;
+    let http = reqwest::Client::new();
+
+    for (path, item) in paths {
+        let operation = &item["get"];
+        let url = format!("http://{rest_address}{CORE_PATH}{}", sample_path(path)?);
+
+        let response = http.get(&url).send().await?;
+        check_response(&spec, operation, response)
+            .await
+            .with_context(|| format!("GET {path}"))?;
+
+        // Every route reads the state at an optional height, which has to be a number.
+        let response = http.get(format!("{url}?height=latest")).send().await?;
+        if response.status() != reqwest::StatusCode::BAD_REQUEST {
+            bail!(
+                "GET {path}?height=latest: expected 400, got {}",
+                response.status()
+            );
+        }
+        check_response(&spec, operation, response)
+            .await
+            .with_context(|| format!("GET {path}?height=latest"))?;
+
+        // Malformed addresses are rejected with a JSON error, like any other bad request.
+        if path.contains("{addr}") || path.contains("{eth_addr}") {
+            let malformed = sample_path(path)?
+                .replace(DELEGATE, "malformed")
+                .replace(DELEGATE_ETH, "malformed");
+            let response = http
+                .get(format!("http://{rest_address}{CORE_PATH}{malformed}"))
+                .send()
+                .await?;
+            if response.status() != reqwest::StatusCode::BAD_REQUEST {
+                bail!("GET {malformed}: expected 400, got {}", response.status());
+            }
+            check_response(&spec, operation, response)
+                .await
+                .with_context(|| format!("GET {malformed}"))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Drafts campaign 0, so the campaign routes have something to return.
+async fn draft_campaign(rpc_address: SocketAddr, rest_address: SocketAddr) -> anyhow::Result<()> {
+    let key_and_address = read_private_keys::<TestSpec>("tx_signer_private_key.json");
+
+    let msg = RuntimeCall::<TestSpec, MockDaSpec>::Core(
+        filament_hub_core::CallMessage::<TestSpec>::Draft {
+            title: "Contract".to_string(),
+            description: "".to_string(),
+            criteria: vec![Criterion {
+                name: "Test Criterion".to_string(),
+                category: CriterionCategory::Balance,
+                parameters: Default::default(),
+                weight: 1,
+            }],
+            evictions: vec![],
+        },
+    );
+    let tx = Transaction::<TestSpec>::new_signed_tx(
+        &key_and_address.private_key,
+        UnsignedTransaction::new(
+            borsh::to_vec(&msg)?,
+            config_value!("CHAIN_ID"),
+            PriorityFeeBips::ZERO,
+            MAX_TX_FEE,
+            0,
+            None,
+        ),
+    );
+
+    let client = ApiClient::new(rpc_address.port(), rest_address.port()).await?;
+    let mut slot_subscription = client
+        .ledger
+        .subscribe_slots()
+        .await
+        .context("Failed to subscribe to slots!")?;
+
+    client
+        .sequencer
+        .publish_batch_with_serialized_txs(&[Auth::Standard(borsh::to_vec(&tx)?)])
+        .await?;
+    // Wait until the rollup has processed the next slot
+    slot_subscription.next().await.transpose()?;
+
+    Ok(())
+}
+
+/// Fills the path parameters of `path` with values of the mock genesis.
+fn sample_path(path: &str) -> anyhow::Result<String> {
+    let mut sample = String::new();
+    for segment in path.split('/').skip(1) {
+        let value = match segment {
+            "{campaign_id}" | "{proposal_id}" => "0",
+            "{addr}" => DELEGATE,
+            "{eth_addr}" => DELEGATE_ETH,
+            segment if segment.starts_with('{') => bail!("no sample for parameter {segment}"),
+            segment => segment,
+        };
+        sample.push('/');
+        sample.push_str(value);
+    }
+
+    Ok(sample)
+}
+
+/// Checks that the status of the response is declared by `operation` and that its body matches
+/// the declared schema.
+async fn check_response(
+    spec: &Value,
+    operation: &Value,
+    response: reqwest::Response,
+) -> anyhow::Result<()> {
+    let status = response.status();
+    let declared = resolve(spec, &operation["responses"][status.as_str()])?;
+    if declared.is_null() {
+        bail!("undeclared status {status}");
+    }
+
+    let body = response.text().await?;
+    let schema = &declared["content"]["application/json"]["schema"];
+    if schema.is_null() {
+        return Ok(());
+    }
+    // Unmatched routes answer with an empty body, which fails here.
+    let body: Value =
+        serde_json::from_str(&body).with_context(|| format!("{status} with body {body:?}"))?;
+    check_schema(spec, schema, &body, "$")
+}
+
+/// Resolves `value` if it is a `$ref` to another part of the spec.
+fn resolve<'a>(spec: &'a Value, value: &'a Value) -> anyhow::Result<&'a Value> {
+    match value["$ref"].as_str() {
+        Some(reference) => {
+            let pointer = reference
+                .strip_prefix('#')
+                .ok_or(anyhow!("unsupported reference {reference}"))?;
+            spec.pointer(pointer)
+                .ok_or(anyhow!("dangling reference {reference}"))
+        },
+        None => Ok(value),
+    }
+}
+
+/// Checks `value` against the subset of JSON schema the spec uses.
+fn check_schema(spec: &Value, schema: &Value, value: &Value, at: &str) -> anyhow::Result<()> {
+    let schema = resolve(spec, schema)?;
+
+    if value.is_null() {
+        if schema["nullable"].as_bool().unwrap_or_default() {
+            return Ok(());
+        }
+        bail!("{at}: unexpected null");
+    }
+
+    if let Some(schemas) = schema["allOf"].as_array() {
+        for each in schemas {
+            check_schema(spec, each, value, at)?;
+        }
+    }
+
+    if let Some(variants) = schema["enum"].as_array() {
+        if !variants.contains(value) {
+            bail!("{at}: {value} is not one of {variants:?}");
+        }
+    }
+
+    match schema["type"].as_str() {
+        Some("object") => {
+            let object = value
+                .as_object()
+                .ok_or(anyhow!("{at}: expected an object, got {value}"))?;
+            for field in schema["required"].as_array().into_iter().flatten() {
+                let field = field.as_str().unwrap_or_default();
+                if !object.contains_key(field) {
+                    bail!("{at}: missing required field {field}");
+                }
+            }
+            for (field, value) in object {
+                let property = &schema["properties"][field];
+                if !property.is_null() {
+                    check_schema(spec, property, value, &format!("{at}.{field}"))?;
+                }
+            }
+        },
+        Some("array") => {
+            let items = value
+                .as_array()
+                .ok_or(anyhow!("{at}: expected an array, got {value}"))?;
+            for (i, item) in items.iter().enumerate() {
+                check_schema(spec, &schema["items"], item, &format!("{at}[{i}]"))?;
+            }
+        },
+        Some("string") if !value.is_string() => bail!("{at}: expected a string, got {value}"),
+        Some("integer") if !value.is_u64() && !value.is_i64() => {
+            bail!("{at}: expected an integer, got {value}")
+        },
+        Some("boolean") if !value.is_boolean() => bail!("{at}: expected a boolean, got {value}"),
+        _ => {},
+    }
+
+    Ok(())
+}
@@ -0,0 +1,161 @@
+//! Tests of the core event subscription: subscribers catch up from past slots and resume after
+//! a disconnect without missing or repeating events.
+use std::{net::SocketAddr, time::Duration};
+
+use anyhow::{anyhow, Context as _};
+use filament_hub_client::{Client, Inclusion, Signer};
+use filament_hub_core::{
+    criteria::{Criterion, CriterionCategory},
+    Event,
+};
+use filament_hub_rollup::events::CoreEvent;
+use filament_hub_stf::genesis_config::GenesisPaths;
+use jsonrpsee::{
+    core::client::{Subscription, SubscriptionClientT as _},
+    rpc_params,
+};
+use sov_kernels::basic::BasicKernelGenesisPaths;
+use sov_mock_da::{BlockProducingConfig, MockAddress, MockDaConfig, MockDaSpec};
+use sov_modules_api::execution_mode::Native;
+use sov_stf_runner::processes::RollupProverConfig;
+
+use super::test_helpers::{read_private_keys, start_rollup};
+
+type TestSpec = sov_modules_api::default_spec::DefaultSpec<
+    sov_mock_zkvm::MockZkVerifier,
+    sov_mock_zkvm::MockZkVerifier,
+    Native,
+>;
+
+/// Time to wait for an event before failing the test.
+const EVENT_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[tokio::test(flavor = "multi_thread")]
+async fn event_subscription_tests() -> Result<(), anyhow::Error> {
+    let (rpc_port_tx, rpc_port_rx) = tokio::sync::oneshot::channel();
+    let (rest_port_tx, rest_port_rx) = tokio::sync::oneshot::channel();
+
+    let rollup_task = tokio::spawn(async {
+        start_rollup(
+            rpc_port_tx,
+            rest_port_tx,
+            GenesisPaths::from_dir("../../test-data/genesis/mock/"),
+            BasicKernelGenesisPaths {
+                chain_state: "../../test-data/genesis/mock/chain_state.json".into(),
+            },
+            RollupProverConfig::Skip,
+            MockDaConfig {
+                connection_string: "sqlite::memory:".to_string(),
+                sender_address: MockAddress::new([0; 32]),
+                finalization_blocks: 3,
+                block_producing: BlockProducingConfig::OnSubmit,
+                block_time_ms: 100_000,
+            },
+        )
+        .await;
+    });
+    let rpc_port = rpc_port_rx.await.unwrap();
+    let _rest_port = rest_port_rx.await.unwrap();
+
+    // If the rollup throws an error, return it and stop checking the subscription
+    tokio::select! {
+        err = rollup_task => err?,
+        res = resume_subscription(rpc_port) => res?,
+    }
+    Ok(())
+}
+
+async fn resume_subscription(rpc_address: SocketAddr) -> anyhow::Result<()> {
+    let key_and_address = read_private_keys::<TestSpec>("tx_signer_private_key.json");
+    let client = Client::<TestSpec, MockDaSpec>::new(
+        &format!("ws://{rpc_address}"),
+        Signer::standard(key_and_address.private_key, key_and_address.address),
+    )
+    .await?;
+
+    // Draft campaigns before subscribing, so the subscription has to catch up on past slots.
+    let first = draft(&client).await?;
+    for _ in 0..2 {
+        draft(&client).await?;
+    }
+
+    let mut received = vec![];
+    let mut events = subscribe(&client, first.slot_number, 0).await?;
+    for _ in 0..2 {
+        received.push(next_event(&mut events).await?);
+    }
+    events.unsubscribe().await?;
+
+    // Campaigns drafted while disconnected are streamed once the subscriber resumes after the
+    // last event it received.
+    for _ in 0..2 {
+        draft(&client).await?;
+    }
+    let last = received.last().expect("no events received").clone();
+    let mut events = subscribe(&client, last.slot_number, last.event_number + 1).await?;
+    for _ in 0..3 {
+        received.push(next_event(&mut events).await?);
+    }
+
+    let campaign_ids = received
+        .iter()
+        .map(|event| match event.event {
+            Event::CampaignDrafted { campaign_id, .. } => Ok(campaign_id),
+            ref event => Err(anyhow!("unexpected event {event:?}")),
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    assert_eq!(campaign_ids, vec![0, 1, 2, 3, 4]);
+    assert!(received
+        .windows(2)
+        .all(|pair| pair[0].event_number < pair[1].event_number
+            && pair[0].slot_number <= pair[1].slot_number));
+
+    Ok(())
+}
+
+async fn draft(client: &Client<TestSpec, MockDaSpec>) -> anyhow::Result<Inclusion<TestSpec>> {
+    client
+        .draft(
+            "Events".to_string(),
+            "".to_string(),
+            vec![Criterion {
+                name: "Test Criterion".to_string(),
+                category: CriterionCategory::Balance,
+                parameters: Default::default(),
+                weight: 1,
+            }],
+            vec![],
+        )
+        .await
+}
+
+/// Subscribes to the drafted campaigns from the slot `from_slot` and event `from_event` on.
+async fn subscribe(
+    client: &Client<TestSpec, MockDaSpec>,
+    from_slot: u64,
+    from_event: u64,
+) -> anyhow::Result<Subscription<CoreEvent<TestSpec>>> {
+    client
+        .rpc()
+        .subscribe(
+            "core_subscribeEvents",
+            rpc_params![serde_json::json!({
+                "kind": "campaign_drafted",
+                "from_slot": from_slot,
+                "from_event": from_event,
+            })],
+            "core_unsubscribeEvents",
+        )
+        .await
+        .context("unable to subscribe to core events")
+}
+
+async fn next_event(
+    events: &mut Subscription<CoreEvent<TestSpec>>,
+) -> anyhow::Result<CoreEvent<TestSpec>> {
+    tokio::time::timeout(EVENT_TIMEOUT, events.next())
+        .await
+        .context("timed out waiting for a core event")?
+        .ok_or(anyhow!("core event subscription closed"))?
+        .context("malformed core event")
+}
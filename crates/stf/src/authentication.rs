@@ -6,7 +6,13 @@ use std::marker::PhantomData;
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use sov_modules_api::{
-    capabilities::{AuthenticationResult, AuthorizationData, UnregisteredAuthenticationError},
+    capabilities::{
+        AuthenticationError,
+        AuthenticationResult,
+        AuthorizationData,
+        FatalError,
+        UnregisteredAuthenticationError,
+    },
     runtime::capabilities::TransactionAuthenticator,
     DaSpec,
     DispatchCall,
@@ -31,20 +37,49 @@ impl<S: Spec, Da: DaSpec> TransactionAuthenticator<S> for Runtime<S, Da> {
         pre_exec_ws: &mut PreExecWorkingSet<S, Self::SequencerStakeMeter>,
     ) -> AuthenticationResult<S, Self::Decodable, Self::AuthorizationData> {
         match input {
-            Auth::Mod(tx) => {
-                match filament_hub_eth::authenticate::<S, Self, Self::SequencerStakeMeter>(
+            Auth::Standard(tx) => sov_modules_api::capabilities::authenticate::<
+                S,
+                Self,
+                Self::SequencerStakeMeter,
+            >(tx, pre_exec_ws),
+            Auth::Eth(tx) => filament_hub_eth::authenticate::<S, Self, Self::SequencerStakeMeter>(
+                tx,
+                pre_exec_ws,
+            ),
+            Auth::Eip712(tx) => filament_hub_eth::eip712::authenticate::<
+                S,
+                Self,
+                Self::SequencerStakeMeter,
+            >(tx, pre_exec_ws),
+            Auth::Multisig(tx) => {
+                filament_hub_eth::multisig::authenticate::<S, Self, Self::SequencerStakeMeter, _>(
                     tx,
+                    |wallet, state| self.core.multisig_owners(wallet, state),
                     pre_exec_ws,
-                ) {
-                    Ok(res) => return Ok(res),
-                    Err(err) => tracing::error!(%err, "failed to authenticate eth transaction"),
-                }
-
-                sov_modules_api::capabilities::authenticate::<S, Self, Self::SequencerStakeMeter>(
+                )
+            },
+            Auth::Session(tx) => {
+                filament_hub_eth::session::authenticate::<S, Self, Self::SequencerStakeMeter, _>(
                     tx,
+                    |key, call, state| match call {
+                        RuntimeCall::Core(filament_hub_core::CallMessage::ExecuteSession {
+                            key: session_key,
+                            call,
+                        }) if filament_hub_eth::parse_eth_address(session_key)? == *key => self
+                            .core
+                            .session_owner(key, call, state)?
+                            .ok_or(anyhow::anyhow!("session key is not granted")),
+                        _ => anyhow::bail!("session keys can only sign session calls of their own"),
+                    },
                     pre_exec_ws,
                 )
             },
+            Auth::Passkey(tx) => filament_hub_webauthn::authenticate::<
+                S,
+                Self,
+                Self::SequencerStakeMeter,
+            >(tx, pre_exec_ws),
+            Auth::Invalid(_) => Err(untagged_tx_error()),
         }
     }
 
@@ -58,14 +93,22 @@ impl<S: Spec, Da: DaSpec> TransactionAuthenticator<S> for Runtime<S, Da> {
         Self::AuthorizationData,
         UnregisteredAuthenticationError,
     > {
-        let Auth::Mod(contents) = raw_tx;
-
-        let (tx_and_raw_hash, auth_data, runtime_call) =
-            sov_modules_api::capabilities::authenticate::<
+        let (tx_and_raw_hash, auth_data, runtime_call) = match raw_tx {
+            Auth::Standard(contents) => sov_modules_api::capabilities::authenticate::<
+                S,
+                Runtime<S, Da>,
+                UnlimitedGasMeter<S::Gas>,
+            >(contents, pre_exec_ws)?,
+            Auth::Eth(contents) => filament_hub_eth::authenticate::<
                 S,
                 Runtime<S, Da>,
                 UnlimitedGasMeter<S::Gas>,
-            >(contents, pre_exec_ws)?;
+            >(contents, pre_exec_ws)?,
+            Auth::Eip712(_) | Auth::Multisig(_) | Auth::Session(_) | Auth::Passkey(_) => {
+                Err(UnregisteredAuthenticationError::RuntimeCall)?
+            },
+            Auth::Invalid(_) => Err(untagged_tx_error())?,
+        };
 
         match &runtime_call {
             RuntimeCall::SequencerRegistry(sov_sequencer_registry::CallMessage::Register {
@@ -76,13 +119,65 @@ impl<S: Spec, Da: DaSpec> TransactionAuthenticator<S> for Runtime<S, Da> {
     }
 
     fn add_standard_auth(tx: RawTx) -> Self::Input {
-        Auth::Mod(tx.data)
+        // The sequencer can't reject a transaction here, so untagged ones are rejected when they
+        // are authenticated.
+        Auth::from_raw(&tx.data).unwrap_or_else(|err| {
+            tracing::debug!(%err, "Raw transaction is not tagged with an auth scheme");
+            Auth::Invalid(tx.data)
+        })
     }
 }
 
+fn untagged_tx_error() -> AuthenticationError {
+    AuthenticationError::FatalError(FatalError::DeserializationFailed(
+        "transaction is not tagged with an auth scheme".to_string(),
+    ))
+}
+
+/// Transaction tagged with the scheme it is authenticated with. Raw transactions submitted to
+/// the sequencer are the borsh encoding of this enum, a discriminant byte followed by the
+/// length-prefixed transaction.
 #[derive(Debug, PartialEq, Clone, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[borsh(use_discriminant = true)]
+#[repr(u8)]
 pub enum Auth {
-    Mod(Vec<u8>),
+    /// Transaction signed with the rollup's native keys, see [`sov_modules_api::transaction`].
+    Standard(Vec<u8>) = 0,
+    /// Transaction signed with an Ethereum key, see [`filament_hub_eth::Tx`].
+    Eth(Vec<u8>) = 1,
+    /// Transaction signed as EIP-712 typed data, see [`filament_hub_eth::eip712`].
+    Eip712(Vec<u8>) = 2,
+    /// Transaction of a smart contract wallet signed by its owners, see
+    /// [`filament_hub_eth::multisig`].
+    Multisig(Vec<u8>) = 3,
+    /// Transaction signed by a session key, see [`filament_hub_eth::session`].
+    Session(Vec<u8>) = 4,
+    /// Transaction signed with a passkey, see [`filament_hub_webauthn`].
+    Passkey(Vec<u8>) = 5,
+    /// Raw transaction that failed to decode as [`Auth`], always rejected.
+    Invalid(Vec<u8>) = 255,
+}
+
+impl Auth {
+    /// Decodes a raw transaction received by the sequencer, which has to be tagged with the
+    /// scheme it is authenticated with, standard transactions included.
+    pub fn from_raw(raw_tx: &[u8]) -> Result<Self, borsh::io::Error> {
+        Self::try_from_slice(raw_tx)
+    }
+
+    /// Transaction bytes the scheme authenticates. The ledger indexes transactions by the hash
+    /// of these bytes, not of the tagged raw transaction.
+    pub fn payload(&self) -> &[u8] {
+        match self {
+            Auth::Standard(tx)
+            | Auth::Eth(tx)
+            | Auth::Eip712(tx)
+            | Auth::Multisig(tx)
+            | Auth::Session(tx)
+            | Auth::Passkey(tx)
+            | Auth::Invalid(tx) => tx,
+        }
+    }
 }
 
 pub struct ModAuth<S: Spec, Da: DaSpec> {
@@ -5,7 +5,8 @@ extern crate alloc;
 pub use alloc::vec::Vec;
 
 use borsh::BorshSerialize;
-use filament_hub_stf::runtime::RuntimeCall;
+use filament_hub_eth::eip712::TypedData;
+use filament_hub_stf::{authentication::Auth, runtime::RuntimeCall};
 use serde::de::DeserializeOwned;
 use sha2::{Digest as _, Sha256};
 use sov_mock_da::MockDaSpec;
@@ -30,6 +31,8 @@ pub type Address = <ZkSpec as Spec>::Address;
 pub type Call = RuntimeCall<ZkSpec, MockDaSpec>;
 pub type UnsignedTx = UnsignedTransaction<ZkSpec>;
 pub type Tx = filament_hub_eth::Tx<ZkSpec>;
+pub type Eip712Tx = filament_hub_eth::eip712::Eip712Tx<ZkSpec>;
+pub type PasskeyTx = filament_hub_webauthn::PasskeyTx<ZkSpec>;
 
 pub fn set_panic_hook() {
     #[cfg(feature = "console_error_panic_hook")]
@@ -72,7 +75,100 @@ pub fn new_serialized_tx(
         },
     };
 
-    serialize_borsh(&tx)
+    serialize_borsh(&Auth::Eth(serialize_borsh(&tx)?))
+}
+
+/// Typed data to sign with `eth_signTypedData_v4` for the runtime call `call` in JSON.
+#[wasm_bindgen]
+pub fn eip712_typed_data(call: &str, chain_id: u64, nonce: u64) -> Result<String, JsError> {
+    serde_json::from_str::<Call>(call).map_err(JsError::from)?;
+    let call = serde_json::from_str(call).map_err(JsError::from)?;
+    let typed_data = TypedData::transaction(&call, nonce, chain_id, 100, PriorityFeeBips::ZERO.0)
+        .map_err(|e| JsError::new(&e.to_string()))?;
+
+    serde_json::to_string(&typed_data).map_err(JsError::from)
+}
+
+/// Raw transaction for the runtime call `call` in JSON and the signature of its typed data.
+#[wasm_bindgen]
+pub fn new_serialized_eip712_tx(
+    signature: Vec<u8>,
+    call: &str,
+    chain_id: u64,
+    nonce: u64,
+) -> Result<Vec<u8>, JsError> {
+    serde_json::from_str::<Call>(call).map_err(JsError::from)?;
+    let tx = Eip712Tx {
+        signature,
+        call: call.to_string(),
+        nonce,
+        details: TxDetails {
+            chain_id,
+            max_priority_fee_bips: PriorityFeeBips::ZERO,
+            max_fee: 100,
+            gas_limit: None,
+        },
+    };
+
+    serialize_borsh(&Auth::Eip712(serialize_borsh(&tx)?))
+}
+
+/// Base64url challenge to pass to `navigator.credentials.get` for signing a transaction with a
+/// passkey.
+#[wasm_bindgen]
+pub fn passkey_challenge(
+    runtime_msg: Vec<u8>,
+    chain_id: u64,
+    nonce: u64,
+) -> Result<String, JsError> {
+    let unsigned_tx = UnsignedTx::new(
+        runtime_msg,
+        chain_id,
+        PriorityFeeBips::ZERO,
+        100,
+        nonce,
+        None,
+    );
+
+    filament_hub_webauthn::challenge(&unsigned_tx).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Hub address of the passkey with the SEC1 encoded public key `public_key`.
+#[wasm_bindgen]
+pub fn passkey_address(public_key: Vec<u8>) -> Result<String, JsError> {
+    let address = filament_hub_webauthn::passkey_address::<ZkSpec>(&public_key)
+        .map_err(|e| JsError::new(&e.to_string()))?;
+
+    Ok(address.to_string())
+}
+
+/// Raw transaction from the WebAuthn assertion of a passkey over [`passkey_challenge`].
+#[wasm_bindgen]
+pub fn new_serialized_passkey_tx(
+    public_key: Vec<u8>,
+    authenticator_data: Vec<u8>,
+    client_data_json: Vec<u8>,
+    signature: Vec<u8>,
+    runtime_msg: Vec<u8>,
+    chain_id: u64,
+    nonce: u64,
+) -> Result<Vec<u8>, JsError> {
+    let tx = PasskeyTx {
+        public_key,
+        authenticator_data,
+        client_data_json,
+        signature,
+        runtime_msg,
+        nonce,
+        details: TxDetails {
+            chain_id,
+            max_priority_fee_bips: PriorityFeeBips::ZERO,
+            max_fee: 100,
+            gas_limit: None,
+        },
+    };
+
+    serialize_borsh(&Auth::Passkey(serialize_borsh(&tx)?))
 }
 
 #[wasm_bindgen]
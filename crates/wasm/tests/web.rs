@@ -3,7 +3,16 @@
 extern crate wasm_bindgen_test;
 use std::assert_eq;
 
-use filament_hub_wasm::{new_unsigned_tx, serialize_call, tx_hash};
+use filament_hub_wasm::{
+    eip712_typed_data,
+    new_serialized_eip712_tx,
+    new_serialized_passkey_tx,
+    new_serialized_tx,
+    new_unsigned_tx,
+    passkey_challenge,
+    serialize_call,
+    tx_hash,
+};
 use wasm_bindgen::JsValue;
 use wasm_bindgen_test::*;
 
@@ -36,6 +45,14 @@ fn test_new_serialized_unsigned_tx() {
     assert_eq!(utx, UNSIGNED_TX_SERIALIZED);
 }
 
+#[wasm_bindgen_test]
+fn test_new_serialized_tx() {
+    let tx = new_serialized_tx(vec![0u8; 65], RUNTIME_CALL_SERIALIZED.into(), 0, 0)
+        .map_err(JsValue::from)
+        .unwrap();
+    assert_eq!(tx[0], 1);
+}
+
 #[wasm_bindgen_test]
 fn test_serialize_call() {
     let call = serialize_call(RUNTIME_CALL_JSON)
@@ -52,3 +69,55 @@ fn test_tx_hash() {
         "0xf5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a92759fb4b".to_string()
     )
 }
+
+#[wasm_bindgen_test]
+fn test_eip712_typed_data() {
+    let typed_data = eip712_typed_data(RUNTIME_CALL_JSON, 0, 0)
+        .map_err(JsValue::from)
+        .unwrap();
+    let typed_data: serde_json::Value = serde_json::from_str(&typed_data).unwrap();
+
+    assert_eq!(typed_data["primaryType"], "Transaction");
+    assert_eq!(typed_data["domain"]["name"], "Filament Hub");
+    assert_eq!(
+        typed_data["types"]["CallBankFreeze"],
+        serde_json::json!([{ "name": "token_id", "type": "string" }])
+    );
+    assert_eq!(
+        typed_data["message"]["call"]["bank"]["freeze"]["token_id"],
+        "token_1rwrh8gn2py0dl4vv65twgctmlwck6esm2as9dftumcw89kqqn3nqrduss6"
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_new_serialized_eip712_tx() {
+    let tx = new_serialized_eip712_tx(vec![0u8; 65], RUNTIME_CALL_JSON, 0, 0)
+        .map_err(JsValue::from)
+        .unwrap();
+    assert_eq!(tx[0], 2);
+}
+
+#[wasm_bindgen_test]
+fn test_passkey_challenge() {
+    let challenge = passkey_challenge(RUNTIME_CALL_SERIALIZED.into(), 0, 0)
+        .map_err(JsValue::from)
+        .unwrap();
+    assert_eq!(challenge.len(), 43);
+    assert!(!challenge.contains(['+', '/', '=']));
+}
+
+#[wasm_bindgen_test]
+fn test_new_serialized_passkey_tx() {
+    let tx = new_serialized_passkey_tx(
+        vec![],
+        vec![0u8; 37],
+        b"{}".to_vec(),
+        vec![0u8; 64],
+        RUNTIME_CALL_SERIALIZED.into(),
+        0,
+        0,
+    )
+    .map_err(JsValue::from)
+    .unwrap();
+    assert_eq!(tx[0], 5);
+}
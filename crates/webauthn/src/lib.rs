@@ -0,0 +1,226 @@
+//! Transactions signed with a passkey. Passkeys produce WebAuthn assertions, a P-256 signature
+//! over the authenticator data followed by the SHA-256 hash of the client data JSON. The hash of
+//! the unsigned transaction is used as the challenge, so the assertion commits to it.
+use anyhow::{anyhow, bail};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use borsh::BorshDeserialize;
+use filament_hub_eth::{bytes_to_address, hub_addr_to_credential_id, CHAIN_ID};
+use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+use sov_modules_api::{
+    capabilities::{AuthenticationError, AuthenticationResult, AuthorizationData, FatalError},
+    macros::config_value,
+    transaction::{
+        AuthenticatedTransactionAndRawHash,
+        AuthenticatedTransactionData,
+        Credentials,
+        TxDetails,
+        UnsignedTransaction,
+    },
+    CryptoSpec,
+    DispatchCall,
+    GasMeter,
+    MeteredHasher,
+    PreExecWorkingSet,
+    Spec,
+};
+use sov_rollup_interface::TxHash;
+
+/// Relying party id passkeys are registered for. Assertions carry its SHA-256 hash.
+pub const RP_ID: &str = config_value!("PASSKEY_RP_ID");
+
+/// Origin of the frontend passkeys sign from, as reported in the client data.
+pub const ORIGIN: &str = config_value!("PASSKEY_ORIGIN");
+
+/// Client data type of assertions produced by `navigator.credentials.get`.
+pub const ASSERTION_TYPE: &str = "webauthn.get";
+
+/// Flag of the authenticator data set when the user was present.
+const USER_PRESENT: u8 = 0x01;
+
+/// Length of the authenticator data without extensions: rpIdHash, flags and signCount.
+const AUTHENTICATOR_DATA_LEN: usize = 37;
+
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+pub struct PasskeyTx<S: Spec> {
+    /// SEC1 encoded P-256 public key of the passkey credential.
+    pub public_key: Vec<u8>,
+    /// Authenticator data of the assertion.
+    pub authenticator_data: Vec<u8>,
+    /// Client data JSON of the assertion, exactly as returned by the authenticator.
+    pub client_data_json: Vec<u8>,
+    /// DER or fixed size encoded signature of the assertion.
+    pub signature: Vec<u8>,
+    /// The runtime message of the transaction.
+    pub runtime_msg: Vec<u8>,
+    /// The nonce of the transaction.
+    pub nonce: u64,
+    /// The transaction metadata. Contains gas parameters and the chain ID.
+    pub details: TxDetails<S>,
+}
+
+#[derive(serde::Deserialize)]
+struct ClientData {
+    #[serde(rename = "type")]
+    ty: String,
+    challenge: String,
+    origin: String,
+}
+
+impl<S: Spec> PasskeyTx<S> {
+    pub fn to_unsigned_transaction(&self) -> UnsignedTransaction<S> {
+        UnsignedTransaction::new_with_details(
+            self.runtime_msg.clone(),
+            self.nonce,
+            self.details.clone(),
+        )
+    }
+
+    /// Verifies the assertion and returns the public key of the credential that signed it.
+    pub fn verify(&self) -> anyhow::Result<VerifyingKey> {
+        let vk = VerifyingKey::from_sec1_bytes(&self.public_key)
+            .map_err(|_| anyhow!("invalid passkey public key"))?;
+
+        if self.authenticator_data.len() < AUTHENTICATOR_DATA_LEN {
+            bail!("authenticator data is too short");
+        }
+        if self.authenticator_data[..32] != Sha256::digest(RP_ID)[..] {
+            bail!(
+                "authenticator data is not scoped to relying party '{}'",
+                RP_ID
+            );
+        }
+        if self.authenticator_data[32] & USER_PRESENT == 0 {
+            bail!("user was not present");
+        }
+
+        let client_data = serde_json::from_slice::<ClientData>(&self.client_data_json)?;
+        if client_data.ty != ASSERTION_TYPE {
+            bail!(
+                "client data type '{}' is not '{}'",
+                client_data.ty,
+                ASSERTION_TYPE
+            );
+        }
+        if client_data.origin != ORIGIN {
+            bail!(
+                "client data origin '{}' is not '{}'",
+                client_data.origin,
+                ORIGIN
+            );
+        }
+        if client_data.challenge != challenge(&self.to_unsigned_transaction())? {
+            bail!("challenge doesn't match the transaction");
+        }
+
+        let signature = Signature::from_der(&self.signature)
+            .or_else(|_| Signature::from_slice(&self.signature))
+            .map_err(|_| anyhow!("invalid passkey signature"))?;
+        let signature = signature.normalize_s().unwrap_or(signature);
+        vk.verify(&self.signed_data(), &signature)
+            .map_err(|_| anyhow!("passkey signature verification failed"))?;
+
+        Ok(vk)
+    }
+
+    /// Data signed by the authenticator, `authenticatorData || sha256(clientDataJSON)`.
+    fn signed_data(&self) -> Vec<u8> {
+        [
+            &self.authenticator_data[..],
+            &Sha256::digest(&self.client_data_json)[..],
+        ]
+        .concat()
+    }
+}
+
+/// Base64url encoded challenge of the transaction, to be passed to `navigator.credentials.get`.
+pub fn challenge<S: Spec>(unsigned_tx: &UnsignedTransaction<S>) -> anyhow::Result<String> {
+    Ok(URL_SAFE_NO_PAD.encode(Sha256::digest(borsh::to_vec(unsigned_tx)?)))
+}
+
+/// Address bytes of a passkey, the first 20 bytes of the SHA-256 hash of its uncompressed
+/// public key.
+pub fn vk_to_passkey_address(vk: &VerifyingKey) -> [u8; 20] {
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&Sha256::digest(vk.to_encoded_point(false).as_bytes())[..20]);
+    address
+}
+
+/// Hub address of the passkey with the SEC1 encoded public key `public_key`.
+pub fn passkey_address<S: Spec>(public_key: &[u8]) -> anyhow::Result<S::Address> {
+    let vk = VerifyingKey::from_sec1_bytes(public_key)
+        .map_err(|_| anyhow!("invalid passkey public key"))?;
+
+    bytes_to_address::<S>(vk_to_passkey_address(&vk))
+}
+
+pub fn authenticate<S: Spec, D: DispatchCall<Spec = S>, Meter: GasMeter<S::Gas>>(
+    raw_tx: &[u8],
+    state: &mut PreExecWorkingSet<S, Meter>,
+) -> AuthenticationResult<S, D::Decodable, AuthorizationData<S>> {
+    let raw_tx_hash = MeteredHasher::<
+        S::Gas,
+        PreExecWorkingSet<S, Meter>,
+        <S::CryptoSpec as CryptoSpec>::Hasher,
+    >::digest(raw_tx, state)
+    .map(TxHash::new)
+    .map_err(|e| AuthenticationError::Invalid(e.to_string()))?;
+
+    let tx = <PasskeyTx<S> as BorshDeserialize>::try_from_slice(raw_tx).map_err(|e| {
+        AuthenticationError::FatalError(FatalError::DeserializationFailed(e.to_string()))
+    })?;
+
+    if tx.details.chain_id != CHAIN_ID {
+        return Err(AuthenticationError::FatalError(
+            FatalError::InvalidChainId {
+                expected: CHAIN_ID,
+                got: tx.details.chain_id,
+            },
+        ));
+    }
+
+    let vk = tx.verify().map_err(|e| {
+        AuthenticationError::FatalError(FatalError::SigVerificationFailed(e.to_string()))
+    })?;
+
+    let runtime_call = D::decode_call(&tx.runtime_msg, state).map_err(|e| {
+        AuthenticationError::FatalError(FatalError::MessageDecodingFailed(
+            e.to_string(),
+            raw_tx_hash,
+        ))
+    })?;
+
+    let address = bytes_to_address::<S>(vk_to_passkey_address(&vk))
+        .map_err(|e| AuthenticationError::FatalError(FatalError::Other(e.to_string())))?;
+    let credential_id =
+        hub_addr_to_credential_id::<<S::CryptoSpec as CryptoSpec>::Hasher, S>(&address);
+    let credentials = Credentials::new(credential_id);
+
+    Ok((
+        AuthenticatedTransactionAndRawHash {
+            raw_tx_hash,
+            authenticated_tx: AuthenticatedTransactionData {
+                chain_id: tx.details.chain_id,
+                gas_limit: tx.details.gas_limit,
+                max_fee: tx.details.max_fee,
+                max_priority_fee_bips: tx.details.max_priority_fee_bips,
+            },
+        },
+        AuthorizationData {
+            nonce: tx.nonce,
+            credential_id,
+            credentials,
+            default_address: Some(address),
+        },
+        runtime_call,
+    ))
+}
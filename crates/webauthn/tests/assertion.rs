@@ -0,0 +1,217 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use filament_hub_webauthn::{
+    challenge,
+    passkey_address,
+    vk_to_passkey_address,
+    PasskeyTx,
+    ORIGIN,
+    RP_ID,
+};
+use p256::ecdsa::{signature::Signer, DerSignature, SigningKey};
+use sha2::{Digest, Sha256};
+use sov_mock_zkvm::MockZkVerifier;
+use sov_modules_api::{
+    default_spec::DefaultSpec,
+    execution_mode::Zk,
+    transaction::{PriorityFeeBips, TxDetails},
+};
+
+type TestSpec = DefaultSpec<MockZkVerifier, MockZkVerifier, Zk>;
+
+fn authenticator_data(rp_id: &str, flags: u8) -> Vec<u8> {
+    [&Sha256::digest(rp_id)[..], &[flags], &[0, 0, 0, 1]].concat()
+}
+
+fn generate_test_tx(signing_key: &SigningKey) -> PasskeyTx<TestSpec> {
+    PasskeyTx {
+        public_key: signing_key
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec(),
+        authenticator_data: authenticator_data(RP_ID, 0x05),
+        client_data_json: vec![],
+        signature: vec![],
+        runtime_msg: vec![2, 4, 27, 135],
+        nonce: 0,
+        details: TxDetails {
+            chain_id: 0,
+            max_priority_fee_bips: PriorityFeeBips::ZERO,
+            max_fee: 100,
+            gas_limit: None,
+        },
+    }
+}
+
+/// Signs `tx` the way an authenticator does, with `client_data_type`, `challenge` and `origin`
+/// in the client data.
+fn sign_assertion(
+    signing_key: &SigningKey,
+    tx: PasskeyTx<TestSpec>,
+    client_data_type: &str,
+    challenge: &str,
+    origin: &str,
+) -> PasskeyTx<TestSpec> {
+    let client_data_json = format!(
+        r#"{{"type":"{client_data_type}","challenge":"{challenge}","origin":"{origin}","crossOrigin":false}}"#
+    )
+    .into_bytes();
+    let signed_data = [
+        &tx.authenticator_data[..],
+        &Sha256::digest(&client_data_json)[..],
+    ]
+    .concat();
+    let signature: DerSignature = signing_key.sign(&signed_data);
+
+    PasskeyTx {
+        client_data_json,
+        signature: signature.as_bytes().to_vec(),
+        ..tx
+    }
+}
+
+fn generate_signed_tx(signing_key: &SigningKey) -> PasskeyTx<TestSpec> {
+    let tx = generate_test_tx(signing_key);
+    let challenge = challenge(&tx.to_unsigned_transaction()).unwrap();
+
+    sign_assertion(signing_key, tx, "webauthn.get", &challenge, ORIGIN)
+}
+
+#[test]
+fn verify_assertion() {
+    let signing_key = SigningKey::from_slice(&[7u8; 32]).unwrap();
+    let tx = generate_signed_tx(&signing_key);
+
+    let vk = tx.verify().unwrap();
+    assert_eq!(&vk, signing_key.verifying_key());
+
+    // Authenticators may return fixed size signatures as well.
+    let signature = p256::ecdsa::Signature::from_der(&tx.signature).unwrap();
+    let tx = PasskeyTx {
+        signature: signature.to_bytes().to_vec(),
+        ..tx
+    };
+    assert_eq!(&tx.verify().unwrap(), signing_key.verifying_key());
+}
+
+#[test]
+fn passkey_addresses() {
+    let signing_key = SigningKey::from_slice(&[7u8; 32]).unwrap();
+    let vk = signing_key.verifying_key();
+    let compressed = vk.to_encoded_point(true);
+    let uncompressed = vk.to_encoded_point(false);
+
+    assert_eq!(
+        vk_to_passkey_address(vk),
+        Sha256::digest(uncompressed.as_bytes())[..20]
+    );
+    assert_eq!(
+        passkey_address::<TestSpec>(compressed.as_bytes()).unwrap(),
+        passkey_address::<TestSpec>(uncompressed.as_bytes()).unwrap()
+    );
+    assert!(passkey_address::<TestSpec>(&[4u8; 65]).is_err());
+}
+
+#[test]
+fn reject_invalid_assertions() {
+    let signing_key = SigningKey::from_slice(&[7u8; 32]).unwrap();
+    let tx = generate_signed_tx(&signing_key);
+
+    let other_nonce = PasskeyTx {
+        nonce: 1,
+        ..tx.clone()
+    };
+    assert_eq!(
+        other_nonce.verify().unwrap_err().to_string(),
+        "challenge doesn't match the transaction"
+    );
+
+    let other_key = SigningKey::from_slice(&[8u8; 32]).unwrap();
+    let wrong_key = PasskeyTx {
+        public_key: other_key
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec(),
+        ..tx.clone()
+    };
+    assert_eq!(
+        wrong_key.verify().unwrap_err().to_string(),
+        "passkey signature verification failed"
+    );
+
+    let tampered_data = PasskeyTx {
+        authenticator_data: [&tx.authenticator_data[..36], &[2]].concat(),
+        ..tx.clone()
+    };
+    assert_eq!(
+        tampered_data.verify().unwrap_err().to_string(),
+        "passkey signature verification failed"
+    );
+
+    let user_absent = generate_test_tx(&signing_key);
+    let user_absent = PasskeyTx {
+        authenticator_data: authenticator_data(RP_ID, 0x04),
+        ..user_absent
+    };
+    let challenge = challenge(&user_absent.to_unsigned_transaction()).unwrap();
+    let user_absent = sign_assertion(
+        &signing_key,
+        user_absent,
+        "webauthn.get",
+        &challenge,
+        ORIGIN,
+    );
+    assert_eq!(
+        user_absent.verify().unwrap_err().to_string(),
+        "user was not present"
+    );
+
+    let created = sign_assertion(
+        &signing_key,
+        generate_test_tx(&signing_key),
+        "webauthn.create",
+        &challenge,
+        ORIGIN,
+    );
+    assert_eq!(
+        created.verify().unwrap_err().to_string(),
+        "client data type 'webauthn.create' is not 'webauthn.get'"
+    );
+
+    let unencoded_challenge = sign_assertion(
+        &signing_key,
+        generate_test_tx(&signing_key),
+        "webauthn.get",
+        &URL_SAFE_NO_PAD.encode([0u8; 32]),
+        ORIGIN,
+    );
+    assert_eq!(
+        unencoded_challenge.verify().unwrap_err().to_string(),
+        "challenge doesn't match the transaction"
+    );
+
+    // Assertions for another relying party or from another origin can't be replayed.
+    let other_rp = PasskeyTx {
+        authenticator_data: authenticator_data("evil.example", 0x05),
+        ..generate_test_tx(&signing_key)
+    };
+    let challenge = filament_hub_webauthn::challenge(&other_rp.to_unsigned_transaction()).unwrap();
+    let other_rp = sign_assertion(&signing_key, other_rp, "webauthn.get", &challenge, ORIGIN);
+    assert_eq!(
+        other_rp.verify().unwrap_err().to_string(),
+        format!("authenticator data is not scoped to relying party '{RP_ID}'")
+    );
+
+    let other_origin = sign_assertion(
+        &signing_key,
+        generate_test_tx(&signing_key),
+        "webauthn.get",
+        &challenge,
+        "https://evil.example",
+    );
+    assert_eq!(
+        other_origin.verify().unwrap_err().to_string(),
+        format!("client data origin 'https://evil.example' is not '{ORIGIN}'")
+    );
+}